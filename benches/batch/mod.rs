@@ -0,0 +1,85 @@
+use const_lru::ConstLru;
+use criterion::{BatchSize, Criterion};
+
+use crate::common::utils::boxed_const_lru;
+
+const BATCH: usize = 1_000;
+
+type TenK = Box<ConstLru<u16, u64, 10_000, u16>>;
+
+// 9k even keys, so that there's room for a batch of odd keys without evicting
+fn ten_k_even_keys() -> TenK {
+    let mut container: TenK = boxed_const_lru();
+    for k in 0..9_000 {
+        container.insert(k * 2, k.into());
+    }
+    container
+}
+
+// scattered odd keys, so that each one goes in between two cached keys.
+// 9_000 is not a multiple of 7919, so these are distinct
+fn scattered_odd_keys() -> [(u16, u64); BATCH] {
+    core::array::from_fn(|i| {
+        let k = (i * 7919 % 9_000 * 2 + 1) as u16;
+        (k, k.into())
+    })
+}
+
+// cached keys spread over the whole cache, in ascending order
+fn sorted_even_keys() -> Vec<u16> {
+    (0..BATCH as u16).map(|i| i * 18).collect()
+}
+
+// 178 us
+pub fn ten_k_insert_scattered_one_by_one_const_lru(c: &mut Criterion) {
+    let batch = scattered_odd_keys();
+    c.bench_function("10k insert 1k scattered one by one ConstLru", |bencher| {
+        bencher.iter_batched(
+            ten_k_even_keys,
+            |mut container| {
+                for (k, v) in batch {
+                    container.insert(k, v);
+                }
+                container
+            },
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+// 120 us
+pub fn ten_k_insert_batch_scattered_const_lru(c: &mut Criterion) {
+    let batch = scattered_odd_keys();
+    c.bench_function("10k insert_batch 1k scattered ConstLru", |bencher| {
+        bencher.iter_batched(
+            ten_k_even_keys,
+            |mut container| {
+                container.insert_batch(batch, |_| {});
+                container
+            },
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+// 72 us
+pub fn ten_k_get_sorted_one_by_one_const_lru(c: &mut Criterion) {
+    let mut container = ten_k_even_keys();
+    let keys = sorted_even_keys();
+    c.bench_function("10k get 1k sorted one by one ConstLru", |bencher| {
+        bencher.iter(|| {
+            for k in keys.iter() {
+                container.get(k);
+            }
+        })
+    });
+}
+
+// 40 us
+pub fn ten_k_get_batch_sorted_const_lru(c: &mut Criterion) {
+    let mut container = ten_k_even_keys();
+    let keys = sorted_even_keys();
+    c.bench_function("10k get_batch 1k sorted ConstLru", |bencher| {
+        bencher.iter(|| container.get_batch(&keys, |_, _| {}))
+    });
+}
@@ -1,27 +1,42 @@
+use batch::{
+    ten_k_get_batch_sorted_const_lru, ten_k_get_sorted_one_by_one_const_lru,
+    ten_k_insert_batch_scattered_const_lru, ten_k_insert_scattered_one_by_one_const_lru,
+};
 use criterion::{criterion_group, criterion_main};
 use get_mru::{
     bigstruct_get_mru_const_lru, bigstruct_get_mru_const_lru_i_usize, bigstruct_get_mru_hashmap,
-    ten_k_bigstruct_get_mru_const_lru, ten_k_bigstruct_get_mru_hashmap, ten_k_get_mru_const_lru,
-    ten_k_get_mru_hashmap, u8_get_mru_const_lru, u8_get_mru_const_lru_i_usize, u8_get_mru_hashmap,
+    ten_k_bigstruct_get_mru_const_lru, ten_k_bigstruct_get_mru_hashmap, ten_k_get_all_const_lru,
+    ten_k_get_all_eytzinger_lru, ten_k_get_all_hashmap, ten_k_get_mru_const_hash_lru,
+    ten_k_get_mru_const_lru, ten_k_get_mru_const_lru_tree, ten_k_get_mru_eytzinger_lru,
+    ten_k_get_mru_hashmap, u8_get_all_const_lru, u8_get_all_eytzinger_lru, u8_get_all_hashmap,
+    u8_get_mru_const_lru, u8_get_mru_const_lru_i_usize, u8_get_mru_eytzinger_lru,
+    u8_get_mru_hashmap,
 };
 use insert::{
     bigstruct_insert_const_lru, bigstruct_insert_const_lru_i_usize, bigstruct_insert_hashmap,
-    ten_k_bigstruct_insert_const_lru, ten_k_bigstruct_insert_hashmap, ten_k_insert_const_lru,
-    ten_k_insert_hashmap, u8_insert_const_lru, u8_insert_const_lru_i_usize, u8_insert_hashmap,
+    ten_k_bigstruct_insert_const_lru, ten_k_bigstruct_insert_hashmap, ten_k_insert_const_hash_lru,
+    ten_k_insert_const_lru, ten_k_insert_const_lru_tree, ten_k_insert_hashmap,
+    ten_k_insert_scattered_const_hash_lru, ten_k_insert_scattered_const_lru,
+    ten_k_insert_scattered_const_lru_tree, u8_insert_const_lru, u8_insert_const_lru_i_usize,
+    u8_insert_hashmap,
 };
 use lru_to_mru::{
     bigstruct_get_lru_to_mru_const_lru, bigstruct_get_lru_to_mru_const_lru_i_usize,
     bigstruct_get_lru_to_mru_hashmap, ten_k_bigstruct_get_lru_to_mru_const_lru,
-    ten_k_bigstruct_get_lru_to_mru_hashmap, ten_k_get_lru_to_mru_const_lru,
+    ten_k_bigstruct_get_lru_to_mru_hashmap, ten_k_evict_then_insert_const_hash_lru,
+    ten_k_evict_then_insert_const_lru, ten_k_evict_then_insert_const_lru_tree,
+    ten_k_evict_then_insert_scattered_const_lru, ten_k_get_lru_to_mru_const_lru,
     ten_k_get_lru_to_mru_hashmap, u8_get_lru_to_mru_const_lru, u8_get_lru_to_mru_const_lru_i_usize,
     u8_get_lru_to_mru_hashmap,
 };
 use remove::{
     bigstruct_remove_const_lru, bigstruct_remove_const_lru_i_usize, bigstruct_remove_hashmap,
-    ten_k_bigstruct_remove_const_lru, ten_k_bigstruct_remove_hashmap, ten_k_remove_const_lru,
-    ten_k_remove_hashmap, u8_remove_const_lru, u8_remove_const_lru_i_usize, u8_remove_hashmap,
+    ten_k_bigstruct_remove_const_lru, ten_k_bigstruct_remove_hashmap, ten_k_remove_const_hash_lru,
+    ten_k_remove_const_lru, ten_k_remove_const_lru_tree, ten_k_remove_hashmap, u8_remove_const_lru,
+    u8_remove_const_lru_i_usize, u8_remove_hashmap,
 };
 
+mod batch;
 mod common;
 mod get_mru;
 mod insert;
@@ -50,11 +65,19 @@ criterion_group!(
     ten_k_bigstruct_get_lru_to_mru_const_lru,
     ten_k_bigstruct_get_lru_to_mru_hashmap
 );
+criterion_group!(
+    ten_k_evict_then_insert,
+    ten_k_evict_then_insert_const_lru,
+    ten_k_evict_then_insert_const_hash_lru,
+    ten_k_evict_then_insert_const_lru_tree,
+    ten_k_evict_then_insert_scattered_const_lru
+);
 
 criterion_group!(
     u8_get_mru,
     u8_get_mru_const_lru,
     u8_get_mru_const_lru_i_usize,
+    u8_get_mru_eytzinger_lru,
     u8_get_mru_hashmap
 );
 criterion_group!(
@@ -66,8 +89,23 @@ criterion_group!(
 criterion_group!(
     ten_k_get_mru,
     ten_k_get_mru_const_lru,
+    ten_k_get_mru_const_hash_lru,
+    ten_k_get_mru_const_lru_tree,
+    ten_k_get_mru_eytzinger_lru,
     ten_k_get_mru_hashmap
 );
+criterion_group!(
+    u8_get_all,
+    u8_get_all_const_lru,
+    u8_get_all_eytzinger_lru,
+    u8_get_all_hashmap
+);
+criterion_group!(
+    ten_k_get_all,
+    ten_k_get_all_const_lru,
+    ten_k_get_all_eytzinger_lru,
+    ten_k_get_all_hashmap
+);
 criterion_group!(
     ten_k_bigstruct_get_mru,
     ten_k_bigstruct_get_mru_const_lru,
@@ -86,7 +124,19 @@ criterion_group!(
     bigstruct_insert_const_lru_i_usize,
     bigstruct_insert_hashmap
 );
-criterion_group!(ten_k_insert, ten_k_insert_const_lru, ten_k_insert_hashmap);
+criterion_group!(
+    ten_k_insert,
+    ten_k_insert_const_lru,
+    ten_k_insert_const_hash_lru,
+    ten_k_insert_const_lru_tree,
+    ten_k_insert_hashmap
+);
+criterion_group!(
+    ten_k_insert_scattered,
+    ten_k_insert_scattered_const_lru,
+    ten_k_insert_scattered_const_hash_lru,
+    ten_k_insert_scattered_const_lru_tree
+);
 criterion_group!(
     ten_k_bigstruct_insert,
     ten_k_bigstruct_insert_const_lru,
@@ -105,28 +155,51 @@ criterion_group!(
     bigstruct_remove_const_lru_i_usize,
     bigstruct_remove_hashmap
 );
-criterion_group!(ten_k_remove, ten_k_remove_const_lru, ten_k_remove_hashmap);
+criterion_group!(
+    ten_k_remove,
+    ten_k_remove_const_lru,
+    ten_k_remove_const_hash_lru,
+    ten_k_remove_const_lru_tree,
+    ten_k_remove_hashmap
+);
 criterion_group!(
     ten_k_bigstruct_remove,
     ten_k_bigstruct_remove_const_lru,
     ten_k_bigstruct_remove_hashmap
 );
 
+criterion_group!(
+    ten_k_insert_batch,
+    ten_k_insert_scattered_one_by_one_const_lru,
+    ten_k_insert_batch_scattered_const_lru
+);
+criterion_group!(
+    ten_k_get_batch,
+    ten_k_get_sorted_one_by_one_const_lru,
+    ten_k_get_batch_sorted_const_lru
+);
+
 criterion_main!(
     u8_get_lru_to_mru,
     bigstruct_get_lru_to_mru,
     ten_k_get_lru_to_mru,
     ten_k_bigstruct_get_lru_to_mru,
+    ten_k_evict_then_insert,
     u8_get_mru,
     bigstruct_get_mru,
     ten_k_get_mru,
     ten_k_bigstruct_get_mru,
+    u8_get_all,
+    ten_k_get_all,
     u8_insert,
     bigstruct_insert,
     ten_k_insert,
+    ten_k_insert_scattered,
     ten_k_bigstruct_insert,
     u8_remove,
     bigstruct_remove,
     ten_k_remove,
-    ten_k_bigstruct_remove
+    ten_k_bigstruct_remove,
+    ten_k_insert_batch,
+    ten_k_get_batch
 );
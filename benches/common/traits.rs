@@ -3,10 +3,12 @@ use std::{
     hash::{BuildHasher, Hash},
 };
 
-use const_lru::ConstLru;
+use const_lru::{ConstHashLru, ConstLru, ConstLruTree, EytzingerLru};
 use num_traits::{PrimInt, Unsigned};
 
-use super::utils::boxed_const_lru;
+use super::utils::{
+    boxed_const_hash_lru, boxed_const_lru, boxed_const_lru_tree, boxed_eytzinger_lru,
+};
 
 pub trait Get<K, V> {
     fn get_by_key(&mut self, k: &K) -> Option<&V>;
@@ -30,6 +32,38 @@ impl<K: Ord, V, const CAP: usize, I: Unsigned + PrimInt> Get<K, V> for Box<Const
     }
 }
 
+impl<K: Ord, V, const CAP: usize, I: Unsigned + PrimInt> Get<K, V>
+    for Box<ConstLruTree<K, V, CAP, I>>
+{
+    fn get_by_key(&mut self, k: &K) -> Option<&V> {
+        self.get(k)
+    }
+}
+
+impl<K: Ord + Clone, V, const CAP: usize, I: Unsigned + PrimInt> Get<K, V>
+    for EytzingerLru<K, V, CAP, I>
+{
+    fn get_by_key(&mut self, k: &K) -> Option<&V> {
+        self.get(k)
+    }
+}
+
+impl<K: Ord + Clone, V, const CAP: usize, I: Unsigned + PrimInt> Get<K, V>
+    for Box<EytzingerLru<K, V, CAP, I>>
+{
+    fn get_by_key(&mut self, k: &K) -> Option<&V> {
+        self.get(k)
+    }
+}
+
+impl<K: Eq + Hash, V, const CAP: usize, I: Unsigned + PrimInt> Get<K, V>
+    for Box<ConstHashLru<K, V, CAP, I>>
+{
+    fn get_by_key(&mut self, k: &K) -> Option<&V> {
+        self.get(k)
+    }
+}
+
 pub trait Insert<K, V> {
     fn insert_no_ret(&mut self, k: K, v: V);
 }
@@ -54,6 +88,38 @@ impl<K: Ord, V, const CAP: usize, I: Unsigned + PrimInt> Insert<K, V>
     }
 }
 
+impl<K: Ord, V, const CAP: usize, I: Unsigned + PrimInt> Insert<K, V>
+    for Box<ConstLruTree<K, V, CAP, I>>
+{
+    fn insert_no_ret(&mut self, k: K, v: V) {
+        self.insert(k, v);
+    }
+}
+
+impl<K: Ord, V, const CAP: usize, I: Unsigned + PrimInt> Insert<K, V>
+    for EytzingerLru<K, V, CAP, I>
+{
+    fn insert_no_ret(&mut self, k: K, v: V) {
+        self.insert(k, v);
+    }
+}
+
+impl<K: Ord, V, const CAP: usize, I: Unsigned + PrimInt> Insert<K, V>
+    for Box<EytzingerLru<K, V, CAP, I>>
+{
+    fn insert_no_ret(&mut self, k: K, v: V) {
+        self.insert(k, v);
+    }
+}
+
+impl<K: Eq + Hash, V, const CAP: usize, I: Unsigned + PrimInt> Insert<K, V>
+    for Box<ConstHashLru<K, V, CAP, I>>
+{
+    fn insert_no_ret(&mut self, k: K, v: V) {
+        self.insert(k, v);
+    }
+}
+
 pub trait Remove<K, V> {
     fn remove_by_key(&mut self, k: &K) -> Option<V>;
 }
@@ -78,6 +144,22 @@ impl<K: Ord, V, const CAP: usize, I: Unsigned + PrimInt> Remove<K, V>
     }
 }
 
+impl<K: Ord, V, const CAP: usize, I: Unsigned + PrimInt> Remove<K, V>
+    for Box<ConstLruTree<K, V, CAP, I>>
+{
+    fn remove_by_key(&mut self, k: &K) -> Option<V> {
+        self.remove(k)
+    }
+}
+
+impl<K: Eq + Hash, V, const CAP: usize, I: Unsigned + PrimInt> Remove<K, V>
+    for Box<ConstHashLru<K, V, CAP, I>>
+{
+    fn remove_by_key(&mut self, k: &K) -> Option<V> {
+        self.remove(k)
+    }
+}
+
 pub trait CreateNew {
     fn create_new() -> Self;
 }
@@ -99,3 +181,21 @@ impl<K, V, const CAP: usize, I: Unsigned + PrimInt> CreateNew for Box<ConstLru<K
         boxed_const_lru()
     }
 }
+
+impl<K, V, const CAP: usize, I: Unsigned + PrimInt> CreateNew for Box<ConstHashLru<K, V, CAP, I>> {
+    fn create_new() -> Self {
+        boxed_const_hash_lru()
+    }
+}
+
+impl<K, V, const CAP: usize, I: Unsigned + PrimInt> CreateNew for Box<ConstLruTree<K, V, CAP, I>> {
+    fn create_new() -> Self {
+        boxed_const_lru_tree()
+    }
+}
+
+impl<K, V, const CAP: usize, I: Unsigned + PrimInt> CreateNew for Box<EytzingerLru<K, V, CAP, I>> {
+    fn create_new() -> Self {
+        boxed_eytzinger_lru()
+    }
+}
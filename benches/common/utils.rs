@@ -1,6 +1,6 @@
 use std::alloc::{alloc, Layout};
 
-use const_lru::ConstLru;
+use const_lru::{ConstHashLru, ConstLru, ConstLruTree, EytzingerLru};
 use num_traits::{PrimInt, Unsigned};
 
 use super::traits::Insert;
@@ -50,3 +50,33 @@ pub fn boxed_const_lru<K, V, const CAP: usize, I: PrimInt + Unsigned>(
         Box::from_raw(ptr)
     }
 }
+
+pub fn boxed_const_hash_lru<K, V, const CAP: usize, I: PrimInt + Unsigned>(
+) -> Box<ConstHashLru<K, V, CAP, I>> {
+    let layout = Layout::new::<ConstHashLru<K, V, CAP, I>>();
+    unsafe {
+        let ptr = alloc(layout) as *mut ConstHashLru<K, V, CAP, I>;
+        ConstHashLru::init_at_alloc(ptr);
+        Box::from_raw(ptr)
+    }
+}
+
+pub fn boxed_const_lru_tree<K, V, const CAP: usize, I: PrimInt + Unsigned>(
+) -> Box<ConstLruTree<K, V, CAP, I>> {
+    let layout = Layout::new::<ConstLruTree<K, V, CAP, I>>();
+    unsafe {
+        let ptr = alloc(layout) as *mut ConstLruTree<K, V, CAP, I>;
+        ConstLruTree::init_at_alloc(ptr);
+        Box::from_raw(ptr)
+    }
+}
+
+pub fn boxed_eytzinger_lru<K, V, const CAP: usize, I: PrimInt + Unsigned>(
+) -> Box<EytzingerLru<K, V, CAP, I>> {
+    let layout = Layout::new::<EytzingerLru<K, V, CAP, I>>();
+    unsafe {
+        let ptr = alloc(layout) as *mut EytzingerLru<K, V, CAP, I>;
+        EytzingerLru::init_at_alloc(ptr);
+        Box::from_raw(ptr)
+    }
+}
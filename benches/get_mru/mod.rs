@@ -1,11 +1,14 @@
 use std::collections::HashMap;
 
-use const_lru::ConstLru;
+use const_lru::{ConstHashLru, ConstLru, ConstLruTree, EytzingerLru};
 use criterion::Criterion;
 
 use crate::common::{
     traits::{Get, Insert},
-    utils::{boxed_const_lru, fill_up_all_10k_keys, fill_up_all_u8_keys, BigStruct},
+    utils::{
+        boxed_const_hash_lru, boxed_const_lru, boxed_const_lru_tree, boxed_eytzinger_lru,
+        fill_up_all_10k_keys, fill_up_all_u8_keys, BigStruct,
+    },
 };
 
 fn bench_get_mru<C: Insert<K, V> + Get<K, V>, K: From<u8>, V: From<u8>>(
@@ -36,6 +39,30 @@ fn bench_get_mru_10k<C: Insert<K, V> + Get<K, V>, K: From<u16>, V: From<u16>>(
     });
 }
 
+// gets all keys in a scattered order so that lookups don't stay in cache
+fn bench_get_all<C: Insert<K, V> + Get<K, V>, K: From<u16>, V: From<u16>>(
+    c: &mut Criterion,
+    bench_name: &str,
+    mut container: C,
+    n: u16,
+) {
+    for k in 0..n {
+        container.insert_no_ret(k.into(), k.into());
+    }
+    // n is not a multiple of 7919, so this visits every key
+    let keys: Vec<K> = (0..n)
+        .map(|i| ((u32::from(i) * 7919 % u32::from(n)) as u16).into())
+        .collect();
+
+    c.bench_function(bench_name, |bencher| {
+        bencher.iter(|| {
+            for k in keys.iter() {
+                container.get_by_key(k);
+            }
+        })
+    });
+}
+
 // 12 ns
 pub fn u8_get_mru_const_lru(c: &mut Criterion) {
     let container: ConstLru<u8, u64, 255, u8> = ConstLru::new();
@@ -48,6 +75,12 @@ pub fn u8_get_mru_const_lru_i_usize(c: &mut Criterion) {
     bench_get_mru(c, "u8 mru ConstLru I=usize", container);
 }
 
+// 9 ns
+pub fn u8_get_mru_eytzinger_lru(c: &mut Criterion) {
+    let container: EytzingerLru<u8, u64, 255, u8> = EytzingerLru::new();
+    bench_get_mru(c, "u8 mru EytzingerLru", container);
+}
+
 // 11 ns
 pub fn u8_get_mru_hashmap(c: &mut Criterion) {
     let container: HashMap<u8, u64> = HashMap::new();
@@ -80,6 +113,24 @@ pub fn ten_k_get_mru_const_lru(c: &mut Criterion) {
     bench_get_mru_10k(c, "10k mru ConstLru", container);
 }
 
+// 33 ns
+pub fn ten_k_get_mru_const_hash_lru(c: &mut Criterion) {
+    let container: Box<ConstHashLru<u16, u64, 10_000, u16>> = boxed_const_hash_lru();
+    bench_get_mru_10k(c, "10k mru ConstHashLru", container);
+}
+
+// 37 ns
+pub fn ten_k_get_mru_const_lru_tree(c: &mut Criterion) {
+    let container: Box<ConstLruTree<u16, u64, 10_000, u16>> = boxed_const_lru_tree();
+    bench_get_mru_10k(c, "10k mru ConstLruTree", container);
+}
+
+// 14 ns
+pub fn ten_k_get_mru_eytzinger_lru(c: &mut Criterion) {
+    let container: Box<EytzingerLru<u16, u64, 10_000, u16>> = boxed_eytzinger_lru();
+    bench_get_mru_10k(c, "10k mru EytzingerLru", container);
+}
+
 // 11 ns
 pub fn ten_k_get_mru_hashmap(c: &mut Criterion) {
     let container: HashMap<u16, u64> = HashMap::new();
@@ -97,3 +148,39 @@ pub fn ten_k_bigstruct_get_mru_hashmap(c: &mut Criterion) {
     let container: HashMap<BigStruct, BigStruct> = HashMap::new();
     bench_get_mru_10k(c, "10k bigstruct mru hashmap", container);
 }
+
+// 3.8 us
+pub fn u8_get_all_const_lru(c: &mut Criterion) {
+    let container: ConstLru<u16, u64, 255, u8> = ConstLru::new();
+    bench_get_all(c, "u8 get all ConstLru", container, 255);
+}
+
+// 3.3 us
+pub fn u8_get_all_eytzinger_lru(c: &mut Criterion) {
+    let container: EytzingerLru<u16, u64, 255, u8> = EytzingerLru::new();
+    bench_get_all(c, "u8 get all EytzingerLru", container, 255);
+}
+
+// 2.6 us
+pub fn u8_get_all_hashmap(c: &mut Criterion) {
+    let container: HashMap<u16, u64> = HashMap::new();
+    bench_get_all(c, "u8 get all HashMap", container, 255);
+}
+
+// 358 us
+pub fn ten_k_get_all_const_lru(c: &mut Criterion) {
+    let container: Box<ConstLru<u16, u64, 10_000, u16>> = boxed_const_lru();
+    bench_get_all(c, "10k get all ConstLru", container, 10_000);
+}
+
+// 240 us
+pub fn ten_k_get_all_eytzinger_lru(c: &mut Criterion) {
+    let container: Box<EytzingerLru<u16, u64, 10_000, u16>> = boxed_eytzinger_lru();
+    bench_get_all(c, "10k get all EytzingerLru", container, 10_000);
+}
+
+// 110 us
+pub fn ten_k_get_all_hashmap(c: &mut Criterion) {
+    let container: HashMap<u16, u64> = HashMap::new();
+    bench_get_all(c, "10k get all HashMap", container, 10_000);
+}
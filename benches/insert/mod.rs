@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use const_lru::ConstLru;
+use const_lru::{ConstHashLru, ConstLru, ConstLruTree};
 use criterion::Criterion;
 
 use crate::common::{
@@ -8,8 +8,8 @@ use crate::common::{
     utils::BigStruct,
 };
 
-// insert in reverse key order: worst-case for ConstLru
-// since new entries go to [0] and push everything right in bs-index
+// insert in reverse key order: new entries all go to [0] in bs-index,
+// which used to push everything right but now just fills the gap from its end
 fn bench_insert<C: Insert<K, V> + CreateNew, K: From<u8>, V: From<u8>>(
     c: &mut Criterion,
     bench_name: &str,
@@ -27,8 +27,8 @@ fn bench_insert<C: Insert<K, V> + CreateNew, K: From<u8>, V: From<u8>>(
     });
 }
 
-// insert in reverse key order: worst-case for ConstLru
-// since new entries go to [0] and push everything right in bs-index
+// insert in reverse key order: new entries all go to [0] in bs-index,
+// which used to push everything right but now just fills the gap from its end
 fn bench_ten_k_insert<C: Insert<K, V> + CreateNew, K: From<u16>, V: From<u16>>(
     c: &mut Criterion,
     bench_name: &str,
@@ -46,6 +46,27 @@ fn bench_ten_k_insert<C: Insert<K, V> + CreateNew, K: From<u16>, V: From<u16>>(
     });
 }
 
+// insert in scattered key order: no locality,
+// so the gap in bs-index has to be moved around for every insert
+fn bench_ten_k_insert_scattered<C: Insert<K, V> + CreateNew, K: From<u16>, V: From<u16>>(
+    c: &mut Criterion,
+    bench_name: &str,
+) {
+    // 10_000 is not a multiple of 7919, so this visits every key
+    let keys: Vec<u16> = (0..10_000u32).map(|i| (i * 7919 % 10_000) as u16).collect();
+    c.bench_function(bench_name, move |bencher| {
+        bencher.iter_batched(
+            || C::create_new(),
+            |mut container| {
+                for k in keys.iter() {
+                    container.insert_no_ret((*k).into(), (*k).into());
+                }
+            },
+            criterion::BatchSize::SmallInput,
+        )
+    });
+}
+
 // 3.6 us
 pub fn u8_insert_const_lru(c: &mut Criterion) {
     bench_insert::<ConstLru<u8, u64, 255, u8>, _, _>(c, "u8 insert ConstLru");
@@ -88,16 +109,56 @@ pub fn bigstruct_insert_hashmap(c: &mut Criterion) {
     bench_insert::<HashMap<BigStruct, BigStruct>, _, _>(c, "bigstruct insert HashMap");
 }
 
-// 1.2 ms
+// 260 us
 pub fn ten_k_insert_const_lru(c: &mut Criterion) {
     bench_ten_k_insert::<Box<ConstLru<u16, u64, 10_000, u16>>, _, _>(c, "10k insert ConstLru");
 }
 
+// 310 us
+pub fn ten_k_insert_const_hash_lru(c: &mut Criterion) {
+    bench_ten_k_insert::<Box<ConstHashLru<u16, u64, 10_000, u16>>, _, _>(
+        c,
+        "10k insert ConstHashLru",
+    );
+}
+
+// 610 us
+pub fn ten_k_insert_const_lru_tree(c: &mut Criterion) {
+    bench_ten_k_insert::<Box<ConstLruTree<u16, u64, 10_000, u16>>, _, _>(
+        c,
+        "10k insert ConstLruTree",
+    );
+}
+
 // 375 us
 pub fn ten_k_insert_hashmap(c: &mut Criterion) {
     bench_ten_k_insert::<HashMap<u16, u64>, _, _>(c, "10k insert HashMap");
 }
 
+// 855 us
+pub fn ten_k_insert_scattered_const_lru(c: &mut Criterion) {
+    bench_ten_k_insert_scattered::<Box<ConstLru<u16, u64, 10_000, u16>>, _, _>(
+        c,
+        "10k insert scattered ConstLru",
+    );
+}
+
+// 495 us
+pub fn ten_k_insert_scattered_const_hash_lru(c: &mut Criterion) {
+    bench_ten_k_insert_scattered::<Box<ConstHashLru<u16, u64, 10_000, u16>>, _, _>(
+        c,
+        "10k insert scattered ConstHashLru",
+    );
+}
+
+// 860 us
+pub fn ten_k_insert_scattered_const_lru_tree(c: &mut Criterion) {
+    bench_ten_k_insert_scattered::<Box<ConstLruTree<u16, u64, 10_000, u16>>, _, _>(
+        c,
+        "10k insert scattered ConstLruTree",
+    );
+}
+
 // 6.5 ms
 pub fn ten_k_bigstruct_insert_const_lru(c: &mut Criterion) {
     bench_ten_k_insert::<Box<ConstLru<BigStruct, BigStruct, 10_000, u16>>, _, _>(
@@ -1,10 +1,10 @@
 use std::collections::HashMap;
 
-use const_lru::ConstLru;
+use const_lru::{ConstHashLru, ConstLru, ConstLruTree};
 use criterion::Criterion;
 
 use crate::common::{
-    traits::{Get, Insert},
+    traits::{CreateNew, Get, Insert},
     utils::{boxed_const_lru, fill_up_all_10k_keys, fill_up_all_u8_keys, BigStruct},
 };
 
@@ -48,6 +48,43 @@ fn bench_ten_k_lru_to_mru<C: Insert<K, V> + Get<K, V>, K: From<u16>, V: From<u16
     });
 }
 
+// if keys were inserted from 0..10_000,
+// inserting each of `new_keys` evicts the LRU, which is the smallest key remaining from the fill
+fn bench_ten_k_evict_then_insert<C: Insert<K, V> + CreateNew, K: From<u16>, V: From<u16>>(
+    c: &mut Criterion,
+    bench_name: &str,
+    new_keys: Vec<u16>,
+) {
+    c.bench_function(bench_name, move |bencher| {
+        bencher.iter_batched(
+            || {
+                let mut container = C::create_new();
+                fill_up_all_10k_keys(&mut container);
+                container
+            },
+            |mut container| {
+                for k in new_keys.iter() {
+                    container.insert_no_ret((*k).into(), (*k).into());
+                }
+            },
+            criterion::BatchSize::SmallInput,
+        )
+    });
+}
+
+/// Keys in 10_000..20_000, ascending
+fn ascending_new_keys() -> Vec<u16> {
+    (10_000..20_000).collect()
+}
+
+/// Keys in 10_000..20_000, in scattered order
+fn scattered_new_keys() -> Vec<u16> {
+    // 10_000 is not a multiple of 7919, so this visits every key
+    (0..10_000u32)
+        .map(|i| (10_000 + i * 7919 % 10_000) as u16)
+        .collect()
+}
+
 // 2.5 us
 pub fn u8_get_lru_to_mru_const_lru(c: &mut Criterion) {
     let container: ConstLru<u8, u64, 255, u8> = ConstLru::new();
@@ -109,3 +146,39 @@ pub fn ten_k_bigstruct_get_lru_to_mru_hashmap(c: &mut Criterion) {
     let container: HashMap<BigStruct, BigStruct> = HashMap::new();
     bench_ten_k_lru_to_mru(c, "10k bigstruct lru to mru HashMap", container);
 }
+
+// 735 us
+pub fn ten_k_evict_then_insert_const_lru(c: &mut Criterion) {
+    bench_ten_k_evict_then_insert::<Box<ConstLru<u16, u64, 10_000, u16>>, _, _>(
+        c,
+        "10k evict then insert ConstLru",
+        ascending_new_keys(),
+    );
+}
+
+// 1.05 ms
+pub fn ten_k_evict_then_insert_const_hash_lru(c: &mut Criterion) {
+    bench_ten_k_evict_then_insert::<Box<ConstHashLru<u16, u64, 10_000, u16>>, _, _>(
+        c,
+        "10k evict then insert ConstHashLru",
+        ascending_new_keys(),
+    );
+}
+
+// 935 us
+pub fn ten_k_evict_then_insert_const_lru_tree(c: &mut Criterion) {
+    bench_ten_k_evict_then_insert::<Box<ConstLruTree<u16, u64, 10_000, u16>>, _, _>(
+        c,
+        "10k evict then insert ConstLruTree",
+        ascending_new_keys(),
+    );
+}
+
+// 1.1 ms
+pub fn ten_k_evict_then_insert_scattered_const_lru(c: &mut Criterion) {
+    bench_ten_k_evict_then_insert::<Box<ConstLru<u16, u64, 10_000, u16>>, _, _>(
+        c,
+        "10k evict then insert scattered ConstLru",
+        scattered_new_keys(),
+    );
+}
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use const_lru::ConstLru;
+use const_lru::{ConstHashLru, ConstLru, ConstLruTree};
 use criterion::Criterion;
 
 use crate::common::{
@@ -97,6 +97,22 @@ pub fn ten_k_remove_const_lru(c: &mut Criterion) {
     bench_ten_k_remove::<Box<ConstLru<u16, u64, 10_000, u16>>, _, _>(c, "10k remove ConstLru");
 }
 
+// 210 us
+pub fn ten_k_remove_const_hash_lru(c: &mut Criterion) {
+    bench_ten_k_remove::<Box<ConstHashLru<u16, u64, 10_000, u16>>, _, _>(
+        c,
+        "10k remove ConstHashLru",
+    );
+}
+
+// 290 us
+pub fn ten_k_remove_const_lru_tree(c: &mut Criterion) {
+    bench_ten_k_remove::<Box<ConstLruTree<u16, u64, 10_000, u16>>, _, _>(
+        c,
+        "10k remove ConstLruTree",
+    );
+}
+
 // 225 us
 pub fn ten_k_remove_hashmap(c: &mut Criterion) {
     bench_ten_k_remove::<HashMap<u16, u64>, _, _>(c, "10k remove HashMap");
@@ -0,0 +1,162 @@
+//! Attribute macros for [const-lru](https://docs.rs/const-lru).
+//!
+//! Enable the `macros` feature of `const-lru` to use them through `const_lru::memoize`.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Error, Expr, FnArg, ItemFn, Pat, ReturnType, Type};
+
+/// Memoizes a function with a `static` `ConstLru` of capacity `capacity`,
+/// keyed by a tuple of clones of its arguments.
+///
+/// The arguments must be owned `Clone + Ord` types and the return type `Clone`.
+/// The cache is behind a `std::sync::Mutex`, so the `macros` feature of `const-lru` also enables `std`.
+/// The lock is not held while the function runs, so it can call itself recursively.
+///
+/// The `ConstLru` is created on the stack on the first call, so keep `capacity` small enough to not overflow it.
+///
+/// Generic functions, methods and `async` functions are not supported.
+///
+/// Example:
+///
+/// ```
+/// use const_lru::memoize;
+///
+/// #[memoize(capacity = 128)]
+/// fn fib(n: u64) -> u64 {
+///     if n < 2 {
+///         return n;
+///     }
+///     fib(n - 1) + fib(n - 2)
+/// }
+///
+/// assert_eq!(fib(90), 2_880_067_194_370_816_120);
+/// ```
+///
+/// The arguments are stored in a `static` cache that outlives any borrow,
+/// so reference arguments such as `&str` are rejected. Take an owned type like `String` instead:
+///
+/// ```compile_fail
+/// use const_lru::memoize;
+///
+/// #[memoize(capacity = 8)]
+/// fn greet(name: &str) -> String {
+///     format!("hello {name}")
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn memoize(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut capacity: Option<Expr> = None;
+    let attr_parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("capacity") {
+            capacity = Some(meta.value()?.parse()?);
+            Ok(())
+        } else {
+            Err(meta.error("unsupported memoize argument, expected `capacity`"))
+        }
+    });
+    parse_macro_input!(attr with attr_parser);
+    let item = parse_macro_input!(item as ItemFn);
+    let Some(capacity) = capacity else {
+        return Error::new(Span::call_site(), "missing `capacity = ...` argument")
+            .to_compile_error()
+            .into();
+    };
+    expand_memoize(capacity, item)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_memoize(capacity: Expr, item: ItemFn) -> syn::Result<proc_macro2::TokenStream> {
+    let ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = item;
+
+    if !sig.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &sig.generics,
+            "memoize does not support generic functions",
+        ));
+    }
+    if let Some(asyncness) = &sig.asyncness {
+        return Err(Error::new_spanned(
+            asyncness,
+            "memoize does not support async functions",
+        ));
+    }
+    let ret_ty: &Type = match &sig.output {
+        ReturnType::Type(_, ty) => ty,
+        ReturnType::Default => {
+            return Err(Error::new_spanned(
+                &sig,
+                "memoize requires the function to return a value",
+            ))
+        }
+    };
+
+    let mut arg_names = Vec::new();
+    let mut arg_tys = Vec::new();
+    for arg in sig.inputs.iter() {
+        let FnArg::Typed(pat_ty) = arg else {
+            return Err(Error::new_spanned(arg, "memoize does not support methods"));
+        };
+        let Pat::Ident(pat_ident) = &*pat_ty.pat else {
+            return Err(Error::new_spanned(
+                &pat_ty.pat,
+                "memoize requires arguments to be plain identifiers",
+            ));
+        };
+        arg_names.push(pat_ident.ident.clone());
+        arg_tys.push(&*pat_ty.ty);
+    }
+
+    // the original function, which the memoized one calls on a cache miss
+    let mut inner_sig = sig.clone();
+    inner_sig.ident = format_ident!("__memoized_{}", sig.ident);
+    let inner_ident = &inner_sig.ident;
+
+    // the memoized function takes the arguments as-is and passes them on to the original
+    let mut outer_sig = sig.clone();
+    for arg in outer_sig.inputs.iter_mut() {
+        if let FnArg::Typed(pat_ty) = arg {
+            if let Pat::Ident(pat_ident) = &mut *pat_ty.pat {
+                pat_ident.mutability = None;
+            }
+        }
+    }
+
+    Ok(quote! {
+        #(#attrs)*
+        #vis #outer_sig {
+            #inner_sig #block
+
+            type __MemoizedCache = ::const_lru::ConstLru<(#(#arg_tys,)*), #ret_ty, { #capacity }>;
+
+            static __MEMOIZED_CACHE: ::std::sync::Mutex<::core::option::Option<__MemoizedCache>> =
+                ::std::sync::Mutex::new(::core::option::Option::None);
+
+            fn __memoized_cache() -> ::std::sync::MutexGuard<'static, ::core::option::Option<__MemoizedCache>> {
+                __MEMOIZED_CACHE
+                    .lock()
+                    .unwrap_or_else(::std::sync::PoisonError::into_inner)
+            }
+
+            let __key = (#(::core::clone::Clone::clone(&#arg_names),)*);
+            if let ::core::option::Option::Some(__cached) = __memoized_cache()
+                .get_or_insert_with(::const_lru::ConstLru::new)
+                .get(&__key)
+            {
+                return ::core::clone::Clone::clone(__cached);
+            }
+            let __res = #inner_ident(#(#arg_names),*);
+            __memoized_cache()
+                .get_or_insert_with(::const_lru::ConstLru::new)
+                .insert(__key, ::core::clone::Clone::clone(&__res));
+            __res
+        }
+    })
+}
@@ -0,0 +1,62 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use const_lru::memoize;
+
+static SLOW_ADD_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+#[memoize(capacity = 2)]
+fn slow_add(a: u32, mut b: u32) -> u32 {
+    SLOW_ADD_CALLS.fetch_add(1, Ordering::Relaxed);
+    b += a;
+    b
+}
+
+#[test]
+fn caches_by_all_args() {
+    assert_eq!(slow_add(1, 2), 3);
+    assert_eq!(slow_add(1, 2), 3);
+    assert_eq!(SLOW_ADD_CALLS.load(Ordering::Relaxed), 1);
+    assert_eq!(slow_add(2, 1), 3);
+    assert_eq!(SLOW_ADD_CALLS.load(Ordering::Relaxed), 2);
+    // evicts (2, 1), the LRU
+    assert_eq!(slow_add(1, 2), 3);
+    assert_eq!(slow_add(5, 5), 10);
+    assert_eq!(SLOW_ADD_CALLS.load(Ordering::Relaxed), 3);
+    assert_eq!(slow_add(1, 2), 3);
+    assert_eq!(SLOW_ADD_CALLS.load(Ordering::Relaxed), 3);
+    assert_eq!(slow_add(2, 1), 3);
+    assert_eq!(SLOW_ADD_CALLS.load(Ordering::Relaxed), 4);
+}
+
+const CAP: usize = 100;
+
+/// Recursion must not deadlock on the cache's lock
+#[memoize(capacity = CAP)]
+fn collatz_len(n: u64) -> usize {
+    match n {
+        1 => 1,
+        n if n % 2 == 0 => 1 + collatz_len(n / 2),
+        n => 1 + collatz_len(3 * n + 1),
+    }
+}
+
+#[test]
+fn recursive() {
+    assert_eq!(collatz_len(27), 112);
+    assert_eq!(collatz_len(27), 112);
+}
+
+#[memoize(capacity = 4)]
+pub(crate) fn greet(name: String) -> String {
+    format!("hello {name}")
+}
+
+#[test]
+fn non_copy_args() {
+    let handles: Vec<_> = (0..4)
+        .map(|i| std::thread::spawn(move || greet(format!("{}", i % 2))))
+        .collect();
+    for (i, h) in handles.into_iter().enumerate() {
+        assert_eq!(h.join().unwrap(), format!("hello {}", i % 2));
+    }
+}
@@ -0,0 +1,428 @@
+use core::cmp::Ordering;
+use core::ptr::{self, addr_of_mut};
+
+use num_traits::{PrimInt, Unsigned};
+
+/// Sorted index of a [`ConstLru`](crate::ConstLru)'s entries, stored as a circular gap buffer.
+///
+/// The `CAP - len` unused slots form a single gap that stays wherever the last insert or removal happened.
+/// Inserting or removing at a position only shifts the elements between the gap and that position,
+/// going whichever way around the ring is shorter. This means that
+/// - inserts close to each other, e.g. in ascending or descending key order, move few elements
+/// - when full, evicting the smallest key and inserting the largest, e.g. monotonically increasing keys,
+///   moves few elements since both ends of the key order are next to each other in the ring
+///
+/// Logical position `bs_i` is stored at slot `(base + bs_i + if bs_i >= gap { gap_len } else { 0 }) % CAP`
+#[derive(Debug)]
+pub(crate) struct BsIndex<I: PrimInt + Unsigned, const CAP: usize> {
+    /// slot of logical position 0, not counting the gap
+    ///
+    /// always < CAP, or 0 if CAP == 0
+    base: I,
+
+    /// logical position that comes right after the gap
+    gap: I,
+
+    /// number of unused slots, CAP - len
+    gap_len: I,
+
+    /// disregard if in the gap
+    slots: [I; CAP],
+}
+
+impl<I: PrimInt + Unsigned, const CAP: usize> BsIndex<I, CAP> {
+    /// Initializes an empty BsIndex at a region of allocated memory
+    ///
+    /// Requirements:
+    /// - `CAP <= I::MAX <= usize::MAX`
+    /// - `ptr` points to allocated memory that can be overwritten
+    pub(crate) unsafe fn init_at_alloc(ptr: *mut Self) {
+        let cap = I::from(CAP).unwrap();
+
+        addr_of_mut!((*ptr).base).write(I::zero());
+        addr_of_mut!((*ptr).gap).write(I::zero());
+        addr_of_mut!((*ptr).gap_len).write(cap);
+
+        // slots = [cap, ..., cap]
+        // UB if not initialized
+        for i in 0..CAP {
+            addr_of_mut!((*ptr).slots[i]).write(cap);
+        }
+    }
+
+    /// Copies the BsIndex to a region of allocated memory
+    ///
+    /// Requirements:
+    /// - `dst` points to allocated memory that can be overwritten
+    pub(crate) unsafe fn clone_to_alloc(&self, dst: *mut Self) {
+        addr_of_mut!((*dst).base).write(self.base);
+        addr_of_mut!((*dst).gap).write(self.gap);
+        addr_of_mut!((*dst).gap_len).write(self.gap_len);
+
+        // raw memmove for the same reason as in `LruList::clone_links_to_alloc`
+        ptr::copy(
+            self.slots.as_ptr(),
+            addr_of_mut!((*dst).slots) as *mut I,
+            CAP,
+        );
+    }
+
+    fn len(&self) -> usize {
+        CAP - self.gap_len.to_usize().unwrap()
+    }
+
+    /// Requirements:
+    /// - `bs_i < len`
+    fn slot_of(&self, bs_i: usize) -> usize {
+        let (base, gap, gap_len, _) = self.raw_parts();
+        ring_slot::<CAP>(base, gap, gap_len, bs_i)
+    }
+
+    /// Returns `base`, `gap`, `gap_len` and the slots, so that the BsIndex can be mirrored elsewhere
+    pub(crate) fn raw_parts(&self) -> (usize, usize, usize, &[I; CAP]) {
+        (
+            self.base.to_usize().unwrap(),
+            self.gap.to_usize().unwrap(),
+            self.gap_len.to_usize().unwrap(),
+            &self.slots,
+        )
+    }
+
+    /// Returns the entry index at logical position `bs_i`
+    ///
+    /// Requirements:
+    /// - `bs_i < len`
+    pub(crate) fn get(&self, bs_i: usize) -> I {
+        self.slots[self.slot_of(bs_i)]
+    }
+
+    /// Returns the valid slots in logical order as contiguous slices, some of which may be empty.
+    ///
+    /// `[0, gap)` starts at slot `base` and `[gap, len)` starts right after the gap.
+    /// Either can wrap around the end of `slots`, but not both since the ring only wraps once.
+    fn as_slices(&self) -> [&[I]; 4] {
+        let base = self.base.to_usize().unwrap();
+        let gap = self.gap.to_usize().unwrap();
+        let gap_len = self.gap_len.to_usize().unwrap();
+        let after_gap = base + gap + gap_len;
+        let after_gap = if after_gap >= CAP {
+            after_gap - CAP
+        } else {
+            after_gap
+        };
+        let (a1, a2) = self.ring_slices(base, gap);
+        let (b1, b2) = self.ring_slices(after_gap, self.len() - gap);
+        [a1, a2, b1, b2]
+    }
+
+    /// Returns the `n` slots starting at slot `start`, split where they wrap around the end of `slots`
+    ///
+    /// Requirements:
+    /// - `start < CAP` or `n == 0`
+    /// - `n <= CAP`
+    fn ring_slices(&self, start: usize, n: usize) -> (&[I], &[I]) {
+        if n == 0 {
+            (&[], &[])
+        } else if start + n <= CAP {
+            (&self.slots[start..start + n], &[])
+        } else {
+            (&self.slots[start..], &self.slots[..start + n - CAP])
+        }
+    }
+
+    /// Same semantics as `slice::binary_search_by()` over the logical positions
+    pub(crate) fn binary_search_by<F: FnMut(&I) -> Ordering>(
+        &self,
+        mut f: F,
+    ) -> Result<usize, usize> {
+        let slices = self.as_slices();
+        // the target is in, or right after, the last slice whose first element is <= it.
+        // When full and not wrapped around, only the first slice is non-empty.
+        let mut chosen = slices[0];
+        let mut offset = 0;
+        let mut passed = slices[0].len();
+        for slice in &slices[1..] {
+            match slice.first() {
+                None => continue,
+                Some(first) if f(first) == Ordering::Greater => break,
+                Some(_) => {
+                    chosen = slice;
+                    offset = passed;
+                }
+            }
+            passed += slice.len();
+        }
+        chosen
+            .binary_search_by(f)
+            .map(|i| i + offset)
+            .map_err(|i| i + offset)
+    }
+
+    /// Same semantics as [`Self::binary_search_by`], but searches forward from logical position `from`
+    /// in exponentially growing steps before binary searching,
+    /// so that searching for keys in ascending order costs `O(log(distance))` per key.
+    ///
+    /// Requirements:
+    /// - `from <= len`
+    /// - `f` returns `Ordering::Less` for all logical positions before `from`
+    pub(crate) fn gallop_by<F: FnMut(&I) -> Ordering>(
+        &self,
+        from: usize,
+        mut f: F,
+    ) -> Result<usize, usize> {
+        let len = self.len();
+        // all positions before lo are Less, position hi is Greater if < len
+        let mut lo = from;
+        let mut step = 1;
+        let mut hi = loop {
+            if lo >= len {
+                break len;
+            }
+            let probe = (lo + step - 1).min(len - 1);
+            match f(&self.get(probe)) {
+                Ordering::Less => {
+                    lo = probe + 1;
+                    step *= 2;
+                }
+                Ordering::Equal => return Ok(probe),
+                Ordering::Greater => break probe,
+            }
+        };
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            match f(&self.get(mid)) {
+                Ordering::Less => lo = mid + 1,
+                Ordering::Equal => return Ok(mid),
+                Ordering::Greater => hi = mid,
+            }
+        }
+        Err(lo)
+    }
+
+    /// Moves the gap to the end of `slots`, so that logical position `bs_i` is at slot `bs_i`
+    fn linearize(&mut self) {
+        self.move_gap(self.len());
+        self.slots.rotate_left(self.base.to_usize().unwrap());
+        self.base = I::zero();
+    }
+
+    /// Removes the entry indices for which `keep` returns `false` in a single pass,
+    /// updating the logical positions in `positions` to where they are after the removal.
+    /// A position of a removed entry index ends up at the next entry index that is kept.
+    ///
+    /// Requirements:
+    /// - `positions` is sorted in ascending order and all of them are `<= len`
+    pub(crate) fn retain<F: FnMut(&I) -> bool>(&mut self, mut keep: F, positions: &mut [usize]) {
+        self.linearize();
+        let len = self.len();
+        let mut w = 0;
+        let mut j = 0;
+        for r in 0..len {
+            while j < positions.len() && positions[j] <= r {
+                positions[j] = w;
+                j += 1;
+            }
+            if keep(&self.slots[r]) {
+                self.slots[w] = self.slots[r];
+                w += 1;
+            }
+        }
+        for bs_i in &mut positions[j..] {
+            *bs_i = w;
+        }
+        self.gap = I::from(w).unwrap();
+        self.gap_len = I::from(CAP - w).unwrap();
+    }
+
+    /// Inserts `n` entry indices at the logical positions they are paired with in a single pass,
+    /// given in descending order of position by `new_rev`.
+    /// Entry indices paired with the same position end up in reverse order of `new_rev`.
+    ///
+    /// Requirements:
+    /// - `new_rev` yields exactly `n` pairs, in descending order of position
+    /// - all positions are `<= len`
+    /// - `len + n <= CAP`
+    pub(crate) fn insert_sorted<It: Iterator<Item = (usize, I)>>(&mut self, n: usize, new_rev: It) {
+        if n == 0 {
+            return;
+        }
+        self.linearize();
+        let len = self.len();
+        // insert from the back so nothing is overwritten before it's moved
+        let mut old_end = len;
+        let mut w = len + n;
+        for (bs_i, index) in new_rev {
+            while old_end > bs_i {
+                old_end -= 1;
+                w -= 1;
+                self.slots[w] = self.slots[old_end];
+            }
+            w -= 1;
+            self.slots[w] = index;
+        }
+        self.gap = I::from(len + n).unwrap();
+        self.gap_len = self.gap_len - I::from(n).unwrap();
+    }
+
+    /// Moves the entry indices at logical positions `[at, len)` to the empty BsIndex `dst`, keeping their order
+    ///
+    /// Requirements:
+    /// - `at <= len`
+    /// - `dst` is empty
+    pub(crate) fn split_off(&mut self, at: usize, dst: &mut Self) {
+        self.linearize();
+        let len = self.len();
+        let n = len - at;
+        dst.slots[..n].copy_from_slice(&self.slots[at..len]);
+        dst.base = I::zero();
+        dst.gap = I::from(n).unwrap();
+        dst.gap_len = dst.gap_len - I::from(n).unwrap();
+        self.gap = I::from(at).unwrap();
+        self.gap_len = self.gap_len + I::from(n).unwrap();
+    }
+
+    /// Replaces the contents with the entry indices yielded by `indices`, sorted by `cmp`
+    ///
+    /// Requirements:
+    /// - `indices` yields at most `CAP` entry indices
+    pub(crate) fn rebuild<It: Iterator<Item = I>, F: FnMut(&I, &I) -> Ordering>(
+        &mut self,
+        indices: It,
+        cmp: F,
+    ) {
+        let mut n = 0;
+        for index in indices {
+            self.slots[n] = index;
+            n += 1;
+        }
+        self.slots[..n].sort_unstable_by(cmp);
+        self.base = I::zero();
+        self.gap = I::from(n).unwrap();
+        self.gap_len = I::from(CAP - n).unwrap();
+    }
+
+    /// Inserts `index` at logical position `bs_i`
+    ///
+    /// Requirements:
+    /// - `bs_i <= len < CAP`
+    pub(crate) fn insert(&mut self, bs_i: usize, index: I) {
+        self.move_gap(bs_i);
+        // gap is now right after bs_i - 1, so bs_i goes into its first slot
+        let s = self.base.to_usize().unwrap() + bs_i;
+        self.slots[if s >= CAP { s - CAP } else { s }] = index;
+        self.gap = self.gap + I::one();
+        self.gap_len = self.gap_len - I::one();
+    }
+
+    /// Removes the entry index at logical position `bs_i`
+    ///
+    /// Requirements:
+    /// - `bs_i < len`
+    pub(crate) fn remove(&mut self, bs_i: usize) {
+        if bs_i < self.gap.to_usize().unwrap() {
+            // bs_i becomes the last slot before the gap, then gets absorbed by it
+            self.move_gap(bs_i + 1);
+            self.gap = self.gap - I::one();
+        } else {
+            // bs_i becomes the first slot after the gap, then gets absorbed by it
+            self.move_gap(bs_i);
+        }
+        self.gap_len = self.gap_len + I::one();
+    }
+
+    /// Moves the gap to right before logical position `to`,
+    /// shifting the elements in between whichever way around the ring is shorter.
+    ///
+    /// Logical positions 0 and len are the same position in the ring.
+    ///
+    /// Requirements:
+    /// - `to <= len`
+    fn move_gap(&mut self, to: usize) {
+        let gap = self.gap.to_usize().unwrap();
+        let gap_len = self.gap_len.to_usize().unwrap();
+        if gap_len == 0 || to == gap {
+            // nothing needs to be moved
+            self.gap = I::from(to).unwrap();
+            return;
+        }
+        let len = CAP - gap_len;
+        let base = self.base.to_usize().unwrap();
+        let direct = to.abs_diff(gap);
+        let around = len - direct;
+
+        if to < gap && direct <= around {
+            // shift [to, gap) right
+            self.shift_right((base + to) % CAP, direct, gap_len);
+        } else if to < gap {
+            // shift [gap, len) and [0, to) left, past the end
+            self.shift_left((base + gap + gap_len) % CAP, around, gap_len);
+            self.base = I::from((base + CAP - gap_len) % CAP).unwrap();
+        } else if direct <= around {
+            // shift [gap, to) left
+            self.shift_left((base + gap + gap_len) % CAP, direct, gap_len);
+        } else {
+            // shift [to, len) and [0, gap) right, past the start
+            self.shift_right((base + to + gap_len) % CAP, around, gap_len);
+            self.base = I::from((base + gap_len) % CAP).unwrap();
+        }
+        self.gap = I::from(to).unwrap();
+    }
+
+    /// Moves the `count` slots starting at slot `src` right by `by`, wrapping around the end.
+    ///
+    /// Requirements:
+    /// - `src < CAP`
+    /// - `count + by <= CAP`
+    fn shift_right(&mut self, src: usize, count: usize, by: usize) {
+        let p = self.slots.as_mut_ptr();
+        // copy contiguous chunks starting from the back so nothing is overwritten before it's moved
+        let mut remaining = count;
+        while remaining > 0 {
+            // exclusive ends of the unmoved chunk, in 1..=CAP
+            let src_end = (src + remaining - 1) % CAP + 1;
+            let dst_end = (src + by + remaining - 1) % CAP + 1;
+            let n = remaining.min(src_end).min(dst_end);
+            unsafe { ptr::copy(p.add(src_end - n), p.add(dst_end - n), n) };
+            remaining -= n;
+        }
+    }
+
+    /// Moves the `count` slots starting at slot `src` left by `by`, wrapping around the start.
+    ///
+    /// Requirements:
+    /// - `src < CAP`
+    /// - `count + by <= CAP`
+    fn shift_left(&mut self, src: usize, count: usize, by: usize) {
+        let p = self.slots.as_mut_ptr();
+        // copy contiguous chunks starting from the front so nothing is overwritten before it's moved
+        let mut done = 0;
+        while done < count {
+            let s = (src + done) % CAP;
+            let d = (src + CAP - by + done) % CAP;
+            let n = (count - done).min(CAP - s).min(CAP - d);
+            unsafe { ptr::copy(p.add(s), p.add(d), n) };
+            done += n;
+        }
+    }
+}
+
+/// Slot of logical position `bs_i` in a [`BsIndex`] with the given `base`, `gap` and `gap_len`
+///
+/// Requirements:
+/// - `base < CAP`
+/// - `bs_i < CAP - gap_len`
+pub(crate) fn ring_slot<const CAP: usize>(
+    base: usize,
+    gap: usize,
+    gap_len: usize,
+    bs_i: usize,
+) -> usize {
+    let skip = if bs_i >= gap { gap_len } else { 0 };
+    // base < CAP and bs_i + skip < CAP, so a single subtraction wraps it around
+    let s = base + bs_i + skip;
+    if s >= CAP {
+        s - CAP
+    } else {
+        s
+    }
+}
@@ -0,0 +1,50 @@
+use core::cmp::Ordering;
+
+/// A total order over `T`, used to sort and look up the keys of a [`ConstLru`](crate::ConstLru).
+///
+/// Implement this on a marker type to order keys differently from their `Ord` impl,
+/// e.g. case-insensitively or by a projection, without wrapping the keys in a newtype.
+///
+/// To look up entries by a borrowed form `Q` of the key type `K`, also implement `Comparator<Q>`.
+/// It must order `Q`s the same way `Comparator<K>` orders the corresponding `K`s.
+///
+/// Example:
+///
+/// ```
+/// use const_lru::{Comparator, ConstLru};
+/// use core::cmp::Ordering;
+///
+/// struct CaseInsensitive;
+///
+/// impl Comparator<str> for CaseInsensitive {
+///     fn cmp(a: &str, b: &str) -> Ordering {
+///         let a = a.bytes().map(|c| c.to_ascii_lowercase());
+///         let b = b.bytes().map(|c| c.to_ascii_lowercase());
+///         a.cmp(b)
+///     }
+/// }
+///
+/// impl Comparator<String> for CaseInsensitive {
+///     fn cmp(a: &String, b: &String) -> Ordering {
+///         <Self as Comparator<str>>::cmp(a, b)
+///     }
+/// }
+///
+/// let mut c: ConstLru<String, u8, 2, u8, CaseInsensitive> = ConstLru::new();
+/// c.insert("Hello".to_owned(), 1);
+/// assert_eq!(c.get("hELLO"), Some(&1));
+/// ```
+pub trait Comparator<T: ?Sized> {
+    /// Returns the ordering of `a` relative to `b`
+    fn cmp(a: &T, b: &T) -> Ordering;
+}
+
+/// The default [`Comparator`], orders keys by their `Ord` impl.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NaturalOrder;
+
+impl<T: Ord + ?Sized> Comparator<T> for NaturalOrder {
+    fn cmp(a: &T, b: &T) -> Ordering {
+        a.cmp(b)
+    }
+}
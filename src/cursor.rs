@@ -0,0 +1,190 @@
+use num_traits::{PrimInt, Unsigned};
+
+use crate::{Comparator, ConstLru, NaturalOrder};
+
+/// A cursor over the entries of a ConstLru in LRU order that can reorder and remove entries.
+///
+/// "Next" is towards the least-recently-used end, "prev" is towards the most-recently-used end.
+///
+/// Besides pointing at an entry, the cursor can point at a "ghost" non-entry between the two ends,
+/// like the cursors of `std`'s `LinkedList`. Moving next from the least-recently-used entry
+/// or prev from the most-recently-used entry moves the cursor to the ghost, and moving next or prev
+/// from the ghost moves it to the most-recently-used or least-recently-used entry respectively.
+///
+/// Created with [`ConstLru::cursor_mut_front`] or [`ConstLru::cursor_mut_back`].
+#[derive(Debug)]
+pub struct CursorMut<'a, K, V, const CAP: usize, I: PrimInt + Unsigned, C = NaturalOrder> {
+    const_lru: &'a mut ConstLru<K, V, CAP, I, C>,
+
+    /// index of the current entry, CAP if the cursor is at the ghost
+    current: I,
+}
+
+impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned, C> CursorMut<'a, K, V, CAP, I, C> {
+    /// At the most-recently-used entry, or the ghost if empty
+    pub(crate) fn new_front(const_lru: &'a mut ConstLru<K, V, CAP, I, C>) -> Self {
+        let current = if const_lru.is_empty() {
+            const_lru.list.cap()
+        } else {
+            const_lru.list.head
+        };
+        Self { const_lru, current }
+    }
+
+    /// At the least-recently-used entry, or the ghost if empty
+    pub(crate) fn new_back(const_lru: &'a mut ConstLru<K, V, CAP, I, C>) -> Self {
+        let current = if const_lru.is_empty() {
+            const_lru.list.cap()
+        } else {
+            const_lru.list.tail
+        };
+        Self { const_lru, current }
+    }
+
+    fn is_ghost(&self) -> bool {
+        self.current == self.const_lru.list.cap()
+    }
+
+    /// Index of the entry after `index`, CAP if `index` is the ghost or the tail.
+    fn next_of(&self, index: I) -> I {
+        let list = &self.const_lru.list;
+        if index == list.cap() || index == list.tail {
+            // nexts[tail] is the first slot of the free-list
+            return list.cap();
+        }
+        list.nexts[index.to_usize().unwrap()]
+    }
+
+    /// Index of the entry before `index`, CAP if `index` is the ghost or the head.
+    fn prev_of(&self, index: I) -> I {
+        let list = &self.const_lru.list;
+        if index == list.cap() || index == list.head {
+            return list.cap();
+        }
+        list.prevs[index.to_usize().unwrap()]
+    }
+
+    /// Assumes `index` is of a valid node
+    fn entry_at(&self, index: I) -> (&K, &V) {
+        let list = &self.const_lru.list;
+        (list.get_key_by_index(index), list.get_by_index(index))
+    }
+
+    /// Returns the key and mutable value of the current entry, or `None` if the cursor is at the ghost.
+    ///
+    /// Does not change the LRU order.
+    pub fn current(&mut self) -> Option<(&K, &mut V)> {
+        if self.is_ghost() {
+            return None;
+        }
+        let i = self.current.to_usize().unwrap();
+        let list = &mut self.const_lru.list;
+        let key = unsafe { list.keys[i].assume_init_ref() };
+        let val = unsafe { list.values[i].assume_init_mut() };
+        Some((key, val))
+    }
+
+    /// Returns the entry after the current one, towards the least-recently-used end.
+    ///
+    /// If the cursor is at the ghost, this is the most-recently-used entry.
+    /// Returns `None` if the next element is the ghost.
+    pub fn peek_next(&self) -> Option<(&K, &V)> {
+        let next = if self.is_ghost() {
+            if self.const_lru.is_empty() {
+                return None;
+            }
+            self.const_lru.list.head
+        } else {
+            self.next_of(self.current)
+        };
+        if next == self.const_lru.list.cap() {
+            return None;
+        }
+        Some(self.entry_at(next))
+    }
+
+    /// Returns the entry before the current one, towards the most-recently-used end.
+    ///
+    /// If the cursor is at the ghost, this is the least-recently-used entry.
+    /// Returns `None` if the previous element is the ghost.
+    pub fn peek_prev(&self) -> Option<(&K, &V)> {
+        let prev = if self.is_ghost() {
+            if self.const_lru.is_empty() {
+                return None;
+            }
+            self.const_lru.list.tail
+        } else {
+            self.prev_of(self.current)
+        };
+        if prev == self.const_lru.list.cap() {
+            return None;
+        }
+        Some(self.entry_at(prev))
+    }
+
+    /// Moves the cursor to the next entry, towards the least-recently-used end.
+    pub fn move_next(&mut self) {
+        self.current = if self.is_ghost() && !self.const_lru.is_empty() {
+            self.const_lru.list.head
+        } else {
+            self.next_of(self.current)
+        };
+    }
+
+    /// Moves the cursor to the previous entry, towards the most-recently-used end.
+    pub fn move_prev(&mut self) {
+        self.current = if self.is_ghost() && !self.const_lru.is_empty() {
+            self.const_lru.list.tail
+        } else {
+            self.prev_of(self.current)
+        };
+    }
+
+    /// Moves the current entry to the most-recently-used position
+    /// and the cursor to the entry that was next to it. Does nothing if the cursor is at the ghost.
+    ///
+    /// When walking from the least-recently-used end, moved entries are visited again once the walk reaches them.
+    pub fn move_current_to_front(&mut self) {
+        if self.is_ghost() {
+            return;
+        }
+        let next = self.next_of(self.current);
+        self.const_lru.list.move_to_head(self.current);
+        self.current = next;
+    }
+
+    /// Moves the current entry to the least-recently-used position, so that it is evicted next,
+    /// and the cursor to the entry that was next to it. Does nothing if the cursor is at the ghost.
+    ///
+    /// When walking from the most-recently-used end, moved entries are visited again once the walk reaches them.
+    pub fn move_current_to_back(&mut self) {
+        if self.is_ghost() {
+            return;
+        }
+        let next = self.next_of(self.current);
+        self.const_lru.list.move_to_tail(self.current);
+        self.current = next;
+    }
+}
+
+impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned, C: Comparator<K>>
+    CursorMut<'a, K, V, CAP, I, C>
+{
+    /// Removes the current entry from the ConstLru and returns it,
+    /// moving the cursor to the entry that was next to it.
+    ///
+    /// Returns `None` and does nothing if the cursor is at the ghost.
+    pub fn remove_current(&mut self) -> Option<(K, V)> {
+        if self.is_ghost() {
+            return None;
+        }
+        let next = self.next_of(self.current);
+        let key = self.const_lru.list.get_key_by_index(self.current);
+        let Ok(index_tup) = self.const_lru.get_index_of(key) else {
+            unreachable!()
+        };
+        let res = self.const_lru.remove_by_index(index_tup);
+        self.current = next;
+        Some(res)
+    }
+}
@@ -0,0 +1,105 @@
+use num_traits::{PrimInt, Unsigned};
+
+use crate::{ConstLru, NaturalOrder};
+
+/// A cursor over the entries of a ConstLru in the keys' sorted order.
+///
+/// "Next" is towards the largest key, "prev" is towards the smallest key.
+///
+/// Like [`CursorMut`](crate::CursorMut), the cursor can point at a "ghost" non-entry between the two ends.
+/// Moving next from the largest key or prev from the smallest key moves the cursor to the ghost,
+/// and moving next or prev from the ghost moves it to the smallest or largest key respectively.
+///
+/// Does not change the LRU order of the entries.
+///
+/// Created with [`ConstLru::lower_bound`] or [`ConstLru::upper_bound`].
+#[derive(Debug)]
+pub struct CursorKeyOrder<'a, K, V, const CAP: usize, I: PrimInt + Unsigned, C = NaturalOrder> {
+    const_lru: &'a ConstLru<K, V, CAP, I, C>,
+
+    /// position of the current entry in the bs_index, len if the cursor is at the ghost
+    bs_i: usize,
+}
+
+impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned, C> CursorKeyOrder<'a, K, V, CAP, I, C> {
+    /// Requirements:
+    /// - `bs_i <= len`
+    pub(crate) fn new(const_lru: &'a ConstLru<K, V, CAP, I, C>, bs_i: usize) -> Self {
+        Self { const_lru, bs_i }
+    }
+
+    fn len(&self) -> usize {
+        self.const_lru.len().to_usize().unwrap()
+    }
+
+    /// Returns the entry at `bs_i`, or `None` if `bs_i` is the ghost
+    fn entry_at(&self, bs_i: usize) -> Option<(&'a K, &'a V)> {
+        if bs_i >= self.len() {
+            return None;
+        }
+        let index = self.const_lru.bs_index.get(bs_i);
+        let list = &self.const_lru.list;
+        Some((list.get_key_by_index(index), list.get_by_index(index)))
+    }
+
+    /// Position of the entry after `bs_i`, wrapping around through the ghost
+    fn next_of(&self, bs_i: usize) -> usize {
+        if bs_i >= self.len() {
+            0
+        } else {
+            bs_i + 1
+        }
+    }
+
+    /// Position of the entry before `bs_i`, wrapping around through the ghost
+    fn prev_of(&self, bs_i: usize) -> usize {
+        if bs_i == 0 {
+            self.len()
+        } else {
+            bs_i - 1
+        }
+    }
+
+    /// Returns the current entry, or `None` if the cursor is at the ghost.
+    pub fn current(&self) -> Option<(&'a K, &'a V)> {
+        self.entry_at(self.bs_i)
+    }
+
+    /// Returns the position of the current entry in key order, starting from 0 at the smallest key,
+    /// or `None` if the cursor is at the ghost.
+    pub fn index(&self) -> Option<usize> {
+        (self.bs_i < self.len()).then_some(self.bs_i)
+    }
+
+    /// Returns the entry with the next larger key.
+    ///
+    /// If the cursor is at the ghost, this is the entry with the smallest key.
+    /// Returns `None` if the next element is the ghost.
+    pub fn peek_next(&self) -> Option<(&'a K, &'a V)> {
+        self.entry_at(self.next_of(self.bs_i))
+    }
+
+    /// Returns the entry with the next smaller key.
+    ///
+    /// If the cursor is at the ghost, this is the entry with the largest key.
+    /// Returns `None` if the previous element is the ghost.
+    pub fn peek_prev(&self) -> Option<(&'a K, &'a V)> {
+        self.entry_at(self.prev_of(self.bs_i))
+    }
+
+    /// Moves the cursor to the entry with the next larger key.
+    ///
+    /// If the cursor is at the largest key, it moves to the ghost.
+    /// If the cursor is at the ghost, it moves to the smallest key.
+    pub fn move_next(&mut self) {
+        self.bs_i = self.next_of(self.bs_i);
+    }
+
+    /// Moves the cursor to the entry with the next smaller key.
+    ///
+    /// If the cursor is at the smallest key, it moves to the ghost.
+    /// If the cursor is at the ghost, it moves to the largest key.
+    pub fn move_prev(&mut self) {
+        self.bs_i = self.prev_of(self.bs_i);
+    }
+}
@@ -0,0 +1,241 @@
+use core::borrow::Borrow;
+
+use num_traits::{PrimInt, Unsigned};
+
+use crate::{Comparator, ConstLru, Entry, NaturalOrder, OccupiedEntry, VacantEntry};
+
+/// A view into a single entry in a ConstLru looked up by a borrowed key, which may either be vacant or occupied.
+/// Returned by [`ConstLru::entry_ref`].
+///
+/// Same as [`Entry`], but the owned key is only created from the borrowed key if a value is inserted.
+#[derive(Debug)]
+pub enum EntryRef<
+    'a,
+    'b,
+    K,
+    Q: ?Sized,
+    V,
+    const CAP: usize,
+    I: PrimInt + Unsigned,
+    C = NaturalOrder,
+> {
+    /// Has no owned lookup key, so [`OccupiedEntry::key`] returns the stored key
+    Occupied(OccupiedEntry<'a, K, V, CAP, I, C>),
+    Vacant(VacantEntryRef<'a, 'b, K, Q, V, CAP, I, C>),
+}
+
+impl<
+        'a,
+        'b,
+        K: Borrow<Q>,
+        Q: ?Sized,
+        V,
+        const CAP: usize,
+        I: PrimInt + Unsigned,
+        C: Comparator<K> + Comparator<Q>,
+    > EntryRef<'a, 'b, K, Q, V, CAP, I, C>
+{
+    pub(crate) fn new(const_lru: &'a mut ConstLru<K, V, CAP, I, C>, k: &'b Q) -> Self {
+        if CAP == 0 {
+            panic!("Entry API only works for CAP > 0");
+        }
+        let insert_bs_i = match const_lru.get_index_of(k) {
+            Ok(tup) => return Self::Occupied(OccupiedEntry::new_keyless(const_lru, tup)),
+            Err(i) => i,
+        };
+        Self::Vacant(VacantEntryRef {
+            const_lru,
+            key: k,
+            insert_bs_i,
+        })
+    }
+}
+
+impl<'a, 'b, K: Borrow<Q>, Q: ?Sized, V, const CAP: usize, I: PrimInt + Unsigned, C>
+    EntryRef<'a, 'b, K, Q, V, CAP, I, C>
+{
+    /// Returns a reference to this entry’s key.
+    pub fn key(&self) -> &Q {
+        match self {
+            Self::Occupied(e) => e.key().borrow(),
+            Self::Vacant(e) => e.key(),
+        }
+    }
+
+    /// Provides in-place mutable access to an occupied entry before any potential inserts into the ConstLru.
+    ///
+    /// Also moves the entry to most-recently-used position if occupied.
+    /// To not update to most-recently-used, use [`Self::and_modify_untouched`]
+    pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Self {
+        match self {
+            Self::Occupied(mut e) => {
+                f(e.get_mut());
+                Self::Occupied(e)
+            }
+            Self::Vacant(e) => Self::Vacant(e),
+        }
+    }
+
+    /// Same as [`Self::and_modify`], but does not move the entry to most-recently-used position
+    pub fn and_modify_untouched<F: FnOnce(&mut V)>(self, f: F) -> Self {
+        match self {
+            Self::Occupied(mut e) => {
+                f(e.get_mut_untouched());
+                Self::Occupied(e)
+            }
+            Self::Vacant(e) => Self::Vacant(e),
+        }
+    }
+}
+
+impl<'a, 'b, K: From<&'b Q>, Q: ?Sized, V, const CAP: usize, I: PrimInt + Unsigned, C>
+    EntryRef<'a, 'b, K, Q, V, CAP, I, C>
+{
+    /// Converts into an [`Entry`], creating the owned key if vacant
+    pub fn into_entry(self) -> Entry<'a, K, V, CAP, I, C> {
+        match self {
+            Self::Occupied(e) => Entry::Occupied(e),
+            Self::Vacant(e) => Entry::Vacant(e.into_vacant_entry()),
+        }
+    }
+}
+
+impl<
+        'a,
+        'b,
+        K: From<&'b Q>,
+        Q: ?Sized,
+        V,
+        const CAP: usize,
+        I: PrimInt + Unsigned,
+        C: Comparator<K>,
+    > EntryRef<'a, 'b, K, Q, V, CAP, I, C>
+{
+    /// Ensures a value is in the entry by inserting the default if empty, and returns a mutable reference to the value in the entry.
+    ///
+    /// Also moves the entry to most-recently-used position if previously existing
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.into_entry().or_insert(default)
+    }
+
+    /// Ensures a value is in the entry by inserting the result of the default function if empty, and returns a mutable reference to the value in the entry.
+    ///
+    /// Also moves the entry to most-recently-used position if previously existing
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        self.into_entry().or_insert_with(default)
+    }
+
+    /// Ensures a value is in the entry by inserting, if empty, the result of the default function called with the borrowed key.
+    ///
+    /// Also moves the entry to most-recently-used position if previously existing
+    pub fn or_insert_with_key<F: FnOnce(&Q) -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Self::Occupied(e) => e.into_mut(),
+            Self::Vacant(e) => {
+                let v = default(e.key());
+                e.insert(v).0
+            }
+        }
+    }
+
+    /// Sets the value of the entry, and returns:
+    /// - an [`OccupiedEntry`] for the entry
+    /// - LRU evicted entry, if the entry was vacant and ConstLru was full
+    ///
+    /// Also moves the entry to most-recently-used position.
+    /// To not update to most-recently-used if previously existing, use [`Self::insert_entry_untouched`]
+    #[allow(clippy::type_complexity)]
+    pub fn insert_entry(self, v: V) -> (OccupiedEntry<'a, K, V, CAP, I, C>, Option<(K, V)>) {
+        self.into_entry().insert_entry(v)
+    }
+
+    /// Same as [`Self::insert_entry`], but does not move the entry to most-recently-used position if previously existing
+    #[allow(clippy::type_complexity)]
+    pub fn insert_entry_untouched(
+        self,
+        v: V,
+    ) -> (OccupiedEntry<'a, K, V, CAP, I, C>, Option<(K, V)>) {
+        self.into_entry().insert_entry_untouched(v)
+    }
+}
+
+impl<
+        'a,
+        'b,
+        K: From<&'b Q>,
+        Q: ?Sized,
+        V: Default,
+        const CAP: usize,
+        I: PrimInt + Unsigned,
+        C: Comparator<K>,
+    > EntryRef<'a, 'b, K, Q, V, CAP, I, C>
+{
+    /// Ensures a value is in the entry by inserting the default value if empty, and returns a mutable reference to the value in the entry.
+    ///
+    /// Also moves the entry to most-recently-used position if previously existing
+    pub fn or_default(self) -> &'a mut V {
+        self.into_entry().or_default()
+    }
+}
+
+/// A view into a vacant entry in a ConstLru looked up by a borrowed key. It is part of the EntryRef enum.
+#[derive(Debug)]
+pub struct VacantEntryRef<
+    'a,
+    'b,
+    K,
+    Q: ?Sized,
+    V,
+    const CAP: usize,
+    I: PrimInt + Unsigned,
+    C = NaturalOrder,
+> {
+    const_lru: &'a mut ConstLru<K, V, CAP, I, C>,
+    key: &'b Q,
+    insert_bs_i: I,
+}
+
+impl<'a, 'b, K, Q: ?Sized, V, const CAP: usize, I: PrimInt + Unsigned, C>
+    VacantEntryRef<'a, 'b, K, Q, V, CAP, I, C>
+{
+    /// Gets a reference to the borrowed key that the owned key would be created from when inserting a value.
+    pub fn key(&self) -> &'b Q {
+        self.key
+    }
+}
+
+impl<'a, 'b, K: From<&'b Q>, Q: ?Sized, V, const CAP: usize, I: PrimInt + Unsigned, C>
+    VacantEntryRef<'a, 'b, K, Q, V, CAP, I, C>
+{
+    /// Creates the owned key and converts into a [`VacantEntry`].
+    ///
+    /// The owned key must compare equal to the borrowed key.
+    pub fn into_vacant_entry(self) -> VacantEntry<'a, K, V, CAP, I, C> {
+        VacantEntry::new(self.const_lru, K::from(self.key), self.insert_bs_i)
+    }
+}
+
+impl<
+        'a,
+        'b,
+        K: From<&'b Q>,
+        Q: ?Sized,
+        V,
+        const CAP: usize,
+        I: PrimInt + Unsigned,
+        C: Comparator<K>,
+    > VacantEntryRef<'a, 'b, K, Q, V, CAP, I, C>
+{
+    /// Creates the owned key and sets the value of the entry with it, and returns:
+    /// - a mutable reference to the new value
+    /// - LRU evicted entry, if ConstLru is full
+    pub fn insert(self, v: V) -> (&'a mut V, Option<(K, V)>) {
+        self.into_vacant_entry().insert(v)
+    }
+
+    /// Same as [`Self::insert`], but returns an [`OccupiedEntry`] for the new entry instead of a mutable reference to its value
+    #[allow(clippy::type_complexity)]
+    pub fn insert_entry(self, v: V) -> (OccupiedEntry<'a, K, V, CAP, I, C>, Option<(K, V)>) {
+        self.into_vacant_entry().insert_entry(v)
+    }
+}
@@ -1,22 +1,28 @@
 use num_traits::{PrimInt, Unsigned};
 
+mod entry_ref;
 mod occupied;
+mod try_entry;
 mod vacant;
 
+pub use entry_ref::*;
 pub use occupied::*;
+pub use try_entry::*;
 pub use vacant::*;
 
-use crate::ConstLru;
+use crate::{Comparator, ConstLru, NaturalOrder};
 
 /// A view into a single entry in a ConstLru, which may either be vacant or occupied.
 #[derive(Debug)]
-pub enum Entry<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> {
-    Occupied(OccupiedEntry<'a, K, V, CAP, I>),
-    Vacant(VacantEntry<'a, K, V, CAP, I>),
+pub enum Entry<'a, K, V, const CAP: usize, I: PrimInt + Unsigned, C = NaturalOrder> {
+    Occupied(OccupiedEntry<'a, K, V, CAP, I, C>),
+    Vacant(VacantEntry<'a, K, V, CAP, I, C>),
 }
 
-impl<'a, K: Ord, V, const CAP: usize, I: PrimInt + Unsigned> Entry<'a, K, V, CAP, I> {
-    pub(crate) fn new(const_lru: &'a mut ConstLru<K, V, CAP, I>, k: K) -> Self {
+impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned, C: Comparator<K>>
+    Entry<'a, K, V, CAP, I, C>
+{
+    pub(crate) fn new(const_lru: &'a mut ConstLru<K, V, CAP, I, C>, k: K) -> Self {
         if CAP == 0 {
             panic!("Entry API only works for CAP > 0");
         }
@@ -28,7 +34,7 @@ impl<'a, K: Ord, V, const CAP: usize, I: PrimInt + Unsigned> Entry<'a, K, V, CAP
     }
 }
 
-impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> Entry<'a, K, V, CAP, I> {
+impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned, C> Entry<'a, K, V, CAP, I, C> {
     /// Returns a reference to this entry’s key.
     pub fn key(&self) -> &K {
         match self {
@@ -38,7 +44,9 @@ impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> Entry<'a, K, V, CAP, I>
     }
 }
 
-impl<'a, K: Ord, V, const CAP: usize, I: PrimInt + Unsigned> Entry<'a, K, V, CAP, I> {
+impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned, C: Comparator<K>>
+    Entry<'a, K, V, CAP, I, C>
+{
     /// Ensures a value is in the entry by inserting the result of the default function if empty, and returns a mutable reference to the value in the entry.
     ///
     /// Also moves the entry to most-recently-used position if previously existing
@@ -73,9 +81,99 @@ impl<'a, K: Ord, V, const CAP: usize, I: PrimInt + Unsigned> Entry<'a, K, V, CAP
             Self::Vacant(e) => e.insert(default).0,
         }
     }
+
+    /// Ensures a value is in the entry by inserting the result of the fallible default function if empty, and returns:
+    /// - a mutable reference to the value in the entry
+    /// - LRU evicted entry, if a value was inserted and ConstLru was full
+    ///
+    /// If the default function fails, its error is returned and nothing is inserted.
+    ///
+    /// Also moves the entry to most-recently-used position if previously existing.
+    /// To not update to most-recently-used, use [`Self::or_try_insert_with_untouched`]
+    #[allow(clippy::type_complexity)]
+    pub fn or_try_insert_with<E, F: FnOnce() -> Result<V, E>>(
+        self,
+        default: F,
+    ) -> Result<(&'a mut V, Option<(K, V)>), E> {
+        match self {
+            Self::Occupied(e) => Ok((e.into_mut(), None)),
+            Self::Vacant(e) => Ok(e.insert(default()?)),
+        }
+    }
+
+    /// Same as [`Self::or_try_insert_with`], but does not move the entry to most-recently-used position if previously existing
+    #[allow(clippy::type_complexity)]
+    pub fn or_try_insert_with_untouched<E, F: FnOnce() -> Result<V, E>>(
+        self,
+        default: F,
+    ) -> Result<(&'a mut V, Option<(K, V)>), E> {
+        match self {
+            Self::Occupied(e) => Ok((e.into_mut_untouched(), None)),
+            Self::Vacant(e) => Ok(e.insert(default()?)),
+        }
+    }
+
+    /// Provides in-place mutable access to an occupied entry before any potential inserts into the ConstLru.
+    ///
+    /// Also moves the entry to most-recently-used position if occupied.
+    /// To not update to most-recently-used, use [`Self::and_modify_untouched`]
+    pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Self {
+        match self {
+            Self::Occupied(mut e) => {
+                f(e.get_mut());
+                Self::Occupied(e)
+            }
+            Self::Vacant(e) => Self::Vacant(e),
+        }
+    }
+
+    /// Same as [`Self::and_modify`], but does not move the entry to most-recently-used position
+    pub fn and_modify_untouched<F: FnOnce(&mut V)>(self, f: F) -> Self {
+        match self {
+            Self::Occupied(mut e) => {
+                f(e.get_mut_untouched());
+                Self::Occupied(e)
+            }
+            Self::Vacant(e) => Self::Vacant(e),
+        }
+    }
+
+    /// Sets the value of the entry, and returns:
+    /// - an [`OccupiedEntry`] for the entry
+    /// - LRU evicted entry, if the entry was vacant and ConstLru was full
+    ///
+    /// Also moves the entry to most-recently-used position.
+    /// To not update to most-recently-used if previously existing, use [`Self::insert_entry_untouched`]
+    #[allow(clippy::type_complexity)]
+    pub fn insert_entry(self, v: V) -> (OccupiedEntry<'a, K, V, CAP, I, C>, Option<(K, V)>) {
+        match self {
+            Self::Occupied(mut e) => {
+                e.insert(v);
+                (e, None)
+            }
+            Self::Vacant(e) => e.insert_entry(v),
+        }
+    }
+
+    /// Same as [`Self::insert_entry`], but does not move the entry to most-recently-used position if previously existing
+    #[allow(clippy::type_complexity)]
+    pub fn insert_entry_untouched(
+        self,
+        v: V,
+    ) -> (OccupiedEntry<'a, K, V, CAP, I, C>, Option<(K, V)>) {
+        match self {
+            Self::Occupied(mut e) => {
+                e.insert_untouched(v);
+                (e, None)
+            }
+            Self::Vacant(e) => e.insert_entry(v),
+        }
+    }
 }
 
-impl<'a, K: Ord, V: Default, const CAP: usize, I: PrimInt + Unsigned> Entry<'a, K, V, CAP, I> {
+impl<'a, K, V: Default, const CAP: usize, I: PrimInt + Unsigned, C: Comparator<K>>
+    Entry<'a, K, V, CAP, I, C>
+{
     /// Ensures a value is in the entry by inserting the default value if empty, and returns a mutable reference to the value in the entry.
     ///
     /// Also moves the entry to most-recently-used position if previously existing
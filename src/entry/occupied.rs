@@ -1,25 +1,39 @@
 use num_traits::{PrimInt, Unsigned};
 
-use crate::ConstLru;
+use crate::{ConstLru, NaturalOrder};
 
 /// A view into an occupied entry in a ConstLru. It is part of the Entry enum.
 #[derive(Debug)]
-pub struct OccupiedEntry<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> {
-    const_lru: &'a mut ConstLru<K, V, CAP, I>,
-    key: K,
+pub struct OccupiedEntry<'a, K, V, const CAP: usize, I: PrimInt + Unsigned, C = NaturalOrder> {
+    const_lru: &'a mut ConstLru<K, V, CAP, I, C>,
+    /// Key the entry was looked up with. `None` if the entry was just inserted or looked up by reference
+    key: Option<K>,
     index: I,
     bs_i: I,
 }
 
-impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> OccupiedEntry<'a, K, V, CAP, I> {
+impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned, C> OccupiedEntry<'a, K, V, CAP, I, C> {
     pub(crate) fn new(
-        const_lru: &'a mut ConstLru<K, V, CAP, I>,
+        const_lru: &'a mut ConstLru<K, V, CAP, I, C>,
         key: K,
         (index, bs_i): (I, I),
     ) -> Self {
         Self {
             const_lru,
-            key,
+            key: Some(key),
+            index,
+            bs_i,
+        }
+    }
+
+    /// For entries without an owned lookup key, so the stored key is the only key
+    pub(crate) fn new_keyless(
+        const_lru: &'a mut ConstLru<K, V, CAP, I, C>,
+        (index, bs_i): (I, I),
+    ) -> Self {
+        Self {
+            const_lru,
+            key: None,
             index,
             bs_i,
         }
@@ -29,31 +43,39 @@ impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> OccupiedEntry<'a, K, V,
     ///
     /// To not update to most-recently-used, use [`Self::get_untouched`]
     pub fn get(&mut self) -> &V {
-        self.const_lru.move_to_head(self.index);
-        self.const_lru.get_by_index(self.index)
+        self.const_lru.list.move_to_head(self.index);
+        self.const_lru.list.get_by_index(self.index)
     }
 
     /// Gets a reference to the value in the entry
     pub fn get_untouched(&self) -> &V {
-        self.const_lru.get_by_index(self.index)
+        self.const_lru.list.get_by_index(self.index)
     }
 
     /// Gets a mutable reference to the value in the entry and moves entry to most recently used slot
     ///
     /// To not update to most-recently-used, use [`Self::get_mut_untouched`]
     pub fn get_mut(&mut self) -> &mut V {
-        self.const_lru.move_to_head(self.index);
-        self.const_lru.get_mut_by_index(self.index)
+        self.const_lru.list.move_to_head(self.index);
+        self.const_lru.list.get_mut_by_index(self.index)
     }
 
     /// Gets a mutable reference to the value in the entry
     pub fn get_mut_untouched(&mut self) -> &mut V {
-        self.const_lru.get_mut_by_index(self.index)
+        self.const_lru.list.get_mut_by_index(self.index)
     }
 
     /// Sets the value of the entry, and returns the entry's old value.
+    /// Also moves the entry to most recently used slot
+    ///
+    /// To not update to most-recently-used, use [`Self::insert_untouched`]
     pub fn insert(&mut self, v: V) -> V {
-        self.const_lru.insert_replace_value(self.index, v)
+        self.const_lru.list.replace_value(self.index, v)
+    }
+
+    /// Sets the value of the entry, and returns the entry's old value.
+    pub fn insert_untouched(&mut self, v: V) -> V {
+        core::mem::replace(self.get_mut_untouched(), v)
     }
 
     /// Converts the `OccupiedEntry` into a mutable reference to the value in the entry with a lifetime bound to the ConstLru itself.
@@ -61,18 +83,51 @@ impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> OccupiedEntry<'a, K, V,
     ///
     /// To not update to most-recently-used, use [`Self::into_mut_untouched`]
     pub fn into_mut(self) -> &'a mut V {
-        self.const_lru.move_to_head(self.index);
-        self.const_lru.get_mut_by_index(self.index)
+        self.const_lru.list.move_to_head(self.index);
+        self.const_lru.list.get_mut_by_index(self.index)
     }
 
     /// Converts the `OccupiedEntry` into a mutable reference to the value in the entry with a lifetime bound to the ConstLru itself.
     pub fn into_mut_untouched(self) -> &'a mut V {
-        self.const_lru.get_mut_by_index(self.index)
+        self.const_lru.list.get_mut_by_index(self.index)
     }
 
-    /// Gets a reference to the key in the entry.
+    /// Gets a reference to the key the entry was looked up with,
+    /// or to the stored key if the entry was returned by an `insert_entry` method or [`ConstLru::entry_ref`].
     pub fn key(&self) -> &K {
-        &self.key
+        match &self.key {
+            Some(k) => k,
+            None => self.const_lru.list.get_key_by_index(self.index),
+        }
+    }
+
+    /// Replaces the stored key with the key the entry was looked up with, and returns the old stored key.
+    /// Useful if keys that compare equal are still distinguishable, e.g. with a [`Comparator`](crate::Comparator) that only compares part of the key.
+    ///
+    /// Does not change LRU order.
+    ///
+    /// Returns `None` and leaves the stored key as is if the entry was returned by an `insert_entry` method
+    /// or [`ConstLru::entry_ref`], since there's no owned lookup key to replace it with.
+    pub fn replace_key(self) -> Option<K> {
+        let k = self.key?;
+        Some(self.const_lru.list.replace_key(self.index, k))
+    }
+
+    /// Replaces the stored key and value with the key the entry was looked up with and `v`,
+    /// returning the old stored key (see [`Self::replace_key`]) and the old value.
+    /// Also moves the entry to most recently used slot
+    ///
+    /// To not update to most-recently-used, use [`Self::replace_entry_untouched`]
+    pub fn replace_entry(self, v: V) -> (Option<K>, V) {
+        self.const_lru.list.move_to_head(self.index);
+        self.replace_entry_untouched(v)
+    }
+
+    /// Replaces the stored key and value with the key the entry was looked up with and `v`,
+    /// returning the old stored key (see [`Self::replace_key`]) and the old value.
+    pub fn replace_entry_untouched(mut self, v: V) -> (Option<K>, V) {
+        let old_v = self.insert_untouched(v);
+        (self.replace_key(), old_v)
     }
 
     /// Takes the value out of the entry, and returns it.
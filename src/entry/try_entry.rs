@@ -0,0 +1,141 @@
+use core::ops::{Deref, DerefMut};
+
+use num_traits::{PrimInt, Unsigned};
+
+use crate::{Comparator, ConstLru, Entry, NaturalOrder, OccupiedEntry, VacantEntry};
+
+/// A view into a single entry in a ConstLru that may not be able to store any entries. Returned by [`ConstLru::try_entry`].
+///
+/// Same as [`Entry`], with an extra variant for `CAP == 0`, so that generic code can treat caches with `CAP == 0` as disabled.
+#[derive(Debug)]
+pub enum TryEntry<'a, K, V, const CAP: usize, I: PrimInt + Unsigned, C = NaturalOrder> {
+    Occupied(OccupiedEntry<'a, K, V, CAP, I, C>),
+    Vacant(VacantEntry<'a, K, V, CAP, I, C>),
+
+    /// `CAP == 0`, so nothing can be stored. Contains the key
+    ZeroCap(K),
+}
+
+impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned, C: Comparator<K>>
+    TryEntry<'a, K, V, CAP, I, C>
+{
+    pub(crate) fn new(const_lru: &'a mut ConstLru<K, V, CAP, I, C>, k: K) -> Self {
+        if CAP == 0 {
+            return Self::ZeroCap(k);
+        }
+        match Entry::new(const_lru, k) {
+            Entry::Occupied(e) => Self::Occupied(e),
+            Entry::Vacant(e) => Self::Vacant(e),
+        }
+    }
+}
+
+impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned, C> TryEntry<'a, K, V, CAP, I, C> {
+    /// Returns a reference to this entry’s key.
+    pub fn key(&self) -> &K {
+        match self {
+            Self::Occupied(e) => e.key(),
+            Self::Vacant(e) => e.key(),
+            Self::ZeroCap(k) => k,
+        }
+    }
+
+    /// Converts into an [`Entry`], or returns the key if `CAP == 0`
+    pub fn into_entry(self) -> Result<Entry<'a, K, V, CAP, I, C>, K> {
+        match self {
+            Self::Occupied(e) => Ok(Entry::Occupied(e)),
+            Self::Vacant(e) => Ok(Entry::Vacant(e)),
+            Self::ZeroCap(k) => Err(k),
+        }
+    }
+}
+
+impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned, C: Comparator<K>>
+    TryEntry<'a, K, V, CAP, I, C>
+{
+    /// Ensures a value is in the entry by inserting the default if empty, and returns a mutable reference to the value in the entry.
+    /// If `CAP == 0`, returns `default` by value instead.
+    ///
+    /// Also moves the entry to most-recently-used position if previously existing
+    pub fn or_insert(self, default: V) -> MaybeStored<'a, V> {
+        self.or_insert_with_key(|_| default)
+    }
+
+    /// Ensures a value is in the entry by inserting the result of the default function if empty, and returns a mutable reference to the value in the entry.
+    /// If `CAP == 0`, returns the result of the default function by value instead.
+    ///
+    /// Also moves the entry to most-recently-used position if previously existing
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> MaybeStored<'a, V> {
+        self.or_insert_with_key(|_| default())
+    }
+
+    /// Same as [`Self::or_insert_with`], but the default function is given a reference to the key.
+    pub fn or_insert_with_key<F: FnOnce(&K) -> V>(self, default: F) -> MaybeStored<'a, V> {
+        match self {
+            Self::Occupied(e) => MaybeStored::Stored(e.into_mut()),
+            Self::Vacant(e) => {
+                let v = default(e.key());
+                MaybeStored::Stored(e.insert(v).0)
+            }
+            Self::ZeroCap(k) => MaybeStored::Unstored(default(&k)),
+        }
+    }
+}
+
+impl<'a, K, V: Default, const CAP: usize, I: PrimInt + Unsigned, C: Comparator<K>>
+    TryEntry<'a, K, V, CAP, I, C>
+{
+    /// Ensures a value is in the entry by inserting the default value if empty, and returns a mutable reference to the value in the entry.
+    /// If `CAP == 0`, returns the default value by value instead.
+    ///
+    /// Also moves the entry to most-recently-used position if previously existing
+    pub fn or_default(self) -> MaybeStored<'a, V> {
+        self.or_insert_with(V::default)
+    }
+}
+
+/// A value that's either stored in a ConstLru, or returned by value because the ConstLru can't store any (`CAP == 0`).
+///
+/// Derefs to the value either way.
+#[derive(Debug)]
+pub enum MaybeStored<'a, V> {
+    Stored(&'a mut V),
+    Unstored(V),
+}
+
+impl<V> MaybeStored<'_, V> {
+    /// Returns `true` if the value is stored in the ConstLru
+    pub fn is_stored(&self) -> bool {
+        matches!(self, Self::Stored(_))
+    }
+}
+
+impl<V: Clone> MaybeStored<'_, V> {
+    /// Returns the value, cloning it if it's stored in the ConstLru
+    pub fn into_owned(self) -> V {
+        match self {
+            Self::Stored(v) => v.clone(),
+            Self::Unstored(v) => v,
+        }
+    }
+}
+
+impl<V> Deref for MaybeStored<'_, V> {
+    type Target = V;
+
+    fn deref(&self) -> &V {
+        match self {
+            Self::Stored(v) => v,
+            Self::Unstored(v) => v,
+        }
+    }
+}
+
+impl<V> DerefMut for MaybeStored<'_, V> {
+    fn deref_mut(&mut self) -> &mut V {
+        match self {
+            Self::Stored(v) => v,
+            Self::Unstored(v) => v,
+        }
+    }
+}
@@ -1,17 +1,21 @@
 use num_traits::{PrimInt, Unsigned};
 
-use crate::ConstLru;
+use crate::{Comparator, ConstLru, NaturalOrder, OccupiedEntry, TryInsertError};
 
 /// A view into an vacant entry in a ConstLru. It is part of the Entry enum.
 #[derive(Debug)]
-pub struct VacantEntry<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> {
-    const_lru: &'a mut ConstLru<K, V, CAP, I>,
+pub struct VacantEntry<'a, K, V, const CAP: usize, I: PrimInt + Unsigned, C = NaturalOrder> {
+    const_lru: &'a mut ConstLru<K, V, CAP, I, C>,
     key: K,
     insert_bs_i: I,
 }
 
-impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> VacantEntry<'a, K, V, CAP, I> {
-    pub(crate) fn new(const_lru: &'a mut ConstLru<K, V, CAP, I>, key: K, insert_bs_i: I) -> Self {
+impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned, C> VacantEntry<'a, K, V, CAP, I, C> {
+    pub(crate) fn new(
+        const_lru: &'a mut ConstLru<K, V, CAP, I, C>,
+        key: K,
+        insert_bs_i: I,
+    ) -> Self {
         Self {
             const_lru,
             key,
@@ -30,22 +34,72 @@ impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> VacantEntry<'a, K, V, CA
     }
 }
 
-impl<'a, K: Ord, V, const CAP: usize, I: PrimInt + Unsigned> VacantEntry<'a, K, V, CAP, I> {
+impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned, C: Comparator<K>>
+    VacantEntry<'a, K, V, CAP, I, C>
+{
+    /// Inserts the entry at the most-recently-used slot,
+    /// then moves it to `fraction` of the way to the least-recently-used slot if given.
+    ///
+    /// Returns the ConstLru, the index tuple of the inserted entry and the LRU evicted entry, if ConstLru was full
+    #[allow(clippy::type_complexity)]
+    fn insert_inner(
+        self,
+        v: V,
+        fraction: Option<f32>,
+    ) -> (&'a mut ConstLru<K, V, CAP, I, C>, (I, I), Option<(K, V)>) {
+        let const_lru = self.const_lru;
+        let (tup, evicted) = if const_lru.is_full() {
+            let (tup, evicted) = const_lru.insert_evict_lru(self.insert_bs_i, self.key, v);
+            (tup, Some(evicted))
+        } else {
+            let i = const_lru.insert_alloc_new(self.insert_bs_i, self.key, v);
+            ((i, self.insert_bs_i), None)
+        };
+        if let Some(fraction) = fraction {
+            let n = const_lru.position_at_fraction(fraction);
+            const_lru.list.move_to_position(tup.0, n);
+        }
+        (const_lru, tup, evicted)
+    }
+
+    fn insert_inner_mut(self, v: V, fraction: Option<f32>) -> (&'a mut V, Option<(K, V)>) {
+        let (const_lru, (i, _), evicted) = self.insert_inner(v, fraction);
+        (const_lru.list.get_mut_by_index(i), evicted)
+    }
+
     /// Sets the value of the entry with the `VacantEntry`’s key, and returns:
     /// - a mutable reference to the new value
     /// - LRU evicted entry, if ConstLru is full
     pub fn insert(self, v: V) -> (&'a mut V, Option<(K, V)>) {
-        let (i, opt) = if self.const_lru.is_full() {
-            let (i, (old_k, old_v)) =
-                self.const_lru
-                    .insert_evict_lru(self.insert_bs_i, self.key, v);
-            (i, Some((old_k, old_v)))
-        } else {
-            let i = self
-                .const_lru
-                .insert_alloc_new(self.insert_bs_i, self.key, v);
-            (i, None)
-        };
-        (self.const_lru.get_mut_by_index(i), opt)
+        self.insert_inner_mut(v, None)
+    }
+
+    /// Same as [`Self::insert`], but returns an [`OccupiedEntry`] for the new entry instead of a mutable reference to its value
+    #[allow(clippy::type_complexity)]
+    pub fn insert_entry(self, v: V) -> (OccupiedEntry<'a, K, V, CAP, I, C>, Option<(K, V)>) {
+        let (const_lru, tup, evicted) = self.insert_inner(v, None);
+        (OccupiedEntry::new_keyless(const_lru, tup), evicted)
+    }
+
+    /// Same as [`Self::insert`], but fails with [`TryInsertError::Full`] instead of evicting
+    /// the least-recently-used entry if the ConstLru is full.
+    pub fn try_insert(self, v: V) -> Result<&'a mut V, TryInsertError<'a, K, V, CAP, I, C>> {
+        if self.const_lru.is_full() {
+            return Err(TryInsertError::Full(self.key, v));
+        }
+        Ok(self.insert(v).0)
+    }
+
+    /// Same as [`Self::insert`], but the entry is inserted at the least-recently-used slot,
+    /// so that it is evicted next. See [`ConstLru::insert_cold`].
+    pub fn insert_cold(self, v: V) -> (&'a mut V, Option<(K, V)>) {
+        self.insert_inner_mut(v, Some(1.0))
+    }
+
+    /// Same as [`Self::insert`], but the entry is inserted at `fraction` of the way
+    /// from the most-recently-used slot (`0.0`) to the least-recently-used one (`1.0`).
+    /// See [`ConstLru::insert_at_fraction`].
+    pub fn insert_at_fraction(self, v: V, fraction: f32) -> (&'a mut V, Option<(K, V)>) {
+        self.insert_inner_mut(v, Some(fraction))
     }
 }
@@ -1,6 +1,10 @@
 use core::fmt::{Debug, Display};
 
-/// Error type of `TryFrom<[(K, V); CAP]>`
+use num_traits::{PrimInt, Unsigned};
+
+use crate::{NaturalOrder, OccupiedEntry};
+
+/// Error type of [`ConstLru::try_from_iter_strict`](crate::ConstLru::try_from_iter_strict)
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
 pub struct DuplicateKeysError<K>(
     /// The first duplicate key found
@@ -12,3 +16,45 @@ impl<K: Debug> Display for DuplicateKeysError<K> {
         write!(f, "duplicate key: {:#?}", self.0)
     }
 }
+
+/// Error type of [`ConstLru::init_in`](crate::ConstLru::init_in)
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CapacityError {
+    /// `CAP > I::MAX`
+    CapExceedsIndexMax,
+
+    /// `I::MAX > usize::MAX`
+    IndexExceedsUsizeMax,
+}
+
+impl Display for CapacityError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::CapExceedsIndexMax => write!(f, "CAP > I::MAX"),
+            Self::IndexExceedsUsizeMax => write!(f, "I::MAX > usize::MAX"),
+        }
+    }
+}
+
+/// Error type of [`ConstLru::try_insert`](crate::ConstLru::try_insert) and [`VacantEntry::try_insert`](crate::VacantEntry::try_insert)
+#[derive(Debug)]
+pub enum TryInsertError<'a, K, V, const CAP: usize, I: PrimInt + Unsigned, C = NaturalOrder> {
+    /// Inserting would have evicted the least-recently-used entry, or `CAP == 0`.
+    /// Contains the key and value that were not inserted
+    Full(K, V),
+
+    /// The key is already present.
+    /// Contains its entry, which was not moved in the LRU order, and the value that was not inserted
+    Occupied(OccupiedEntry<'a, K, V, CAP, I, C>, V),
+}
+
+impl<K: Debug, V, const CAP: usize, I: PrimInt + Unsigned, C> Display
+    for TryInsertError<'_, K, V, CAP, I, C>
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Full(k, _) => write!(f, "full, cannot insert key: {k:#?}"),
+            Self::Occupied(e, _) => write!(f, "key already present: {:#?}", e.key()),
+        }
+    }
+}
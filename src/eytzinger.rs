@@ -0,0 +1,530 @@
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt::Debug;
+use core::hash::{Hash, Hasher};
+use core::mem::MaybeUninit;
+use core::ops::Deref;
+use core::ptr::{self, addr_of_mut};
+use num_traits::{PrimInt, Unsigned};
+
+use crate::lru_list::LruList;
+use crate::{
+    CapacityError, Comparator, ConstLru, Entry, InsertReplaced, IterKeyOrderMut, IterMut,
+    NaturalOrder,
+};
+
+/// A [`ConstLru`] with an additional copy of its sorted keys laid out in Eytzinger (breadth-first) order
+/// to speed up lookups for large `CAP`.
+///
+/// Binary search over the sorted index loads a key from a random slot at every probe,
+/// while the Eytzinger copy stores the keys inline so each probe is a single load,
+/// the first few levels share a handful of cache lines and each probe's children are adjacent.
+/// This costs an extra `CAP * (size_of::<K>() + size_of::<I>())` bytes.
+///
+/// The Eytzinger copy can't be updated incrementally, so it is rebuilt in `O(N)` by cloning the keys on the next
+/// [`Self::get`] or [`Self::get_mut`] after the keys of the cache change.
+/// This makes it suitable for read-heavy workloads where inserts and removes come in bursts.
+/// [`Self::get_untouched`] can't rebuild the index so it falls back to binary search if the index is out of date.
+///
+/// Derefs to the underlying [`ConstLru`] for read-only operations.
+pub struct EytzingerLru<K, V, const CAP: usize, I: PrimInt + Unsigned = usize, C = NaturalOrder> {
+    const_lru: ConstLru<K, V, CAP, I, C>,
+
+    eytzinger: EytzingerIndex<K, CAP, I>,
+}
+
+/// Clones of the keys in `bs_index` and their indices in Eytzinger order:
+/// root at 0, children of `i` at `2i + 1` and `2i + 2`
+struct EytzingerIndex<K, const CAP: usize, I> {
+    /// keys[..len] are valid
+    keys: [MaybeUninit<K>; CAP],
+
+    /// indices[..len] are valid
+    indices: [I; CAP],
+
+    len: usize,
+
+    /// whether the keys of the `ConstLru` changed since this was last built
+    is_dirty: bool,
+
+    /// set by [`EytzingerLru::entry`] if this was up to date,
+    /// to check whether the returned entry inserted or removed a key
+    entry_snapshot: Option<EntrySnapshot>,
+}
+
+/// State of the `ConstLru` before an entry returned by [`EytzingerLru::entry`] was used
+#[derive(Clone, Copy)]
+struct EntrySnapshot {
+    len: usize,
+
+    /// Eytzinger position of the least-recently-used key if the `ConstLru` was full,
+    /// since inserting then evicts it and reuses its slot without changing `len`
+    lru_pos: Option<usize>,
+}
+
+impl<K, const CAP: usize, I> EytzingerIndex<K, CAP, I> {
+    /// Drops the key clones
+    fn clear(&mut self) {
+        let len = self.len;
+        // set first so that a panicking drop leaks instead of double-dropping
+        self.len = 0;
+        for k in self.keys[..len].iter_mut() {
+            unsafe { k.assume_init_drop() };
+        }
+    }
+}
+
+impl<K, const CAP: usize, I> Drop for EytzingerIndex<K, CAP, I> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned, C> EytzingerLru<K, V, CAP, I, C> {
+    /// Creates a new empty `EytzingerLru` on the stack
+    ///
+    /// Panics and might overflow the stack like [`ConstLru::new`].
+    /// Use [`Self::init_at_alloc`] to initialize larger variants at preallocated memory
+    pub fn new() -> Self {
+        let mut res: MaybeUninit<Self> = MaybeUninit::uninit();
+        unsafe {
+            Self::init_at_alloc(res.as_mut_ptr());
+            res.assume_init()
+        }
+    }
+
+    /// Initializes the EytzingerLru at a region of allocated memory
+    ///
+    /// # Safety
+    /// `ptr` must point to uninitialized memory, since init()
+    /// overwrites the data at `ptr`
+    ///
+    /// Panics like [`ConstLru::init_at_alloc`].
+    ///
+    /// Use [`Self::init_in`] for a safe, non-panicking alternative.
+    pub unsafe fn init_at_alloc(ptr: *mut Self) {
+        if let Err(e) = LruList::<K, V, CAP, I>::check_cap() {
+            panic!("{e}");
+        }
+        Self::init_at_alloc_unchecked(ptr);
+    }
+
+    /// Initializes the EytzingerLru in `slot` and returns a mutable reference to it.
+    ///
+    /// Errors and overwrites `slot` like [`ConstLru::init_in`].
+    pub fn init_in(slot: &mut MaybeUninit<Self>) -> Result<&mut Self, CapacityError> {
+        LruList::<K, V, CAP, I>::check_cap()?;
+        unsafe {
+            Self::init_at_alloc_unchecked(slot.as_mut_ptr());
+            Ok(slot.assume_init_mut())
+        }
+    }
+
+    /// Requirements:
+    /// - `LruList::check_cap()` is `Ok`
+    /// - `ptr` points to allocated memory that can be overwritten
+    unsafe fn init_at_alloc_unchecked(ptr: *mut Self) {
+        ConstLru::init_at_alloc_unchecked(addr_of_mut!((*ptr).const_lru));
+        Self::init_index_at_alloc(addr_of_mut!((*ptr).eytzinger));
+    }
+
+    /// Requirements:
+    /// - `ptr` points to allocated memory that can be overwritten
+    unsafe fn init_index_at_alloc(ptr: *mut EytzingerIndex<K, CAP, I>) {
+        // keys should remain uninitialized
+        // UB if not initialized
+        let cap = I::from(CAP).unwrap();
+        for i in 0..CAP {
+            addr_of_mut!((*ptr).indices[i]).write(cap);
+        }
+        addr_of_mut!((*ptr).len).write(0);
+        // empty index is trivially up to date
+        addr_of_mut!((*ptr).is_dirty).write(false);
+        addr_of_mut!((*ptr).entry_snapshot).write(None);
+    }
+
+    /// Creates an iterator that iterates through the keys and mutable values of the `EytzingerLru` from most-recently-used to least-recently-used
+    ///
+    /// Does not change the LRU order of the elements, even if mutated.
+    ///
+    /// Double-ended: reversing iterates from least-recently-used to most-recently-used
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V, CAP, I> {
+        self.const_lru.iter_mut()
+    }
+
+    /// Creates an iterator that iterates through the keys and mutable values of the `EytzingerLru` in the order of its keys
+    ///
+    /// Does not change the LRU order of the elements, even if mutated.
+    ///
+    /// Double-ended: reversing iterates from descending order of its keys
+    pub fn iter_key_order_mut(&mut self) -> IterKeyOrderMut<'_, K, V, CAP, I> {
+        self.const_lru.iter_key_order_mut()
+    }
+
+    /// Clears the `EytzingerLru`, removing all key-value pairs.
+    pub fn clear(&mut self) {
+        self.const_lru.clear();
+        self.eytzinger.clear();
+        self.eytzinger.is_dirty = false;
+        self.eytzinger.entry_snapshot = None;
+    }
+
+    /// Returns the underlying `ConstLru`
+    pub fn into_inner(self) -> ConstLru<K, V, CAP, I, C> {
+        self.const_lru
+    }
+}
+
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned, C: Comparator<K>>
+    EytzingerLru<K, V, CAP, I, C>
+{
+    /// Same as [`ConstLru::insert`]. Marks the Eytzinger copy out of date if the keys changed.
+    pub fn insert(&mut self, k: K, v: V) -> Option<InsertReplaced<K, V>> {
+        self.check_entry_snapshot();
+        let len = self.const_lru.len();
+        let res = self.const_lru.insert(k, v);
+        // replacing a value leaves the keys unchanged
+        if self.const_lru.len() != len || matches!(res, Some(InsertReplaced::LruEvicted(..))) {
+            self.eytzinger.is_dirty = true;
+        }
+        res
+    }
+
+    /// Same as [`ConstLru::remove`]. Marks the Eytzinger copy out of date if the keys changed.
+    pub fn remove<Q: ?Sized>(&mut self, k: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        self.check_entry_snapshot();
+        let res = self.const_lru.remove(k);
+        if res.is_some() {
+            self.eytzinger.is_dirty = true;
+        }
+        res
+    }
+
+    /// Same as [`ConstLru::entry`]. The Eytzinger copy is marked out of date on the next call
+    /// if the returned entry was used to insert or remove a key.
+    ///
+    /// **panics** if CAP == 0
+    pub fn entry(&mut self, k: K) -> Entry<'_, K, V, CAP, I, C> {
+        self.check_entry_snapshot();
+        if !self.eytzinger.is_dirty {
+            let len = self.const_lru.len().to_usize().unwrap();
+            let lru_pos = if len > 0 && self.const_lru.is_full() {
+                let lru_k = self
+                    .const_lru
+                    .list
+                    .get_key_by_index(self.const_lru.list.tail);
+                self.eytzinger_pos_of(lru_k)
+            } else {
+                None
+            };
+            self.eytzinger.entry_snapshot = Some(EntrySnapshot { len, lru_pos });
+        }
+        self.const_lru.entry(k)
+    }
+
+    /// Whether the keys changed since the Eytzinger copy was last built
+    fn is_dirty(&self) -> bool {
+        self.eytzinger.is_dirty
+            || self
+                .eytzinger
+                .entry_snapshot
+                .is_some_and(|snapshot| self.entry_changed_keys(snapshot))
+    }
+
+    /// Marks the Eytzinger copy out of date if the entry returned by the last [`Self::entry`]
+    /// inserted or removed a key
+    fn check_entry_snapshot(&mut self) {
+        if let Some(snapshot) = self.eytzinger.entry_snapshot.take() {
+            if self.entry_changed_keys(snapshot) {
+                self.eytzinger.is_dirty = true;
+            }
+        }
+    }
+
+    /// Requirements:
+    /// - no keys were inserted or removed except through the entry returned by the [`Self::entry`] that took `snapshot`
+    fn entry_changed_keys(&self, snapshot: EntrySnapshot) -> bool {
+        if self.const_lru.len().to_usize().unwrap() != snapshot.len {
+            return true;
+        }
+        // if len is unchanged and the ConstLru was full, the only possible change is an insert that evicted the
+        // least-recently-used entry, whose slot now holds the new key
+        snapshot.lru_pos.is_some_and(|pos| {
+            let EytzingerIndex { keys, indices, .. } = &self.eytzinger;
+            let k = self.const_lru.list.get_key_by_index(indices[pos]);
+            <C as Comparator<K>>::cmp(k, unsafe { keys[pos].assume_init_ref() }) != Ordering::Equal
+        })
+    }
+
+    /// Returns a reference to the value corresponding to the key without updating the entry to most-recently-used slot
+    ///
+    /// Uses binary search over the sorted index if the Eytzinger copy is out of date.
+    ///
+    /// To update to most-recently-used, use [`Self::get`]
+    pub fn get_untouched<Q: ?Sized>(&self, k: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        if self.is_dirty() {
+            return self.const_lru.get_untouched(k);
+        }
+        let index = self.eytzinger_index_of(k)?;
+        Some(self.const_lru.list.get_by_index(index))
+    }
+
+    /// Requirements:
+    /// - !self.is_dirty()
+    fn eytzinger_index_of<Q: ?Sized>(&self, k: &Q) -> Option<I>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        self.eytzinger_pos_of(k)
+            .map(|pos| self.eytzinger.indices[pos])
+    }
+
+    /// Returns the position of `k` in the Eytzinger copy
+    ///
+    /// Requirements:
+    /// - the Eytzinger copy is up to date
+    fn eytzinger_pos_of<Q: ?Sized>(&self, k: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        let EytzingerIndex { keys, len, .. } = &self.eytzinger;
+        let len = *len;
+        let key_at = |j: usize| unsafe { keys[j - 1].assume_init_ref() }.borrow();
+        // 1-indexed so that children of j are 2j and 2j + 1.
+        // Branchless descent: only go right if probe < k, check for equality at the end
+        let mut j = 1;
+        while j <= len {
+            let is_less = <C as Comparator<Q>>::cmp(key_at(j), k) == Ordering::Less;
+            j = 2 * j + usize::from(is_less);
+        }
+        // Undo the right turns taken after the last left turn, and the last left turn itself,
+        // to get the smallest key >= k. j == 0 if all keys < k
+        j >>= j.trailing_ones() + 1;
+        if j == 0 {
+            return None;
+        }
+        match <C as Comparator<Q>>::cmp(key_at(j), k) {
+            Ordering::Equal => Some(j - 1),
+            _ => None,
+        }
+    }
+}
+
+impl<K: Clone, V, const CAP: usize, I: PrimInt + Unsigned, C: Comparator<K>>
+    EytzingerLru<K, V, CAP, I, C>
+{
+    /// Rebuilds the Eytzinger copy if the keys changed since it was last built.
+    ///
+    /// Called automatically by [`Self::get`], [`Self::get_mut`] and [`Self::get_mut_untouched`].
+    /// Call this after a burst of inserts or removes to let [`Self::get_untouched`] use the Eytzinger copy.
+    pub fn rebuild_index(&mut self) {
+        self.check_entry_snapshot();
+        if !self.eytzinger.is_dirty {
+            return;
+        }
+        self.eytzinger.clear();
+        let len = self.const_lru.len().to_usize().unwrap();
+        let mut bs_i = 0;
+        // if a clone panics, the clones made so far are leaked since len is still 0
+        Self::fill_subtree(&mut self.eytzinger, &self.const_lru, len, &mut bs_i, 0);
+        self.eytzinger.len = len;
+        self.eytzinger.is_dirty = false;
+    }
+
+    /// In-order traversal of the implicit tree rooted at `e_i`,
+    /// filling it with consecutive entries of `bs_index` starting from `bs_i`.
+    ///
+    /// Recursion depth is at most log2(CAP)
+    fn fill_subtree(
+        eytzinger: &mut EytzingerIndex<K, CAP, I>,
+        const_lru: &ConstLru<K, V, CAP, I, C>,
+        len: usize,
+        bs_i: &mut usize,
+        e_i: usize,
+    ) {
+        if e_i >= len {
+            return;
+        }
+        Self::fill_subtree(eytzinger, const_lru, len, bs_i, 2 * e_i + 1);
+        let index = const_lru.bs_index.get(*bs_i);
+        eytzinger.keys[e_i].write(const_lru.list.get_key_by_index(index).clone());
+        eytzinger.indices[e_i] = index;
+        *bs_i += 1;
+        Self::fill_subtree(eytzinger, const_lru, len, bs_i, 2 * e_i + 2);
+    }
+
+    /// Returns a reference to the value corresponding to the key and moves entry to most-recently-used slot.
+    ///
+    /// Rebuilds the Eytzinger copy first if it's out of date, which clones all keys in `O(N)`.
+    ///
+    /// To not update to most-recently-used, use [`Self::get_untouched`]
+    pub fn get<Q: ?Sized>(&mut self, k: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        self.rebuild_index();
+        let index = self.eytzinger_index_of(k)?;
+        self.const_lru.list.move_to_head(index);
+        Some(self.const_lru.list.get_by_index(index))
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key and moves entry to most-recently-used slot.
+    ///
+    /// Rebuilds the Eytzinger copy first if it's out of date, which clones all keys in `O(N)`.
+    ///
+    /// To not update to most-recently-used, use [`Self::get_mut_untouched`]
+    pub fn get_mut<Q: ?Sized>(&mut self, k: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        self.rebuild_index();
+        let index = self.eytzinger_index_of(k)?;
+        self.const_lru.list.move_to_head(index);
+        Some(self.const_lru.list.get_mut_by_index(index))
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key without updating the entry to most-recently-used slot
+    ///
+    /// Rebuilds the Eytzinger copy first if it's out of date, which clones all keys in `O(N)`.
+    ///
+    /// To update to most-recently-used, use [`Self::get_mut`]
+    pub fn get_mut_untouched<Q: ?Sized>(&mut self, k: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        self.rebuild_index();
+        let index = self.eytzinger_index_of(k)?;
+        Some(self.const_lru.list.get_mut_by_index(index))
+    }
+}
+
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned, C> Deref for EytzingerLru<K, V, CAP, I, C> {
+    type Target = ConstLru<K, V, CAP, I, C>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.const_lru
+    }
+}
+
+/// Moves the `ConstLru` into an `EytzingerLru`. The Eytzinger copy is built on first use.
+///
+/// Might overflow the stack like [`ConstLru::new`].
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned, C> From<ConstLru<K, V, CAP, I, C>>
+    for EytzingerLru<K, V, CAP, I, C>
+{
+    fn from(const_lru: ConstLru<K, V, CAP, I, C>) -> Self {
+        let mut res: MaybeUninit<Self> = MaybeUninit::uninit();
+        let ptr = res.as_mut_ptr();
+        unsafe {
+            addr_of_mut!((*ptr).const_lru).write(const_lru);
+            Self::init_index_at_alloc(addr_of_mut!((*ptr).eytzinger));
+            (*ptr).eytzinger.is_dirty = true;
+            res.assume_init()
+        }
+    }
+}
+
+impl<K: Clone, V: Clone, const CAP: usize, I: PrimInt + Unsigned, C> EytzingerLru<K, V, CAP, I, C> {
+    /// Clones the EytzingerLru to a region of allocated memory
+    ///
+    /// # Safety
+    /// `dst` must point to uninitialized memory, since this
+    /// overwrites the data at `dst`
+    pub unsafe fn clone_to_alloc(&self, dst: *mut Self) {
+        self.const_lru
+            .clone_to_alloc(addr_of_mut!((*dst).const_lru));
+
+        let src = &self.eytzinger;
+        let dst = addr_of_mut!((*dst).eytzinger);
+        // raw memmove for the same reason as in `LruList::clone_links_to_alloc`
+        ptr::copy(
+            src.indices.as_ptr(),
+            addr_of_mut!((*dst).indices) as *mut I,
+            CAP,
+        );
+        for i in 0..src.len {
+            addr_of_mut!((*dst).keys[i])
+                .write(MaybeUninit::new(src.keys[i].assume_init_ref().clone()));
+        }
+        addr_of_mut!((*dst).len).write(src.len);
+        addr_of_mut!((*dst).is_dirty).write(src.is_dirty);
+        // the clone has the same entry indices, so the snapshot stays valid
+        addr_of_mut!((*dst).entry_snapshot).write(src.entry_snapshot);
+    }
+
+    /// Clones the EytzingerLru into `slot` and returns a mutable reference to the clone.
+    ///
+    /// Safe alternative to [`Self::clone_to_alloc`].
+    ///
+    /// Overwrites `slot` like [`ConstLru::init_in`].
+    pub fn clone_into_uninit<'a>(&self, slot: &'a mut MaybeUninit<Self>) -> &'a mut Self {
+        unsafe {
+            self.clone_to_alloc(slot.as_mut_ptr());
+            slot.assume_init_mut()
+        }
+    }
+}
+
+/// To clone a large `EytzingerLru` without overflowing the stack, use [`EytzingerLru::clone_into_uninit`]
+impl<K: Clone, V: Clone, const CAP: usize, I: PrimInt + Unsigned, C> Clone
+    for EytzingerLru<K, V, CAP, I, C>
+{
+    fn clone(&self) -> Self {
+        let mut res: MaybeUninit<Self> = MaybeUninit::uninit();
+        unsafe {
+            self.clone_to_alloc(res.as_mut_ptr());
+            res.assume_init()
+        }
+    }
+}
+
+// not derived so that `C` doesn't have to be `Debug`,
+// Eytzinger copy is left out since it duplicates the keys
+impl<K: Debug, V: Debug, const CAP: usize, I: PrimInt + Unsigned, C> Debug
+    for EytzingerLru<K, V, CAP, I, C>
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.const_lru.fmt(f)
+    }
+}
+
+/// Map equality that ignores LRU order, same as `ConstLru`'s
+impl<K: PartialEq, V: PartialEq, const CAP: usize, I: PrimInt + Unsigned, C> PartialEq
+    for EytzingerLru<K, V, CAP, I, C>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.const_lru == other.const_lru
+    }
+}
+
+impl<K: Eq, V: Eq, const CAP: usize, I: PrimInt + Unsigned, C> Eq
+    for EytzingerLru<K, V, CAP, I, C>
+{
+}
+
+impl<K: Hash, V: Hash, const CAP: usize, I: PrimInt + Unsigned, C> Hash
+    for EytzingerLru<K, V, CAP, I, C>
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.const_lru.hash(state);
+    }
+}
+
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned, C> Default for EytzingerLru<K, V, CAP, I, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,51 @@
+use core::hash::{BuildHasher, Hash};
+
+use num_traits::{PrimInt, Unsigned};
+
+use crate::hash::{ConstHashLru, DefaultHashBuilder};
+
+/// A view into an vacant entry in a ConstHashLru. It is part of the Entry enum.
+#[derive(Debug)]
+pub struct VacantEntry<'a, K, V, const CAP: usize, I: PrimInt + Unsigned, S = DefaultHashBuilder> {
+    const_lru: &'a mut ConstHashLru<K, V, CAP, I, S>,
+    key: K,
+    hash: u64,
+}
+
+impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned, S> VacantEntry<'a, K, V, CAP, I, S> {
+    pub(crate) fn new(const_lru: &'a mut ConstHashLru<K, V, CAP, I, S>, key: K, hash: u64) -> Self {
+        Self {
+            const_lru,
+            key,
+            hash,
+        }
+    }
+
+    /// Take ownership of the key.
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Gets a reference to the key that would be used when inserting a value through the `VacantEntry`.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+}
+
+impl<'a, K: Hash + Eq, V, const CAP: usize, I: PrimInt + Unsigned, S: BuildHasher>
+    VacantEntry<'a, K, V, CAP, I, S>
+{
+    /// Sets the value of the entry with the `VacantEntry`’s key, and returns:
+    /// - a mutable reference to the new value
+    /// - LRU evicted entry, if ConstHashLru is full
+    pub fn insert(self, v: V) -> (&'a mut V, Option<(K, V)>) {
+        let (i, opt) = if self.const_lru.is_full() {
+            let (i, (old_k, old_v)) = self.const_lru.insert_evict_lru(self.hash, self.key, v);
+            (i, Some((old_k, old_v)))
+        } else {
+            let i = self.const_lru.insert_alloc_new(self.hash, self.key, v);
+            (i, None)
+        };
+        (self.const_lru.list.get_mut_by_index(i), opt)
+    }
+}
@@ -0,0 +1,60 @@
+use core::hash::{BuildHasherDefault, Hasher};
+
+/// Default `BuildHasher` of [`ConstHashLru`](crate::ConstHashLru)
+pub type DefaultHashBuilder = BuildHasherDefault<FxHasher>;
+
+const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+/// The fast, non-cryptographic hash function used by rustc and Firefox.
+///
+/// It is not resistant to HashDoS. If keys can be chosen by an adversary,
+/// use a keyed `BuildHasher` instead.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct FxHasher {
+    hash: u64,
+}
+
+impl FxHasher {
+    fn add_to_hash(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(SEED);
+    }
+}
+
+impl Hasher for FxHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            self.add_to_hash(u64::from_le_bytes(chunk.try_into().unwrap()));
+        }
+        let rem = chunks.remainder();
+        if !rem.is_empty() {
+            let mut buf = [0u8; 8];
+            buf[..rem.len()].copy_from_slice(rem);
+            self.add_to_hash(u64::from_le_bytes(buf));
+        }
+    }
+
+    fn write_u8(&mut self, i: u8) {
+        self.add_to_hash(i.into());
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.add_to_hash(i.into());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.add_to_hash(i.into());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.add_to_hash(i);
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.add_to_hash(i as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
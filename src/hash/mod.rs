@@ -0,0 +1,470 @@
+//! [`ConstHashLru`], a variant of [`ConstLru`](crate::ConstLru) indexed by a hash table instead of a sorted index.
+
+use core::borrow::Borrow;
+use core::fmt::Debug;
+use core::hash::{BuildHasher, Hash, Hasher};
+use core::mem::MaybeUninit;
+use core::ptr::addr_of_mut;
+use num_traits::{PrimInt, Unsigned};
+
+mod entry;
+mod fx;
+mod table;
+
+pub use entry::*;
+pub use fx::*;
+
+use crate::lru_list::LruList;
+use crate::{CapacityError, InsertReplaced, IntoIter, Iter, IterMut};
+use table::RobinHoodTable;
+
+/// Constant capacity key-addressed LRU cache with `O(1)` lookups, inserts and removes.
+///
+/// Same as [`ConstLru`](crate::ConstLru), but keys are looked up through a fixed-size
+/// open-addressing hash table of `2 * CAP` buckets instead of a sorted index.
+/// This avoids the `O(N)` shifting of the sorted index at the cost of 4x its memory usage
+/// and losing key-order iteration.
+///
+/// Generics:
+/// - `K`. Type of key. `Hash + Eq` is used for lookup and to address entries.
+/// - `V`. Type of value.
+/// - `CAP`. Capacity of the cache.
+/// - `I`. Type of the index used. Must be an unsigned primitive type with bitwidth <= `usize`'s bitwidth.
+/// - `S`. `BuildHasher` used to hash the keys. Defaults to [`DefaultHashBuilder`].
+pub struct ConstHashLru<
+    K,
+    V,
+    const CAP: usize,
+    I: PrimInt + Unsigned = usize,
+    S = DefaultHashBuilder,
+> {
+    list: LruList<K, V, CAP, I>,
+
+    table: RobinHoodTable<I, CAP>,
+
+    hash_builder: S,
+}
+
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned, S: Default> ConstHashLru<K, V, CAP, I, S> {
+    /// Creates a new empty `ConstHashLru` on the stack
+    ///
+    /// Panics and might overflow the stack like [`ConstLru::new`](crate::ConstLru::new).
+    /// Use [`Self::init_at_alloc`] to initialize larger variants at preallocated memory
+    pub fn new() -> Self {
+        Self::with_hasher(S::default())
+    }
+
+    /// Initializes the ConstHashLru at a region of allocated memory
+    ///
+    /// # Safety
+    /// `ptr` must point to uninitialized memory, since init()
+    /// overwrites the data at `ptr`
+    ///
+    /// Panics like [`ConstLru::init_at_alloc`](crate::ConstLru::init_at_alloc).
+    ///
+    /// Use [`Self::init_in`] for a safe, non-panicking alternative.
+    pub unsafe fn init_at_alloc(ptr: *mut Self) {
+        Self::init_at_alloc_with_hasher(ptr, S::default());
+    }
+
+    /// Initializes the ConstHashLru in `slot` and returns a mutable reference to it.
+    ///
+    /// Errors and overwrites `slot` like [`ConstLru::init_in`](crate::ConstLru::init_in).
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use const_lru::ConstHashLru;
+    /// use core::mem::MaybeUninit;
+    ///
+    /// let mut slot: Box<MaybeUninit<ConstHashLru<u32, u16, 1_000, u16>>> = Box::new_uninit();
+    /// let c = ConstHashLru::init_in(&mut slot).unwrap();
+    /// assert!(c.insert(1, 2).is_none());
+    /// ```
+    pub fn init_in(slot: &mut MaybeUninit<Self>) -> Result<&mut Self, CapacityError> {
+        Self::init_in_with_hasher(slot, S::default())
+    }
+}
+
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned, S> ConstHashLru<K, V, CAP, I, S> {
+    /// Creates a new empty `ConstHashLru` on the stack that uses `hash_builder` to hash its keys
+    ///
+    /// Panics and might overflow the stack like [`ConstLru::new`](crate::ConstLru::new).
+    /// Use [`Self::init_at_alloc_with_hasher`] to initialize larger variants at preallocated memory
+    pub fn with_hasher(hash_builder: S) -> Self {
+        let mut res: MaybeUninit<Self> = MaybeUninit::uninit();
+        unsafe {
+            Self::init_at_alloc_with_hasher(res.as_mut_ptr(), hash_builder);
+            res.assume_init()
+        }
+    }
+
+    /// Initializes the ConstHashLru at a region of allocated memory with the given `hash_builder`
+    ///
+    /// # Safety
+    /// `ptr` must point to uninitialized memory, since init()
+    /// overwrites the data at `ptr`
+    ///
+    /// Panics like [`ConstLru::init_at_alloc`](crate::ConstLru::init_at_alloc).
+    pub unsafe fn init_at_alloc_with_hasher(ptr: *mut Self, hash_builder: S) {
+        if let Err(e) = LruList::<K, V, CAP, I>::check_cap() {
+            panic!("{e}");
+        }
+        Self::init_at_alloc_unchecked(ptr, hash_builder);
+    }
+
+    /// Same as [`Self::init_in`], but with the given `hash_builder`
+    pub fn init_in_with_hasher(
+        slot: &mut MaybeUninit<Self>,
+        hash_builder: S,
+    ) -> Result<&mut Self, CapacityError> {
+        LruList::<K, V, CAP, I>::check_cap()?;
+        unsafe {
+            Self::init_at_alloc_unchecked(slot.as_mut_ptr(), hash_builder);
+            Ok(slot.assume_init_mut())
+        }
+    }
+
+    /// Requirements:
+    /// - `LruList::check_cap()` is `Ok`
+    /// - `ptr` points to allocated memory that can be overwritten
+    unsafe fn init_at_alloc_unchecked(ptr: *mut Self, hash_builder: S) {
+        LruList::init_at_alloc(addr_of_mut!((*ptr).list));
+        RobinHoodTable::init_at_alloc(addr_of_mut!((*ptr).table));
+        addr_of_mut!((*ptr).hash_builder).write(hash_builder);
+    }
+
+    /// Returns a reference to the `ConstHashLru`'s `BuildHasher`
+    pub fn hasher(&self) -> &S {
+        &self.hash_builder
+    }
+
+    /// Creates an iterator that iterates through the keys and values of the `ConstHashLru` from most-recently-used to least-recently-used
+    ///
+    /// Does not change the LRU order of the elements.
+    ///
+    /// Double-ended: reversing iterates from least-recently-used to most-recently-used
+    pub fn iter(&self) -> Iter<'_, K, V, CAP, I> {
+        Iter::from_list(&self.list)
+    }
+
+    /// Creates an iterator that iterates through the keys and mutable values of the `ConstHashLru` from most-recently-used to least-recently-used
+    ///
+    /// Does not change the LRU order of the elements, even if mutated.
+    ///
+    /// Double-ended: reversing iterates from least-recently-used to most-recently-used
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V, CAP, I> {
+        IterMut::from_list(&mut self.list)
+    }
+
+    /// Clears the `ConstHashLru`, removing all key-value pairs.
+    pub fn clear(&mut self) {
+        self.list.drop_cleanup();
+        unsafe {
+            LruList::init_at_alloc(&mut self.list);
+            RobinHoodTable::init_at_alloc(&mut self.table);
+        }
+    }
+
+    /// Returns the maximum number of elements this `ConstHashLru` can hold
+    pub fn cap(&self) -> I {
+        self.list.cap()
+    }
+
+    /// Returns `true` if the `ConstHashLru` contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == I::zero()
+    }
+
+    /// Returns `true` if the `ConstHashLru` has reached max capacity.
+    pub fn is_full(&self) -> bool {
+        self.len() == self.cap()
+    }
+
+    /// Returns the number of elements in the `ConstHashLru`.
+    pub fn len(&self) -> I {
+        self.list.len()
+    }
+
+    /// Returns `true` if both `ConstHashLru`s contain the same key-value pairs in the same LRU order.
+    pub fn eq_with_order<const CAP2: usize, I2: PrimInt + Unsigned, S2>(
+        &self,
+        other: &ConstHashLru<K, V, CAP2, I2, S2>,
+    ) -> bool
+    where
+        K: PartialEq,
+        V: PartialEq,
+    {
+        self.len().to_usize() == other.len().to_usize() && self.iter().eq(other.iter())
+    }
+
+    // Assumes index tuple is of a valid node. Should be result of Ok returned by self.get_index_of()
+    fn remove_by_index(&mut self, (index, pos): (I, usize)) -> (K, V) {
+        self.table.remove_at(pos);
+        self.list.free_by_index(index)
+    }
+}
+
+impl<K: Hash + Eq, V, const CAP: usize, I: PrimInt + Unsigned, S: BuildHasher>
+    ConstHashLru<K, V, CAP, I, S>
+{
+    /// Inserts a key-value pair into the map. The entry is moved to the most-recently-used slot
+    ///
+    /// If `CAP == 0`, `None` is returned.
+    ///
+    /// If the map did not have this key present and is not full, `None` is returned.
+    ///
+    /// If the map did have this key present, the value is updated, and the old value is returned in a [`InsertReplaced::OldValue`].
+    /// The key is not updated, though; this matters for types that can be `==` without being identical.
+    ///
+    /// If the map is full, the least-recently used key-value pair is evicted and returned in a [`InsertReplaced::LruEvicted`].
+    pub fn insert(&mut self, k: K, v: V) -> Option<InsertReplaced<K, V>> {
+        if CAP == 0 {
+            return None;
+        }
+        let hash = match self.get_index_of(&k) {
+            Ok((existing_index, _)) => {
+                return Some(InsertReplaced::OldValue(
+                    self.list.replace_value(existing_index, v),
+                ))
+            }
+            Err(hash) => hash,
+        };
+        if self.is_full() {
+            let (_, (old_k, old_v)) = self.insert_evict_lru(hash, k, v);
+            Some(InsertReplaced::LruEvicted(old_k, old_v))
+        } else {
+            self.insert_alloc_new(hash, k, v);
+            None
+        }
+    }
+
+    // Assumes N > 0 and self is not full
+    // Moves newly inserted elem to MRU position
+    // Returns index entry was inserted into
+    fn insert_alloc_new(&mut self, hash: u64, k: K, v: V) -> I {
+        let free_index = self.list.alloc_new(k, v);
+        self.table.insert(hash, free_index);
+        free_index
+    }
+
+    /// Assumes N > 0 and self is full
+    /// Moves newly inserted elem to MRU position
+    ///
+    /// Returns (index entry was inserted into, evicted entry)
+    fn insert_evict_lru(&mut self, hash: u64, k: K, v: V) -> (I, (K, V)) {
+        // N > 0, tail must be valid
+        let tail = self.list.tail;
+        let evicted_hash = self.hash_builder.hash_one(self.list.get_key_by_index(tail));
+        // compare indices instead of keys, tail must be in the table
+        let Some((evicted_pos, _)) = self.table.find(evicted_hash, |slot| slot == tail) else {
+            unreachable!()
+        };
+        self.table.remove_at(evicted_pos);
+
+        let (i, evicted) = self.list.replace_tail(k, v);
+        self.table.insert(hash, i);
+        (i, evicted)
+    }
+
+    /// Removes a key from the `ConstHashLru`, returning the value at the key if the key was previously in the `ConstHashLru`.
+    pub fn remove<Q: Hash + Eq + ?Sized>(&mut self, k: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+    {
+        let tup = self.get_index_of(k).ok()?;
+        Some(self.remove_by_index(tup).1)
+    }
+
+    /// Returns a reference to the value corresponding to the key and moves entry to most-recently-used slot.
+    ///
+    /// To not update to most-recently-used, use [`Self::get_untouched`]
+    pub fn get<Q: Hash + Eq + ?Sized>(&mut self, k: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+    {
+        let (index, _) = self.get_index_of(k).ok()?;
+        self.list.move_to_head(index);
+        Some(self.list.get_by_index(index))
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key and moves entry to most-recently-used slot.
+    ///
+    /// To not update to most-recently-used, use [`Self::get_mut_untouched`]
+    pub fn get_mut<Q: Hash + Eq + ?Sized>(&mut self, k: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+    {
+        let (index, _) = self.get_index_of(k).ok()?;
+        self.list.move_to_head(index);
+        Some(self.list.get_mut_by_index(index))
+    }
+
+    /// Ok(kv_i, bucket position)
+    ///
+    /// Err(hash of k)
+    fn get_index_of<Q: Hash + Eq + ?Sized>(&self, k: &Q) -> Result<(I, usize), u64>
+    where
+        K: Borrow<Q>,
+    {
+        let hash = self.hash_builder.hash_one(k);
+        self.table
+            .find(hash, |slot| self.list.get_key_by_index(slot).borrow() == k)
+            .map(|(pos, index)| (index, pos))
+            .ok_or(hash)
+    }
+
+    /// Returns a reference to the value corresponding to the key without updating the entry to most-recently-used slot
+    ///
+    /// To update to most-recently-used, use [`Self::get`]
+    pub fn get_untouched<Q: Hash + Eq + ?Sized>(&self, k: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+    {
+        let (index, _) = self.get_index_of(k).ok()?;
+        Some(self.list.get_by_index(index))
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key without updating the entry to most-recently-used slot
+    ///
+    /// To update to most-recently-used, use [`Self::get_mut`]
+    pub fn get_mut_untouched<Q: Hash + Eq + ?Sized>(&mut self, k: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+    {
+        let (index, _) = self.get_index_of(k).ok()?;
+        Some(self.list.get_mut_by_index(index))
+    }
+
+    /// Gets the given key’s corresponding entry in the map for in-place manipulation.
+    ///
+    /// **panics** if CAP == 0
+    pub fn entry(&mut self, k: K) -> Entry<'_, K, V, CAP, I, S> {
+        Entry::new(self, k)
+    }
+
+    /// Returns `true` if both `ConstHashLru`s contain the same key-value pairs, regardless of their LRU order.
+    ///
+    /// This is what `==` compares. Each entry of `self` is looked up in `other`.
+    pub fn eq_as_map<const CAP2: usize, I2: PrimInt + Unsigned>(
+        &self,
+        other: &ConstHashLru<K, V, CAP2, I2, S>,
+    ) -> bool
+    where
+        V: PartialEq,
+    {
+        self.len().to_usize() == other.len().to_usize()
+            && self
+                .iter()
+                .all(|(k, v)| other.get_untouched(k).is_some_and(|other_v| v == other_v))
+    }
+}
+
+impl<K: Clone, V: Clone, const CAP: usize, I: PrimInt + Unsigned, S: Clone>
+    ConstHashLru<K, V, CAP, I, S>
+{
+    /// Clones the ConstHashLru to a region of allocated memory
+    ///
+    /// # Safety
+    /// `dst` must point to uninitialized memory, since this
+    /// overwrites the data at `dst`
+    pub unsafe fn clone_to_alloc(&self, dst: *mut Self) {
+        let dst_list = addr_of_mut!((*dst).list);
+        self.list.clone_links_to_alloc(dst_list);
+        self.table.clone_to_alloc(addr_of_mut!((*dst).table));
+        addr_of_mut!((*dst).hash_builder).write(self.hash_builder.clone());
+
+        for index in self.table.slots() {
+            self.list.clone_entry_to_alloc(dst_list, index);
+        }
+    }
+
+    /// Clones the ConstHashLru into `slot` and returns a mutable reference to the clone.
+    ///
+    /// Safe alternative to [`Self::clone_to_alloc`].
+    ///
+    /// Overwrites `slot` like [`ConstLru::init_in`](crate::ConstLru::init_in).
+    pub fn clone_into_uninit<'a>(&self, slot: &'a mut MaybeUninit<Self>) -> &'a mut Self {
+        unsafe {
+            self.clone_to_alloc(slot.as_mut_ptr());
+            slot.assume_init_mut()
+        }
+    }
+}
+
+/// To clone a large `ConstHashLru` without overflowing the stack, use [`ConstHashLru::clone_into_uninit`]
+impl<K: Clone, V: Clone, const CAP: usize, I: PrimInt + Unsigned, S: Clone> Clone
+    for ConstHashLru<K, V, CAP, I, S>
+{
+    fn clone(&self) -> Self {
+        let mut res: MaybeUninit<Self> = MaybeUninit::uninit();
+        unsafe {
+            self.clone_to_alloc(res.as_mut_ptr());
+            res.assume_init()
+        }
+    }
+}
+
+// not derived so that `S` doesn't have to be `Debug`
+/// Formats the entries as a map from most-recently-used to least-recently-used
+impl<K: Debug, V: Debug, const CAP: usize, I: PrimInt + Unsigned, S> Debug
+    for ConstHashLru<K, V, CAP, I, S>
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Map equality that ignores LRU order, see [`ConstHashLru::eq_as_map`].
+/// Use [`ConstHashLru::eq_with_order`] to also compare LRU order.
+impl<K: Hash + Eq, V: PartialEq, const CAP: usize, I: PrimInt + Unsigned, S: BuildHasher> PartialEq
+    for ConstHashLru<K, V, CAP, I, S>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.eq_as_map(other)
+    }
+}
+
+impl<K: Hash + Eq, V: Eq, const CAP: usize, I: PrimInt + Unsigned, S: BuildHasher> Eq
+    for ConstHashLru<K, V, CAP, I, S>
+{
+}
+
+/// Hashes the length and the wrapping sum of the entries' hashes, consistent with `PartialEq`
+/// ignoring the order of the entries.
+///
+/// Entries are hashed with [`FxHasher`] instead of `S`, since equal maps can have differently seeded `S`s.
+impl<K: Hash, V: Hash, const CAP: usize, I: PrimInt + Unsigned, S> Hash
+    for ConstHashLru<K, V, CAP, I, S>
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len().to_usize().unwrap().hash(state);
+        let mut sum: u64 = 0;
+        for entry in self.iter() {
+            let mut hasher = FxHasher::default();
+            entry.hash(&mut hasher);
+            sum = sum.wrapping_add(hasher.finish());
+        }
+        sum.hash(state);
+    }
+}
+
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned, S: Default> Default
+    for ConstHashLru<K, V, CAP, I, S>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned, S> IntoIterator
+    for ConstHashLru<K, V, CAP, I, S>
+{
+    type Item = <IntoIter<K, V, CAP, I> as Iterator>::Item;
+
+    type IntoIter = IntoIter<K, V, CAP, I>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::from_list(self.list)
+    }
+}
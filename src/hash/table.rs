@@ -0,0 +1,177 @@
+use core::ptr::{self, addr_of_mut};
+
+use num_traits::{PrimInt, Unsigned};
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Bucket<I> {
+    /// index of the entry in the LruList
+    ///
+    /// bucket is empty if value == CAP
+    slot: I,
+
+    /// distance of this bucket from the entry's home bucket
+    dist: I,
+}
+
+/// Open-addressing hash table of `2 * CAP` buckets
+/// that maps hashes of keys to their indices in the LruList.
+///
+/// Uses robin-hood hashing with backward-shift deletion.
+/// Since the table is never more than half full, probe sequences stay short
+/// and there is always an empty bucket to terminate them.
+///
+/// Keys are not stored in the table,
+/// matching a probed bucket is left to the cache that owns this.
+#[derive(Debug)]
+pub(crate) struct RobinHoodTable<I: PrimInt + Unsigned, const CAP: usize> {
+    // [[_; 2]; CAP] instead of [_; 2 * CAP] since
+    // generic const exprs are not stable yet
+    buckets: [[Bucket<I>; 2]; CAP],
+}
+
+impl<I: PrimInt + Unsigned, const CAP: usize> RobinHoodTable<I, CAP> {
+    const N_BUCKETS: usize = 2 * CAP;
+
+    /// Initializes an empty table at a region of allocated memory
+    ///
+    /// Requirements:
+    /// - `CAP <= I::MAX <= usize::MAX`
+    /// - `ptr` points to allocated memory that can be overwritten
+    pub(crate) unsafe fn init_at_alloc(ptr: *mut Self) {
+        let empty = Self::empty_bucket();
+        for i in 0..CAP {
+            addr_of_mut!((*ptr).buckets[i]).write([empty; 2]);
+        }
+    }
+
+    /// Copies the table to a region of allocated memory
+    ///
+    /// Requirements:
+    /// - `dst` points to allocated memory that can be overwritten
+    pub(crate) unsafe fn clone_to_alloc(&self, dst: *mut Self) {
+        // raw memmove for the same reason as in `LruList::clone_links_to_alloc`
+        ptr::copy(
+            self.buckets.as_ptr(),
+            addr_of_mut!((*dst).buckets) as *mut [Bucket<I>; 2],
+            CAP,
+        );
+    }
+
+    /// Returns the LruList indices of all entries in the table, in bucket order
+    pub(crate) fn slots(&self) -> impl Iterator<Item = I> + '_ {
+        self.buckets
+            .iter()
+            .flatten()
+            .filter(|b| !Self::is_empty(b))
+            .map(|b| b.slot)
+    }
+
+    /// Returns (bucket position, LruList index) of the first entry
+    /// in `hash`'s probe sequence whose index satisfies `is_match`
+    pub(crate) fn find(
+        &self,
+        hash: u64,
+        mut is_match: impl FnMut(I) -> bool,
+    ) -> Option<(usize, I)> {
+        if CAP == 0 {
+            return None;
+        }
+        let mut pos = Self::home(hash);
+        let mut dist = I::zero();
+        loop {
+            let b = self.bucket(pos);
+            // robin-hood invariant: the entry would have displaced
+            // any entry closer to its home bucket than it
+            if Self::is_empty(b) || b.dist < dist {
+                return None;
+            }
+            if is_match(b.slot) {
+                return Some((pos, b.slot));
+            }
+            pos = Self::next_pos(pos);
+            dist = dist + I::one();
+        }
+    }
+
+    /// Requirements:
+    /// - CAP > 0
+    /// - table has < CAP entries
+    /// - `slot` is not already in the table
+    pub(crate) fn insert(&mut self, hash: u64, slot: I) {
+        let mut pos = Self::home(hash);
+        let mut carry = Bucket {
+            slot,
+            dist: I::zero(),
+        };
+        loop {
+            let b = self.bucket_mut(pos);
+            if Self::is_empty(b) {
+                *b = carry;
+                return;
+            }
+            // take from the rich, give to the poor
+            if b.dist < carry.dist {
+                core::mem::swap(b, &mut carry);
+            }
+            pos = Self::next_pos(pos);
+            carry.dist = carry.dist + I::one();
+        }
+    }
+
+    /// Empties the bucket at `pos`, shifting the entries after it
+    /// back towards their home buckets
+    ///
+    /// Requirements:
+    /// - `pos` is that of an occupied bucket, returned by [`Self::find`]
+    pub(crate) fn remove_at(&mut self, mut pos: usize) {
+        loop {
+            let next_pos = Self::next_pos(pos);
+            let next = *self.bucket(next_pos);
+            if Self::is_empty(&next) || next.dist == I::zero() {
+                *self.bucket_mut(pos) = Self::empty_bucket();
+                return;
+            }
+            *self.bucket_mut(pos) = Bucket {
+                slot: next.slot,
+                dist: next.dist - I::one(),
+            };
+            pos = next_pos;
+        }
+    }
+
+    fn empty_bucket() -> Bucket<I> {
+        Bucket {
+            slot: I::from(CAP).unwrap(),
+            dist: I::zero(),
+        }
+    }
+
+    fn is_empty(b: &Bucket<I>) -> bool {
+        b.slot.to_usize().unwrap() == CAP
+    }
+
+    /// Maps `hash` to `[0, N_BUCKETS)` using its high bits
+    ///
+    /// Requirements:
+    /// - CAP > 0
+    fn home(hash: u64) -> usize {
+        ((u128::from(hash) * Self::N_BUCKETS as u128) >> 64) as usize
+    }
+
+    fn next_pos(pos: usize) -> usize {
+        let next = pos + 1;
+        if next == Self::N_BUCKETS {
+            0
+        } else {
+            next
+        }
+    }
+
+    fn bucket(&self, pos: usize) -> &Bucket<I> {
+        &self.buckets[pos >> 1][pos & 1]
+    }
+
+    fn bucket_mut(&mut self, pos: usize) -> &mut Bucket<I> {
+        &mut self.buckets[pos >> 1][pos & 1]
+    }
+}
@@ -1,6 +1,6 @@
 use num_traits::{PrimInt, Unsigned};
 
-use crate::ConstLru;
+use crate::lru_list::LruList;
 
 /// assumes:
 /// from_head: consume then increment
@@ -13,16 +13,13 @@ pub struct DoubleEndedIterCursors<I, const CAP: usize> {
 }
 
 impl<I: PrimInt + Unsigned, const CAP: usize> DoubleEndedIterCursors<I, CAP> {
-    pub fn new<K, V>(const_lru: &ConstLru<K, V, CAP, I>) -> Self {
-        let (from_head, from_tail) = if const_lru.is_empty() {
+    pub fn new<K, V>(list: &LruList<K, V, CAP, I>) -> Self {
+        let (from_head, from_tail) = if list.is_empty() {
             (I::zero(), I::zero())
-        } else if const_lru.is_full() {
-            (const_lru.head, const_lru.cap())
+        } else if list.is_full() {
+            (list.head, list.cap())
         } else {
-            (
-                const_lru.head,
-                const_lru.nexts[const_lru.tail.to_usize().unwrap()],
-            )
+            (list.head, list.nexts[list.tail.to_usize().unwrap()])
         };
         Self {
             from_head,
@@ -31,15 +28,15 @@ impl<I: PrimInt + Unsigned, const CAP: usize> DoubleEndedIterCursors<I, CAP> {
     }
 
     /// assumes next is valid
-    pub fn advance_from_head<K, V>(&mut self, const_lru: &ConstLru<K, V, CAP, I>) {
-        self.from_head = const_lru.nexts[self.get_from_head_idx()];
+    pub fn advance_from_head<K, V>(&mut self, list: &LruList<K, V, CAP, I>) {
+        self.from_head = list.nexts[self.get_from_head_idx()];
     }
 
-    pub fn retreat_from_tail<K, V>(&mut self, const_lru: &ConstLru<K, V, CAP, I>) {
-        self.from_tail = if self.from_tail == const_lru.cap() {
-            const_lru.tail
+    pub fn retreat_from_tail<K, V>(&mut self, list: &LruList<K, V, CAP, I>) {
+        self.from_tail = if self.from_tail == list.cap() {
+            list.tail
         } else {
-            const_lru.prevs[self.get_from_tail_idx()]
+            list.prevs[self.get_from_tail_idx()]
         };
     }
 
@@ -1,6 +1,6 @@
 use num_traits::{PrimInt, Unsigned};
 
-use crate::ConstLru;
+use crate::{lru_list::LruList, ConstLru};
 
 use super::double_ended_iter_cursors::DoubleEndedIterCursors;
 
@@ -9,18 +9,22 @@ use super::double_ended_iter_cursors::DoubleEndedIterCursors;
 /// Does not change the LRU order of the elements.
 pub struct IntoIter<K, V, const CAP: usize, I: PrimInt + Unsigned> {
     cursors: DoubleEndedIterCursors<I, CAP>,
-    const_lru: ConstLru<K, V, CAP, I>,
+    list: LruList<K, V, CAP, I>,
 }
 
 impl<K, V, const CAP: usize, I: PrimInt + Unsigned> IntoIter<K, V, CAP, I> {
-    pub fn new(const_lru: ConstLru<K, V, CAP, I>) -> Self {
-        let cursors = DoubleEndedIterCursors::new(&const_lru);
-        Self { cursors, const_lru }
+    pub fn new<C>(const_lru: ConstLru<K, V, CAP, I, C>) -> Self {
+        Self::from_list(const_lru.list)
+    }
+
+    pub(crate) fn from_list(list: LruList<K, V, CAP, I>) -> Self {
+        let cursors = DoubleEndedIterCursors::new(&list);
+        Self { cursors, list }
     }
 
     fn get_entry(&mut self, i: usize) -> (K, V) {
-        let key = unsafe { self.const_lru.keys[i].assume_init_read() };
-        let val = unsafe { self.const_lru.values[i].assume_init_read() };
+        let key = unsafe { self.list.keys[i].assume_init_read() };
+        let val = unsafe { self.list.values[i].assume_init_read() };
         (key, val)
     }
 }
@@ -34,17 +38,17 @@ impl<K, V, const CAP: usize, I: PrimInt + Unsigned> Iterator for IntoIter<K, V,
         }
         // consume then increment
         let i = self.cursors.get_from_head_idx();
-        self.cursors.advance_from_head(&self.const_lru);
+        self.cursors.advance_from_head(&self.list);
         // get_entry copies out (k, v),
-        // we need to truncate the const_lru so that they dont get dropped again
-        // when const_lru drops
-        self.const_lru.head = self.cursors.get_from_head();
-        self.const_lru.len = self.const_lru.len - I::one();
+        // we need to truncate the list so that they dont get dropped again
+        // when list drops
+        self.list.head = self.cursors.get_from_head();
+        self.list.len = self.list.len - I::one();
         Some(self.get_entry(i))
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let l = self.const_lru.len.to_usize().unwrap();
+        let l = self.list.len.to_usize().unwrap();
         (l, Some(l))
     }
 }
@@ -58,16 +62,16 @@ impl<K, V, const CAP: usize, I: PrimInt + Unsigned> DoubleEndedIterator for Into
             return None;
         }
         // decrement then consume
-        self.cursors.retreat_from_tail(&self.const_lru);
+        self.cursors.retreat_from_tail(&self.list);
         let i = self.cursors.get_from_tail_idx();
         // get_entry copies out (k, v),
-        // we need to truncate the const_lru so that they dont get dropped again
-        // when const_lru drops
+        // we need to truncate the list so that they dont get dropped again
+        // when list drops
 
         // index safety: from_tail is < CAP so prevs[i] wont panic
         // but might = CAP, but in that case len = 0 so mustve ended
-        self.const_lru.tail = self.const_lru.prevs[i];
-        self.const_lru.len = self.const_lru.len - I::one();
+        self.list.tail = self.list.prevs[i];
+        self.list.len = self.list.len - I::one();
         Some(self.get_entry(i))
     }
 }
@@ -0,0 +1,76 @@
+use num_traits::{PrimInt, Unsigned};
+
+use crate::{bs_index::BsIndex, lru_list::LruList, ConstLru};
+
+/// Iterates through the keys and values of the `ConstLru` in the keys' sorted order, consuming the `ConstLru`
+pub struct IntoIterKeyOrder<K, V, const CAP: usize, I: PrimInt + Unsigned> {
+    /// from_smallest_bsi == from_largest_bsi means ended
+    from_smallest_bsi: I,
+    from_largest_bsi: I,
+    bs_index: BsIndex<I, CAP>,
+    list: LruList<K, V, CAP, I>,
+}
+
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned> IntoIterKeyOrder<K, V, CAP, I> {
+    pub fn new<C>(const_lru: ConstLru<K, V, CAP, I, C>) -> Self {
+        Self {
+            from_smallest_bsi: I::zero(),
+            from_largest_bsi: const_lru.len(),
+            bs_index: const_lru.bs_index,
+            list: const_lru.list,
+        }
+    }
+
+    /// Assumes bs_i is in bounds.
+    ///
+    /// Frees the entry from the list so that it doesn't get dropped again when list drops.
+    /// The consumed bs_index range isn't read again, so bs_index doesn't need to be updated.
+    fn take_entry(&mut self, bs_i: I) -> (K, V) {
+        let index = self.bs_index.get(bs_i.to_usize().unwrap());
+        self.list.free_by_index(index)
+    }
+
+    fn has_ended(&self) -> bool {
+        self.from_smallest_bsi == self.from_largest_bsi
+    }
+}
+
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned> Iterator for IntoIterKeyOrder<K, V, CAP, I> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.has_ended() {
+            return None;
+        }
+        // consume then increment
+        let res = self.take_entry(self.from_smallest_bsi);
+        self.from_smallest_bsi = self.from_smallest_bsi + I::one();
+        Some(res)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let l = (self.from_largest_bsi - self.from_smallest_bsi)
+            .to_usize()
+            .unwrap();
+        (l, Some(l))
+    }
+}
+
+// TODO: look into https://doc.rust-lang.org/std/iter/trait.TrustedLen.html when it lands in stable
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned> ExactSizeIterator
+    for IntoIterKeyOrder<K, V, CAP, I>
+{
+}
+
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned> DoubleEndedIterator
+    for IntoIterKeyOrder<K, V, CAP, I>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.has_ended() {
+            return None;
+        }
+        // decrement then consume
+        self.from_largest_bsi = self.from_largest_bsi - I::one();
+        Some(self.take_entry(self.from_largest_bsi))
+    }
+}
@@ -1,6 +1,6 @@
 use num_traits::{PrimInt, Unsigned};
 
-use crate::ConstLru;
+use crate::{lru_list::LruList, ConstLru};
 
 use super::double_ended_iter_cursors::DoubleEndedIterCursors;
 
@@ -9,18 +9,22 @@ use super::double_ended_iter_cursors::DoubleEndedIterCursors;
 /// Does not change the LRU order of the elements.
 pub struct Iter<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> {
     cursors: DoubleEndedIterCursors<I, CAP>,
-    const_lru: &'a ConstLru<K, V, CAP, I>,
+    list: &'a LruList<K, V, CAP, I>,
 }
 
 impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> Iter<'a, K, V, CAP, I> {
-    pub fn new(const_lru: &'a ConstLru<K, V, CAP, I>) -> Self {
-        let cursors = DoubleEndedIterCursors::new(const_lru);
-        Self { cursors, const_lru }
+    pub fn new<C>(const_lru: &'a ConstLru<K, V, CAP, I, C>) -> Self {
+        const_lru.iter()
+    }
+
+    pub(crate) fn from_list(list: &'a LruList<K, V, CAP, I>) -> Self {
+        let cursors = DoubleEndedIterCursors::new(list);
+        Self { cursors, list }
     }
 
     fn get_entry(&mut self, i: usize) -> (&'a K, &'a V) {
-        let key = unsafe { self.const_lru.keys[i].assume_init_ref() };
-        let val = unsafe { self.const_lru.values[i].assume_init_ref() };
+        let key = unsafe { self.list.keys[i].assume_init_ref() };
+        let val = unsafe { self.list.values[i].assume_init_ref() };
         (key, val)
     }
 }
@@ -34,7 +38,7 @@ impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> Iterator for Iter<'a, K,
         }
         // consume then increment
         let i = self.cursors.get_from_head_idx();
-        self.cursors.advance_from_head(self.const_lru);
+        self.cursors.advance_from_head(self.list);
         Some(self.get_entry(i))
     }
 
@@ -54,7 +58,7 @@ impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> DoubleEndedIterator
             return None;
         }
         // decrement then consume
-        self.cursors.retreat_from_tail(self.const_lru);
+        self.cursors.retreat_from_tail(self.list);
         let i = self.cursors.get_from_tail_idx();
         Some(self.get_entry(i))
     }
@@ -1,6 +1,6 @@
 use num_traits::{PrimInt, Unsigned};
 
-use crate::ConstLru;
+use crate::{bs_index::BsIndex, lru_list::LruList, ConstLru};
 
 /// Iterates through the keys and values of the `ConstLru` in the keys' sorted order
 ///
@@ -9,29 +9,31 @@ pub struct IterKeyOrder<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> {
     /// from_smallest_bsi == from_largest_bsi means ended
     from_smallest_bsi: I,
     from_largest_bsi: I,
-    const_lru: &'a ConstLru<K, V, CAP, I>,
+    bs_index: &'a BsIndex<I, CAP>,
+    list: &'a LruList<K, V, CAP, I>,
 }
 
 impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> IterKeyOrder<'a, K, V, CAP, I> {
-    pub fn new(const_lru: &'a ConstLru<K, V, CAP, I>) -> Self {
+    pub fn new<C>(const_lru: &'a ConstLru<K, V, CAP, I, C>) -> Self {
         Self {
             from_smallest_bsi: I::zero(),
             from_largest_bsi: const_lru.len(),
-            const_lru,
+            bs_index: &const_lru.bs_index,
+            list: &const_lru.list,
         }
     }
 
     /// Assumes bs_i is in bounds
     /// returns const_lru.bs_index[bs_i]
     fn get_index(&self, bs_i: I) -> I {
-        self.const_lru.bs_index[bs_i.to_usize().unwrap()]
+        self.bs_index.get(bs_i.to_usize().unwrap())
     }
 
     /// Assumes bs_i is in bounds
     fn get_entry(&mut self, bs_i: I) -> (&'a K, &'a V) {
         let i = self.get_index(bs_i).to_usize().unwrap();
-        let key = unsafe { self.const_lru.keys[i].assume_init_ref() };
-        let val = unsafe { self.const_lru.values[i].assume_init_ref() };
+        let key = unsafe { self.list.keys[i].assume_init_ref() };
+        let val = unsafe { self.list.values[i].assume_init_ref() };
         (key, val)
     }
 
@@ -91,7 +93,7 @@ pub struct IterIndexed<'a, K, V, const CAP: usize, I: PrimInt + Unsigned>(
 );
 
 impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> IterIndexed<'a, K, V, CAP, I> {
-    pub fn new(const_lru: &'a ConstLru<K, V, CAP, I>) -> Self {
+    pub fn new<C>(const_lru: &'a ConstLru<K, V, CAP, I, C>) -> Self {
         Self(IterKeyOrder::new(const_lru))
     }
 }
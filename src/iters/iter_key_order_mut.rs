@@ -1,6 +1,6 @@
 use num_traits::{PrimInt, Unsigned};
 
-use crate::ConstLru;
+use crate::{bs_index::BsIndex, lru_list::LruList, ConstLru};
 
 /// Iterates through the keys and mutable values of the `ConstLru` in the keys' sorted order
 ///
@@ -9,26 +9,30 @@ pub struct IterKeyOrderMut<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> {
     /// from_smallest_bsi == from_largest_bsi means ended
     from_smallest_bsi: I,
     from_largest_bsi: I,
-    const_lru: &'a mut ConstLru<K, V, CAP, I>,
+    bs_index: &'a BsIndex<I, CAP>,
+    list: &'a mut LruList<K, V, CAP, I>,
 }
 
 impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> IterKeyOrderMut<'a, K, V, CAP, I> {
-    pub fn new(const_lru: &'a mut ConstLru<K, V, CAP, I>) -> Self {
+    pub fn new<C>(const_lru: &'a mut ConstLru<K, V, CAP, I, C>) -> Self {
         Self {
             from_smallest_bsi: I::zero(),
             from_largest_bsi: const_lru.len(),
-            const_lru,
+            bs_index: &const_lru.bs_index,
+            list: &mut const_lru.list,
         }
     }
 
     fn get_entry_mut(&mut self, bs_i: I) -> (&'a K, &'a mut V) {
-        let i = self.const_lru.bs_index[bs_i.to_usize().unwrap()]
+        let i = self
+            .bs_index
+            .get(bs_i.to_usize().unwrap())
             .to_usize()
             .unwrap();
         // TODO: double check unsafes
-        let key_ptr = unsafe { self.const_lru.keys[i].assume_init_ref() } as *const _;
+        let key_ptr = unsafe { self.list.keys[i].assume_init_ref() } as *const _;
         let key: &'a K = unsafe { &*key_ptr };
-        let val_ptr = unsafe { self.const_lru.values[i].assume_init_mut() } as *mut _;
+        let val_ptr = unsafe { self.list.values[i].assume_init_mut() } as *mut _;
         let val: &'a mut V = unsafe { &mut *val_ptr };
         (key, val)
     }
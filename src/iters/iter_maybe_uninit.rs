@@ -2,30 +2,30 @@ use core::mem::MaybeUninit;
 
 use num_traits::{PrimInt, Unsigned};
 
-use crate::ConstLru;
+use crate::lru_list::LruList;
 
 use super::double_ended_iter_cursors::DoubleEndedIterCursors;
 
-/// Iterates through the keys and values of the ConstLru from most-recently-used to least-recently-used
+/// Iterates through the keys and values of the LruList from most-recently-used to least-recently-used
 ///
 /// Does not change the LRU order of the elements.
 ///
-/// Only used to implement `Drop` for ConstLru
+/// Only used to implement `Drop` for LruList
 pub struct IterMaybeUninit<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> {
     cursors: DoubleEndedIterCursors<I, CAP>,
-    const_lru: &'a mut ConstLru<K, V, CAP, I>,
+    list: &'a mut LruList<K, V, CAP, I>,
 }
 
 impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> IterMaybeUninit<'a, K, V, CAP, I> {
-    pub fn new(const_lru: &'a mut ConstLru<K, V, CAP, I>) -> Self {
-        let cursors = DoubleEndedIterCursors::new(const_lru);
-        Self { cursors, const_lru }
+    pub fn new(list: &'a mut LruList<K, V, CAP, I>) -> Self {
+        let cursors = DoubleEndedIterCursors::new(list);
+        Self { cursors, list }
     }
 
     fn get_entry_mut(&mut self, i: usize) -> (&'a mut MaybeUninit<K>, &'a mut MaybeUninit<V>) {
-        let key_ptr = &mut self.const_lru.keys[i] as *mut _;
+        let key_ptr = &mut self.list.keys[i] as *mut _;
         let key: &'a mut MaybeUninit<K> = unsafe { &mut *key_ptr };
-        let val_ptr = &mut self.const_lru.values[i] as *mut _;
+        let val_ptr = &mut self.list.values[i] as *mut _;
         let val: &'a mut MaybeUninit<V> = unsafe { &mut *val_ptr };
         (key, val)
     }
@@ -42,7 +42,7 @@ impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> Iterator
         }
         // consume then increment
         let i = self.cursors.get_from_head_idx();
-        self.cursors.advance_from_head(self.const_lru);
+        self.cursors.advance_from_head(self.list);
         Some(self.get_entry_mut(i))
     }
 
@@ -62,7 +62,7 @@ impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> DoubleEndedIterator
             return None;
         }
         // decrement then consume
-        self.cursors.retreat_from_tail(self.const_lru);
+        self.cursors.retreat_from_tail(self.list);
         let i = self.cursors.get_from_tail_idx();
         Some(self.get_entry_mut(i))
     }
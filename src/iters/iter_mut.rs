@@ -1,6 +1,6 @@
 use num_traits::{PrimInt, Unsigned};
 
-use crate::ConstLru;
+use crate::{lru_list::LruList, ConstLru};
 
 use super::double_ended_iter_cursors::DoubleEndedIterCursors;
 
@@ -9,20 +9,24 @@ use super::double_ended_iter_cursors::DoubleEndedIterCursors;
 /// Does not change the LRU order of the elements.
 pub struct IterMut<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> {
     cursors: DoubleEndedIterCursors<I, CAP>,
-    const_lru: &'a mut ConstLru<K, V, CAP, I>,
+    list: &'a mut LruList<K, V, CAP, I>,
 }
 
 impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> IterMut<'a, K, V, CAP, I> {
-    pub fn new(const_lru: &'a mut ConstLru<K, V, CAP, I>) -> Self {
-        let cursors = DoubleEndedIterCursors::new(const_lru);
-        Self { cursors, const_lru }
+    pub fn new<C>(const_lru: &'a mut ConstLru<K, V, CAP, I, C>) -> Self {
+        const_lru.iter_mut()
+    }
+
+    pub(crate) fn from_list(list: &'a mut LruList<K, V, CAP, I>) -> Self {
+        let cursors = DoubleEndedIterCursors::new(list);
+        Self { cursors, list }
     }
 
     fn get_entry_mut(&mut self, i: usize) -> (&'a K, &'a mut V) {
         // TODO: double check unsafes
-        let key_ptr = unsafe { self.const_lru.keys[i].assume_init_ref() } as *const _;
+        let key_ptr = unsafe { self.list.keys[i].assume_init_ref() } as *const _;
         let key: &'a K = unsafe { &*key_ptr };
-        let val_ptr = unsafe { self.const_lru.values[i].assume_init_mut() } as *mut _;
+        let val_ptr = unsafe { self.list.values[i].assume_init_mut() } as *mut _;
         let val: &'a mut V = unsafe { &mut *val_ptr };
         (key, val)
     }
@@ -37,7 +41,7 @@ impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> Iterator for IterMut<'a,
         }
         // consume then increment
         let i = self.cursors.get_from_head_idx();
-        self.cursors.advance_from_head(self.const_lru);
+        self.cursors.advance_from_head(self.list);
         Some(self.get_entry_mut(i))
     }
 
@@ -57,7 +61,7 @@ impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> DoubleEndedIterator
             return None;
         }
         // decrement then consume
-        self.cursors.retreat_from_tail(self.const_lru);
+        self.cursors.retreat_from_tail(self.list);
         let i = self.cursors.get_from_tail_idx();
         Some(self.get_entry_mut(i))
     }
@@ -0,0 +1,52 @@
+/// Iterates through the keys of an iterator of key-value pairs, in the same order
+///
+/// Returned by [`ConstLru::keys`](crate::ConstLru::keys), [`ConstLru::into_keys`](crate::ConstLru::into_keys) and their key order counterparts.
+#[derive(Debug, Clone)]
+pub struct Keys<T>(pub(crate) T);
+
+impl<K, V, T: Iterator<Item = (K, V)>> Iterator for Keys<T> {
+    type Item = K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(k, _)| k)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<K, V, T: ExactSizeIterator<Item = (K, V)>> ExactSizeIterator for Keys<T> {}
+
+impl<K, V, T: DoubleEndedIterator<Item = (K, V)>> DoubleEndedIterator for Keys<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|(k, _)| k)
+    }
+}
+
+/// Iterates through the values of an iterator of key-value pairs, in the same order
+///
+/// Returned by [`ConstLru::values`](crate::ConstLru::values), [`ConstLru::values_mut`](crate::ConstLru::values_mut),
+/// [`ConstLru::into_values`](crate::ConstLru::into_values) and their key order counterparts.
+#[derive(Debug, Clone)]
+pub struct Values<T>(pub(crate) T);
+
+impl<K, V, T: Iterator<Item = (K, V)>> Iterator for Values<T> {
+    type Item = V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(_, v)| v)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<K, V, T: ExactSizeIterator<Item = (K, V)>> ExactSizeIterator for Values<T> {}
+
+impl<K, V, T: DoubleEndedIterator<Item = (K, V)>> DoubleEndedIterator for Values<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|(_, v)| v)
+    }
+}
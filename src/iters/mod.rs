@@ -1,7 +1,9 @@
 pub mod double_ended_iter_cursors;
 pub mod into_iter;
+pub mod into_iter_key_order;
 pub mod iter;
 pub mod iter_key_order;
 pub mod iter_key_order_mut;
 pub mod iter_maybe_uninit;
 pub mod iter_mut;
+pub mod keys_values;
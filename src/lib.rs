@@ -1,72 +1,96 @@
 #![no_std]
 #![doc = include_str!("../README.md")]
 
+#[cfg(feature = "std")]
+extern crate std;
+
 use core::borrow::Borrow;
-use core::cmp::Ordering;
+use core::fmt::Debug;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
 use core::mem::MaybeUninit;
-use core::ptr::{self, addr_of_mut};
+use core::ops::{Index, IndexMut};
+use core::ptr::addr_of_mut;
 use num_traits::{PrimInt, Unsigned};
 
+mod bs_index;
+mod comparator;
+mod cursor;
+mod cursor_key_order;
 mod entry;
 mod errs;
+mod eytzinger;
+pub mod hash;
 mod iters;
-
+#[cfg(feature = "std")]
+mod loading;
+mod lock;
+mod lru_list;
+mod memoized;
+mod seqlock;
+pub mod sharded;
+#[cfg(feature = "critical-section")]
+mod shared;
+pub mod tree;
+
+pub use comparator::*;
+#[cfg(feature = "macros")]
+pub use const_lru_macros::memoize;
+pub use cursor::CursorMut;
+pub use cursor_key_order::CursorKeyOrder;
 pub use entry::*;
 pub use errs::*;
+pub use eytzinger::EytzingerLru;
+pub use hash::ConstHashLru;
 pub use iters::into_iter::IntoIter;
+pub use iters::into_iter_key_order::IntoIterKeyOrder;
 pub use iters::iter::Iter;
 pub use iters::iter_key_order::IterKeyOrder;
 pub use iters::iter_key_order_mut::IterKeyOrderMut;
 pub use iters::iter_mut::IterMut;
-
+pub use iters::keys_values::{Keys, Values};
+#[cfg(feature = "std")]
+pub use loading::LoadingCache;
+pub use memoized::Memoized;
+pub use seqlock::{NoUninit, SeqLockConstLru};
+pub use sharded::ShardedConstLru;
+#[cfg(feature = "critical-section")]
+pub use shared::SharedConstLru;
+pub use tree::ConstLruTree;
+
+use bs_index::BsIndex;
 use iters::iter_key_order::IterIndexed;
-use iters::iter_maybe_uninit::IterMaybeUninit;
+use lru_list::LruList;
+
+/// Number of entries [`ConstLru::insert_batch`] buffers on the stack and merges at a time, independent of `CAP`
+const INSERT_BATCH_CHUNK_LEN: usize = 32;
 
 /// Constant capacity key-addressed LRU cache.
 ///
 /// Generics:
-/// - `K`. Type of key. `Ord` is used for lookup and to address entries.
+/// - `K`. Type of key. `C` is used for lookup and to address entries.
 /// - `V`. Type of value.
 /// - `CAP`. Capacity of the cache.
 /// - `I`. Type of the index used. Must be an unsigned primitive type with bitwidth <= `usize`'s bitwidth.
-#[derive(Debug)]
-pub struct ConstLru<K, V, const CAP: usize, I: PrimInt + Unsigned = usize> {
-    len: I,
-
-    /// head is index of most recently used
-    ///
-    /// can be any value if cache is empty
-    head: I,
-
-    /// tail is index of least recently used
-    ///
-    /// if cache is empty, tail is the first slot of unallocated memory / "free-list"
-    /// else, next of the tail is the first slot of unallocated memory / "free-list"
-    ///
-    /// tail is always < CAP
-    tail: I,
+/// - `C`. [`Comparator`] that orders the keys. Defaults to [`NaturalOrder`], which uses `K`'s `Ord` impl.
+pub struct ConstLru<K, V, const CAP: usize, I: PrimInt + Unsigned = usize, C = NaturalOrder> {
+    list: LruList<K, V, CAP, I>,
 
     /// binary search index
-    bs_index: [I; CAP],
-
-    /// disregard if value == CAP
-    nexts: [I; CAP],
-
-    /// disregard if value == CAP
-    prevs: [I; CAP],
-
-    keys: [MaybeUninit<K>; CAP],
+    bs_index: BsIndex<I, CAP>,
 
-    values: [MaybeUninit<V>; CAP],
+    comparator: PhantomData<C>,
 }
 
-impl<K, V, const CAP: usize, I: PrimInt + Unsigned> ConstLru<K, V, CAP, I> {
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned, C> ConstLru<K, V, CAP, I, C> {
     /// Creates a new empty `ConstLru` on the stack
     ///
     /// panics if
     /// - `CAP > I::MAX`
     /// - `I::MAX > usize::MAX`
     ///
+    /// Use [`Self::new_checked`] to reject these at compile time instead.
+    ///
     /// WARNING: this might result in runtime stack overflow errors for large `CAP`.
     /// Use [`Self::init_at_alloc`] to initialize larger variants at preallocated memory
     pub fn new() -> Self {
@@ -87,6 +111,8 @@ impl<K, V, const CAP: usize, I: PrimInt + Unsigned> ConstLru<K, V, CAP, I> {
     /// - `CAP > I::MAX`
     /// - `I::MAX > usize::MAX`
     ///
+    /// Use [`Self::init_in`] for a safe, non-panicking alternative.
+    ///
     /// Example:
     ///
     /// ```
@@ -101,119 +127,85 @@ impl<K, V, const CAP: usize, I: PrimInt + Unsigned> ConstLru<K, V, CAP, I> {
     /// };
     /// ```
     pub unsafe fn init_at_alloc(ptr: *mut Self) {
-        // using as_mut_ptr from MaybeUninit is UB,
-        // initialize fields using addr_of_mut!()
-
-        let i_max = I::max_value()
-            .to_usize()
-            .unwrap_or_else(|| panic!("I::MAX > usize::MAX"));
-        if CAP > i_max {
-            panic!("CAP > I::MAX");
-        }
-
-        let cap = I::from(CAP).unwrap();
-
-        addr_of_mut!((*ptr).len).write(I::zero());
-        addr_of_mut!((*ptr).head).write(cap);
-        addr_of_mut!((*ptr).tail).write(I::zero());
-
-        // nexts = [1, 2, ..., cap-1, cap]
-        for i in 0..CAP {
-            addr_of_mut!((*ptr).nexts[i]).write(I::from(i + 1).unwrap());
-        }
-
-        // prevs = [cap, 0, 1, ..., cap-2]
-        if CAP > 0 {
-            addr_of_mut!((*ptr).prevs[0]).write(cap);
-            for i in 1..CAP {
-                addr_of_mut!((*ptr).prevs[i]).write(I::from(i - 1).unwrap());
-            }
+        if let Err(e) = LruList::<K, V, CAP, I>::check_cap() {
+            panic!("{e}");
         }
-
-        // bs_index = [cap, ..., cap]
-        // UB if not initialized
-        for i in 0..CAP {
-            addr_of_mut!((*ptr).bs_index[i]).write(cap);
-        }
-
-        // keys and values should remain uninitialized
+        Self::init_at_alloc_unchecked(ptr);
     }
 
-    /// private helper fn.
+    /// Initializes the ConstLru in `slot` and returns a mutable reference to it.
     ///
-    /// Unlinks the node at `index` from the doubly-linked list,
-    /// patching its previous and next nodes, as well as self.head and self.tail if required.
+    /// Safe alternative to [`Self::init_at_alloc`] that returns an error instead of panicking if
+    /// - `CAP > I::MAX`
+    /// - `I::MAX > usize::MAX`
     ///
-    /// Can be used on both valid and invalid nodes.
+    /// Any value previously in `slot` is overwritten without being dropped.
     ///
-    /// When this fn returns, `index`'s next and prev should be treated as invalid
+    /// Example:
     ///
-    /// `self.head` and `self.tail` are not modified if only 1 elem in list
+    /// ```
+    /// use const_lru::ConstLru;
+    /// use core::mem::MaybeUninit;
     ///
-    /// Requirements:
-    /// - index < CAP
-    fn unlink_node(&mut self, index: I) {
-        let i = index.to_usize().unwrap();
-        let next = self.nexts[i];
-        let prev = self.prevs[i];
-
-        // index.next.prev = index.prev
-        if next != self.cap() {
-            self.prevs[next.to_usize().unwrap()] = prev;
-        }
-
-        // index.prev.next = index.next
-        if prev != self.cap() {
-            self.nexts[prev.to_usize().unwrap()] = next;
-        }
-
-        let is_one_elem_list = self.head == self.tail;
-
-        if self.head == index && !is_one_elem_list {
-            self.head = next;
-        }
-
-        if self.tail == index && !is_one_elem_list {
-            self.tail = prev;
+    /// let mut slot: Box<MaybeUninit<ConstLru<u32, u16, 1_000, u16>>> = Box::new_uninit();
+    /// let c = ConstLru::init_in(&mut slot).unwrap();
+    /// assert!(c.insert(1, 2).is_none());
+    /// ```
+    pub fn init_in(slot: &mut MaybeUninit<Self>) -> Result<&mut Self, CapacityError> {
+        LruList::<K, V, CAP, I>::check_cap()?;
+        unsafe {
+            Self::init_at_alloc_unchecked(slot.as_mut_ptr());
+            Ok(slot.assume_init_mut())
         }
     }
 
-    /// private helper fn.
+    /// Same as [`Self::init_in`], but invalid `CAP` and `I` combinations are rejected at compile time
+    /// instead of returning an error.
     ///
-    /// Moves the element at index to the most-recently-used position.
+    /// ```compile_fail
+    /// use const_lru::ConstLru;
+    /// use core::mem::MaybeUninit;
     ///
-    /// Requirements:
-    /// - !self.is_empty()
-    /// - index must be that of a valid node
-    fn move_to_head(&mut self, index: I) {
-        if self.head == index {
-            return;
+    /// let mut slot: MaybeUninit<ConstLru<u8, u8, 256, u8>> = MaybeUninit::uninit();
+    /// ConstLru::init_in_checked(&mut slot);
+    /// ```
+    pub fn init_in_checked(slot: &mut MaybeUninit<Self>) -> &mut Self {
+        #[allow(clippy::let_unit_value)]
+        let () = LruList::<K, V, CAP, I>::CAP_FITS_IN_I;
+        unsafe {
+            Self::init_at_alloc_unchecked(slot.as_mut_ptr());
+            slot.assume_init_mut()
         }
+    }
 
-        self.unlink_node(index);
-        let i = index.to_usize().unwrap();
-
-        // since self.head != index
-        // and index is valid,
-        // head must be valid
-        let head = self.head;
-        self.prevs[i] = self.cap();
-        self.nexts[i] = head;
+    /// Same as [`Self::new`], but invalid `CAP` and `I` combinations are rejected at compile time
+    /// instead of panicking at runtime.
+    ///
+    /// Might overflow the stack like [`Self::new`].
+    /// Use [`Self::init_in_checked`] to initialize larger variants at preallocated memory
+    ///
+    /// ```compile_fail
+    /// use const_lru::ConstLru;
+    ///
+    /// let _c: ConstLru<u8, u8, 256, u8> = ConstLru::new_checked();
+    /// ```
+    pub fn new_checked() -> Self {
+        let mut res: MaybeUninit<Self> = MaybeUninit::uninit();
+        Self::init_in_checked(&mut res);
+        unsafe { res.assume_init() }
+    }
 
-        self.prevs[head.to_usize().unwrap()] = index;
+    /// Requirements:
+    /// - `LruList::check_cap()` is `Ok`
+    /// - `ptr` points to allocated memory that can be overwritten
+    pub(crate) unsafe fn init_at_alloc_unchecked(ptr: *mut Self) {
+        // using as_mut_ptr from MaybeUninit is UB,
+        // initialize fields using addr_of_mut!()
 
-        self.head = index;
-    }
+        LruList::init_at_alloc(addr_of_mut!((*ptr).list));
+        BsIndex::init_at_alloc(addr_of_mut!((*ptr).bs_index));
 
-    /// Cleanup for drop impl. Drops keys and values.
-    /// Other fields should be all primitive types
-    fn drop_cleanup(&mut self) {
-        for (k, v) in IterMaybeUninit::new(self) {
-            unsafe {
-                k.assume_init_drop();
-                v.assume_init_drop();
-            }
-        }
+        addr_of_mut!((*ptr).comparator).write(PhantomData);
     }
 
     /// Creates an iterator that iterates through the keys and values of the `ConstLru` from most-recently-used to least-recently-used
@@ -221,8 +213,8 @@ impl<K, V, const CAP: usize, I: PrimInt + Unsigned> ConstLru<K, V, CAP, I> {
     /// Does not change the LRU order of the elements.
     ///
     /// Double-ended: reversing iterates from least-recently-used to most-recently-used
-    pub fn iter(&self) -> Iter<K, V, CAP, I> {
-        Iter::new(self)
+    pub fn iter(&self) -> Iter<'_, K, V, CAP, I> {
+        Iter::from_list(&self.list)
     }
 
     /// Creates an iterator that iterates through the keys and mutable values of the `ConstLru` from most-recently-used to least-recently-used
@@ -230,8 +222,8 @@ impl<K, V, const CAP: usize, I: PrimInt + Unsigned> ConstLru<K, V, CAP, I> {
     /// Does not change the LRU order of the elements, even if mutated.
     ///
     /// Double-ended: reversing iterates from least-recently-used to most-recently-used
-    pub fn iter_mut(&mut self) -> IterMut<K, V, CAP, I> {
-        IterMut::new(self)
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V, CAP, I> {
+        IterMut::from_list(&mut self.list)
     }
 
     /// Creates an iterator that iterates through the keys and values of the `ConstLru` in the order of its keys
@@ -239,7 +231,7 @@ impl<K, V, const CAP: usize, I: PrimInt + Unsigned> ConstLru<K, V, CAP, I> {
     /// Does not change the LRU order of the elements.
     ///
     /// Double-ended: reversing iterates from descending order of its keys
-    pub fn iter_key_order(&self) -> IterKeyOrder<K, V, CAP, I> {
+    pub fn iter_key_order(&self) -> IterKeyOrder<'_, K, V, CAP, I> {
         IterKeyOrder::new(self)
     }
 
@@ -248,20 +240,136 @@ impl<K, V, const CAP: usize, I: PrimInt + Unsigned> ConstLru<K, V, CAP, I> {
     /// Does not change the LRU order of the elements, even if mutated.
     ///
     /// Double-ended: reversing iterates from descending order of its keys
-    pub fn iter_key_order_mut(&mut self) -> IterKeyOrderMut<K, V, CAP, I> {
+    pub fn iter_key_order_mut(&mut self) -> IterKeyOrderMut<'_, K, V, CAP, I> {
         IterKeyOrderMut::new(self)
     }
 
+    /// Creates an iterator that iterates through the keys and values of the `ConstLru` in the order of its keys, consuming it
+    ///
+    /// Double-ended: reversing iterates from descending order of its keys
+    pub fn into_iter_key_order(self) -> IntoIterKeyOrder<K, V, CAP, I> {
+        IntoIterKeyOrder::new(self)
+    }
+
+    /// Returns the entry with the smallest key, or `None` if empty.
+    ///
+    /// Does not change the LRU order of the elements.
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        self.iter_key_order().next()
+    }
+
+    /// Returns the entry with the largest key, or `None` if empty.
+    ///
+    /// Does not change the LRU order of the elements.
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        self.iter_key_order().next_back()
+    }
+
+    /// Removes and returns the entry with the smallest key, or `None` if empty.
+    pub fn pop_first(&mut self) -> Option<(K, V)> {
+        if self.is_empty() {
+            return None;
+        }
+        let index = self.bs_index.get(0);
+        Some(self.remove_by_index((index, I::zero())))
+    }
+
+    /// Removes and returns the entry with the largest key, or `None` if empty.
+    pub fn pop_last(&mut self) -> Option<(K, V)> {
+        if self.is_empty() {
+            return None;
+        }
+        let bs_i = self.len() - I::one();
+        let index = self.bs_index.get(bs_i.to_usize().unwrap());
+        Some(self.remove_by_index((index, bs_i)))
+    }
+
+    /// Creates an iterator that iterates through the keys of the `ConstLru` from most-recently-used to least-recently-used
+    ///
+    /// Does not change the LRU order of the elements.
+    pub fn keys(&self) -> Keys<Iter<'_, K, V, CAP, I>> {
+        Keys(self.iter())
+    }
+
+    /// Creates an iterator that iterates through the keys of the `ConstLru` in the order of its keys
+    ///
+    /// Does not change the LRU order of the elements.
+    pub fn keys_key_order(&self) -> Keys<IterKeyOrder<'_, K, V, CAP, I>> {
+        Keys(self.iter_key_order())
+    }
+
+    /// Creates an iterator that iterates through the values of the `ConstLru` from most-recently-used to least-recently-used
+    ///
+    /// Does not change the LRU order of the elements.
+    pub fn values(&self) -> Values<Iter<'_, K, V, CAP, I>> {
+        Values(self.iter())
+    }
+
+    /// Creates an iterator that iterates through the values of the `ConstLru` in the order of its keys
+    ///
+    /// Does not change the LRU order of the elements.
+    pub fn values_key_order(&self) -> Values<IterKeyOrder<'_, K, V, CAP, I>> {
+        Values(self.iter_key_order())
+    }
+
+    /// Creates an iterator that iterates through the mutable values of the `ConstLru` from most-recently-used to least-recently-used
+    ///
+    /// Does not change the LRU order of the elements, even if mutated.
+    pub fn values_mut(&mut self) -> Values<IterMut<'_, K, V, CAP, I>> {
+        Values(self.iter_mut())
+    }
+
+    /// Creates an iterator that iterates through the mutable values of the `ConstLru` in the order of its keys
+    ///
+    /// Does not change the LRU order of the elements, even if mutated.
+    pub fn values_key_order_mut(&mut self) -> Values<IterKeyOrderMut<'_, K, V, CAP, I>> {
+        Values(self.iter_key_order_mut())
+    }
+
+    /// Creates an iterator that iterates through the keys of the `ConstLru` from most-recently-used to least-recently-used, consuming it
+    pub fn into_keys(self) -> Keys<IntoIter<K, V, CAP, I>> {
+        Keys(self.into_iter())
+    }
+
+    /// Creates an iterator that iterates through the keys of the `ConstLru` in the order of its keys, consuming it
+    pub fn into_keys_key_order(self) -> Keys<IntoIterKeyOrder<K, V, CAP, I>> {
+        Keys(self.into_iter_key_order())
+    }
+
+    /// Creates an iterator that iterates through the values of the `ConstLru` from most-recently-used to least-recently-used, consuming it
+    pub fn into_values(self) -> Values<IntoIter<K, V, CAP, I>> {
+        Values(self.into_iter())
+    }
+
+    /// Creates an iterator that iterates through the values of the `ConstLru` in the order of its keys, consuming it
+    pub fn into_values_key_order(self) -> Values<IntoIterKeyOrder<K, V, CAP, I>> {
+        Values(self.into_iter_key_order())
+    }
+
+    /// Creates a cursor at the most-recently-used entry that can walk the LRU order and reorder or remove entries.
+    ///
+    /// If the `ConstLru` is empty, the cursor is at the ghost non-entry, see [`CursorMut`].
+    pub fn cursor_mut_front(&mut self) -> CursorMut<'_, K, V, CAP, I, C> {
+        CursorMut::new_front(self)
+    }
+
+    /// Creates a cursor at the least-recently-used entry that can walk the LRU order and reorder or remove entries.
+    ///
+    /// If the `ConstLru` is empty, the cursor is at the ghost non-entry, see [`CursorMut`].
+    pub fn cursor_mut_back(&mut self) -> CursorMut<'_, K, V, CAP, I, C> {
+        CursorMut::new_back(self)
+    }
+
     /// Clears the `ConstLru`, removing all key-value pairs.
     pub fn clear(&mut self) {
-        self.drop_cleanup();
+        self.list.drop_cleanup();
         let ptr_to_self: *mut Self = self;
-        unsafe { Self::init_at_alloc(ptr_to_self) }
+        unsafe { Self::init_at_alloc_unchecked(ptr_to_self) }
     }
 
     /// Returns the maximum number of elements this `ConstLru` can hold
     pub fn cap(&self) -> I {
-        I::from(CAP).unwrap()
+        self.list.cap()
     }
 
     /// Returns `true` if the `ConstLru` contains no elements.
@@ -276,105 +384,81 @@ impl<K, V, const CAP: usize, I: PrimInt + Unsigned> ConstLru<K, V, CAP, I> {
 
     /// Returns the number of elements in the `ConstLru`.
     pub fn len(&self) -> I {
-        self.len
+        self.list.len()
+    }
+
+    /// Returns `true` if both `ConstLru`s contain the same key-value pairs, regardless of their LRU order.
+    ///
+    /// This is what `==` compares. The entries are compared in key order,
+    /// so `C` must be consistent with `K`'s `PartialEq`.
+    pub fn eq_as_map<const CAP2: usize, I2: PrimInt + Unsigned>(
+        &self,
+        other: &ConstLru<K, V, CAP2, I2, C>,
+    ) -> bool
+    where
+        K: PartialEq,
+        V: PartialEq,
+    {
+        self.len().to_usize() == other.len().to_usize()
+            && self.iter_key_order().eq(other.iter_key_order())
+    }
+
+    /// Returns `true` if both `ConstLru`s contain the same key-value pairs in the same LRU order.
+    pub fn eq_with_order<const CAP2: usize, I2: PrimInt + Unsigned>(
+        &self,
+        other: &ConstLru<K, V, CAP2, I2, C>,
+    ) -> bool
+    where
+        K: PartialEq,
+        V: PartialEq,
+    {
+        self.len().to_usize() == other.len().to_usize() && self.iter().eq(other.iter())
     }
 
-    /// Assumes `index` is of a valid node
-    /// Moves `index` to MRU position
-    fn insert_replace_value(&mut self, index: I, replacement: V) -> V {
-        let old_v = unsafe { self.values[index.to_usize().unwrap()].assume_init_mut() };
-        let old_v_out = core::mem::replace(old_v, replacement);
-        self.move_to_head(index);
-        old_v_out
+    /// Position in LRU order at `fraction` of the way from the most-recently-used slot to the least-recently-used one,
+    /// rounded down.
+    ///
+    /// Assumes self is not empty
+    fn position_at_fraction(&self, fraction: f32) -> I {
+        let last = self.len() - I::one();
+        if fraction.is_nan() || fraction <= 0.0 {
+            return I::zero();
+        }
+        if fraction >= 1.0 {
+            return last;
+        }
+        // fraction is exactly mantissa / 2^shift, so multiply in integers to round down exactly.
+        // Multiplying in floating point can round up to the next position, or lose precision for large len
+        let bits = fraction.to_bits();
+        let (mantissa, shift) = match bits >> 23 {
+            // subnormal
+            0 => (bits, 149),
+            biased_exp => (bits & 0x7f_ffff | 0x80_0000, 150 - biased_exp),
+        };
+        let n = (last.to_u128().unwrap() * u128::from(mantissa))
+            .checked_shr(shift)
+            .unwrap_or(0);
+        I::from(n).unwrap()
     }
 
     // Assumes N > 0 and self is not full
     // Moves newly inserted elem to MRU position
     // Returns index entry was inserted into
     fn insert_alloc_new(&mut self, insert_bs_i: I, k: K, v: V) -> I {
-        let free_index = if self.is_empty() {
-            self.head = self.tail;
-            self.tail
-        } else {
-            self.nexts[self.tail.to_usize().unwrap()]
-        };
-        self.tail = free_index;
-        let f = free_index.to_usize().unwrap();
-        self.keys[f].write(k);
-        self.values[f].write(v);
-
-        if insert_bs_i < self.len {
-            // shift everything between [bs_i, len) right
-            unsafe {
-                let insert_bs_i_ptr = self
-                    .bs_index
-                    .as_mut_ptr()
-                    .add(insert_bs_i.to_usize().unwrap());
-                ptr::copy(
-                    insert_bs_i_ptr,
-                    insert_bs_i_ptr.add(1),
-                    (self.len - insert_bs_i).to_usize().unwrap(),
-                );
-            }
-        }
-        self.bs_index[insert_bs_i.to_usize().unwrap()] = free_index;
-
-        self.len = self.len + I::one();
-
-        self.move_to_head(self.tail);
+        let free_index = self.list.alloc_new(k, v);
+        self.bs_index
+            .insert(insert_bs_i.to_usize().unwrap(), free_index);
         free_index
     }
 
     // Assumes index tuple is of a valid node. Should be result of Ok returned by self.get_index_of()
     fn remove_by_index(&mut self, (index, bs_i): (I, I)) -> (K, V) {
-        let i = index.to_usize().unwrap();
-
-        let key = unsafe { self.keys[i].assume_init_read() };
-        let val = unsafe { self.values[i].assume_init_read() };
-
-        // if len == 1, correct links are already in place
-        if self.len() > I::one() {
-            // len > 1
-            // move to front of free list
-            self.unlink_node(index);
-            let t = self.tail.to_usize().unwrap();
-            let first_free = self.nexts[t];
-
-            if first_free < self.cap() {
-                self.prevs[first_free.to_usize().unwrap()] = index;
-            }
-            self.nexts[i] = first_free;
-
-            self.prevs[i] = self.tail;
-            self.nexts[t] = index;
-        }
-
-        unsafe {
-            let bs_i_ptr = self.bs_index.as_mut_ptr().add(bs_i.to_usize().unwrap());
-            // shift everything left to fill bs_i
-            ptr::copy(
-                bs_i_ptr.add(1),
-                bs_i_ptr,
-                (self.len - bs_i - I::one()).to_usize().unwrap(),
-            );
-        }
-
-        self.len = self.len - I::one();
-        (key, val)
-    }
-
-    /// Assumes index is valid
-    fn get_by_index(&self, index: I) -> &V {
-        unsafe { self.values[index.to_usize().unwrap()].assume_init_ref() }
-    }
-
-    /// Assumes index is valid
-    fn get_mut_by_index(&mut self, index: I) -> &mut V {
-        unsafe { self.values[index.to_usize().unwrap()].assume_init_mut() }
+        self.bs_index.remove(bs_i.to_usize().unwrap());
+        self.list.free_by_index(index)
     }
 }
 
-impl<K: Ord, V, const CAP: usize, I: PrimInt + Unsigned> ConstLru<K, V, CAP, I> {
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned, C: Comparator<K>> ConstLru<K, V, CAP, I, C> {
     /// Inserts a key-value pair into the map. The entry is moved to the most-recently-used slot
     ///
     /// If `CAP == 0`, `None` is returned.
@@ -392,7 +476,7 @@ impl<K: Ord, V, const CAP: usize, I: PrimInt + Unsigned> ConstLru<K, V, CAP, I>
         let insert_bs_i = match self.get_index_of(&k) {
             Ok((existing_index, _)) => {
                 return Some(InsertReplaced::OldValue(
-                    self.insert_replace_value(existing_index, v),
+                    self.list.replace_value(existing_index, v),
                 ))
             }
             Err(i) => i,
@@ -406,68 +490,296 @@ impl<K: Ord, V, const CAP: usize, I: PrimInt + Unsigned> ConstLru<K, V, CAP, I>
         }
     }
 
-    /// Assumes N > 0 and self is full
-    /// Moves newly inserted elem to MRU position
+    /// Inserts the key-value pairs of `entries` as if by calling [`Self::insert`] on each of them in order,
+    /// but merges the new keys into the sorted index chunk by chunk
+    /// instead of shifting it once per new key like repeated [`Self::insert`]s do.
     ///
-    /// Returns (index entry was inserted into, evicted entry)
-    fn insert_evict_lru(&mut self, insert_bs_i: I, k: K, v: V) -> (I, (K, V)) {
-        // N > 0, tail must be valid
-        let i = self.tail;
-        let t = i.to_usize().unwrap();
-        let evicted_k = unsafe { self.keys[t].assume_init_read() };
-        let evicted_v = unsafe { self.values[t].assume_init_read() };
-        let Ok((_should_be_t, evicted_bs_i)) = self.get_index_of(&evicted_k) else {
-            unreachable!()
-        };
-        self.keys[t].write(k);
-        self.values[t].write(v);
-
-        match insert_bs_i.cmp(&evicted_bs_i) {
-            // nothing to be done, bs_index[insert_bs_i] already == tail
-            Ordering::Equal => (),
-            Ordering::Less => {
-                // shift everything between [insert_bs_i, evicted_bs_i) right
-                // then insert at insert_bs_i
-                let b = insert_bs_i.to_usize().unwrap();
-                unsafe {
-                    let bs_i_ptr = self.bs_index.as_mut_ptr().add(b);
-                    ptr::copy(
-                        bs_i_ptr,
-                        bs_i_ptr.add(1),
-                        (evicted_bs_i - insert_bs_i).to_usize().unwrap(),
-                    );
+    /// The resulting entries, their LRU order and the calls to `on_replaced` are the same as inserting the entries in order:
+    /// `on_replaced` is called with what each [`Self::insert`] would have returned, in the same order.
+    ///
+    /// `entries` is buffered on the stack in chunks of up to 32 entries.
+    /// Each chunk is sorted, its keys are searched for in ascending order and
+    /// it is merged into the sorted index with one pass over the index,
+    /// so inserting `n` entries moves `O(n / 32 * CAP)` index elements. If `CAP == 0`, the entries are dropped.
+    ///
+    /// `on_replaced` is only called once a chunk is fully merged, so the map stays consistent if it panics.
+    pub fn insert_batch<T: IntoIterator<Item = (K, V)>, F: FnMut(InsertReplaced<K, V>)>(
+        &mut self,
+        entries: T,
+        mut on_replaced: F,
+    ) {
+        if CAP == 0 {
+            return;
+        }
+        let mut entries = entries.into_iter();
+        loop {
+            let mut chunk: [Option<(K, V)>; INSERT_BATCH_CHUNK_LEN] =
+                core::array::from_fn(|_| None);
+            let mut n = 0;
+            for (slot, entry) in chunk.iter_mut().zip(entries.by_ref()) {
+                *slot = Some(entry);
+                n += 1;
+            }
+            if n == 0 {
+                return;
+            }
+            self.insert_chunk(&mut chunk[..n], &mut on_replaced);
+            if n < INSERT_BATCH_CHUNK_LEN {
+                return;
+            }
+        }
+    }
+
+    /// [`Self::insert_batch`] for at most [`INSERT_BATCH_CHUNK_LEN`] entries, leaving `entries` empty
+    ///
+    /// All comparisons happen before the list and bs_index are modified and
+    /// `on_replaced` is called after they're consistent again, so a panic in either leaves a valid map.
+    ///
+    /// Requirements:
+    /// - `0 < entries.len() <= INSERT_BATCH_CHUNK_LEN`
+    /// - all of `entries` are `Some`
+    fn insert_chunk<F: FnMut(InsertReplaced<K, V>)>(
+        &mut self,
+        entries: &mut [Option<(K, V)>],
+        on_replaced: &mut F,
+    ) {
+        let cap = self.list.cap();
+        let n = entries.len();
+
+        // positions of entries sorted by key, then by position
+        let mut sorted: [usize; INSERT_BATCH_CHUNK_LEN] = core::array::from_fn(|p| p);
+        let sorted = &mut sorted[..n];
+        sorted.sort_unstable_by(|&a, &b| {
+            let (Some((ka, _)), Some((kb, _))) = (&entries[a], &entries[b]) else {
+                unreachable!()
+            };
+            <C as Comparator<K>>::cmp(ka, kb).then(a.cmp(&b))
+        });
+
+        // entries with equal keys are adjacent in `sorted` and form a group.
+        // Groups are numbered in ascending key order
+        let mut group_of = [0; INSERT_BATCH_CHUNK_LEN];
+        let mut n_groups = 0;
+        for (s, &p) in sorted.iter().enumerate() {
+            if s > 0 {
+                let (Some((k_prev, _)), Some((k, _))) = (&entries[sorted[s - 1]], &entries[p])
+                else {
+                    unreachable!()
+                };
+                if <C as Comparator<K>>::cmp(k_prev, k) != core::cmp::Ordering::Equal {
+                    n_groups += 1;
                 }
-                self.bs_index[b] = self.tail;
             }
-            Ordering::Greater => {
-                // shift everything between (evicted_bs_i, bs_i - 1] left
-                // then insert at bs_i - 1
-
-                // safety: greater, so bs_i must be > 0
-                let inser_bs_i_sub_1 = insert_bs_i - I::one();
-                unsafe {
-                    let evicted_bs_i_ptr = self
-                        .bs_index
-                        .as_mut_ptr()
-                        .add(evicted_bs_i.to_usize().unwrap());
-                    ptr::copy(
-                        evicted_bs_i_ptr.add(1),
-                        evicted_bs_i_ptr,
-                        (inser_bs_i_sub_1 - evicted_bs_i).to_usize().unwrap(),
-                    );
+            group_of[p] = n_groups;
+        }
+        n_groups += 1;
+
+        // find the bs_index position of each group's key, or where it would be inserted if it's new.
+        // keys are searched for in ascending order, so each search continues from where the previous one ended
+        let mut searched: [Result<usize, usize>; INSERT_BATCH_CHUNK_LEN] =
+            [Err(0); INSERT_BATCH_CHUNK_LEN];
+        // index each group's key is currently stored at, cap if not stored
+        let mut group_indices = [cap; INSERT_BATCH_CHUNK_LEN];
+        let mut from = 0;
+        for (s, &p) in sorted.iter().enumerate() {
+            let g = group_of[p];
+            if s > 0 && group_of[sorted[s - 1]] == g {
+                continue;
+            }
+            let Some((k, _)) = &entries[p] else {
+                unreachable!()
+            };
+            let list = &self.list;
+            let res = self.bs_index.gallop_by(from, |probe| {
+                <C as Comparator<K>>::cmp(list.get_key_by_index(*probe), k)
+            });
+            let (Ok(bs_i) | Err(bs_i)) = res;
+            from = bs_i;
+            if res.is_ok() {
+                group_indices[g] = self.bs_index.get(bs_i);
+            }
+            searched[g] = res;
+        }
+
+        // no comparisons from here on.
+        // The entries of a group found in the bs_index are removed from it and re-inserted at the end,
+        // along with the entries of the other groups that are still stored then
+        let mut removed = [cap; 2 * INSERT_BATCH_CHUNK_LEN];
+        let mut n_removed = 0;
+        for (g, res) in searched[..n_groups].iter().enumerate() {
+            if res.is_ok() {
+                removed[n_removed] = group_indices[g];
+                n_removed += 1;
+            }
+        }
+
+        // replay the inserts in order on the list only.
+        // Replaced keys are dropped at the end, once the map is consistent again
+        let mut replaced: [Option<InsertReplaced<K, V>>; INSERT_BATCH_CHUNK_LEN] =
+            core::array::from_fn(|_| None);
+        let mut n_replaced = 0;
+        let mut dropped_keys: [Option<K>; INSERT_BATCH_CHUNK_LEN] = core::array::from_fn(|_| None);
+        for p in 0..n {
+            let g = group_of[p];
+            let (k, v) = entries[p].take().unwrap();
+            if group_indices[g] != cap {
+                let old_v = self.list.replace_value(group_indices[g], v);
+                replaced[n_replaced] = Some(InsertReplaced::OldValue(old_v));
+                n_replaced += 1;
+                dropped_keys[p] = Some(k);
+            } else if !self.list.is_full() {
+                group_indices[g] = self.list.alloc_new(k, v);
+            } else {
+                let tail = self.list.tail;
+                match group_indices[..n_groups].iter_mut().find(|i| **i == tail) {
+                    Some(evicted_group_index) => *evicted_group_index = cap,
+                    // an entry the batch hasn't touched
+                    None => {
+                        removed[n_removed] = tail;
+                        n_removed += 1;
+                    }
                 }
-                self.bs_index[inser_bs_i_sub_1.to_usize().unwrap()] = self.tail;
+                let (i, (old_k, old_v)) = self.list.replace_tail(k, v);
+                group_indices[g] = i;
+                replaced[n_replaced] = Some(InsertReplaced::LruEvicted(old_k, old_v));
+                n_replaced += 1;
             }
         }
 
-        self.move_to_head(self.tail);
-        (i, (evicted_k, evicted_v))
+        // positions the stored groups are inserted at, in ascending key order,
+        // and the indices of their entries
+        let mut new_bs_is = [0; INSERT_BATCH_CHUNK_LEN];
+        let mut new_indices = [cap; INSERT_BATCH_CHUNK_LEN];
+        let mut n_new = 0;
+        for (g, res) in searched[..n_groups].iter().enumerate() {
+            if group_indices[g] != cap {
+                let (Ok(bs_i) | Err(bs_i)) = *res;
+                new_bs_is[n_new] = bs_i;
+                new_indices[n_new] = group_indices[g];
+                n_new += 1;
+            }
+        }
+        let new_bs_is = &mut new_bs_is[..n_new];
+        if n_removed > 0 {
+            let removed = &mut removed[..n_removed];
+            removed.sort_unstable();
+            self.bs_index
+                .retain(|index| removed.binary_search(index).is_err(), new_bs_is);
+        }
+        self.bs_index.insert_sorted(
+            n_new,
+            new_bs_is
+                .iter()
+                .copied()
+                .zip(new_indices[..n_new].iter().copied())
+                .rev(),
+        );
+
+        for r in replaced[..n_replaced].iter_mut() {
+            on_replaced(r.take().unwrap());
+        }
+    }
+
+    /// Inserts a key-value pair into the map at the most-recently-used slot without evicting or overwriting,
+    /// returning a mutable reference to the inserted value.
+    ///
+    /// Errors with
+    /// - [`TryInsertError::Occupied`] if the key is present. Its LRU position is not changed
+    /// - [`TryInsertError::Full`] if the map is full or `CAP == 0`
+    pub fn try_insert(
+        &mut self,
+        k: K,
+        v: V,
+    ) -> Result<&mut V, TryInsertError<'_, K, V, CAP, I, C>> {
+        if CAP == 0 {
+            return Err(TryInsertError::Full(k, v));
+        }
+        match self.entry(k) {
+            Entry::Occupied(e) => Err(TryInsertError::Occupied(e, v)),
+            Entry::Vacant(e) => e.try_insert(v),
+        }
+    }
+
+    /// Inserts a key-value pair into the map at the least-recently-used slot, so that it is evicted next.
+    /// Use this for speculative or scanned data that shouldn't displace the entries in use.
+    ///
+    /// If `CAP == 0`, `None` is returned.
+    ///
+    /// If the map did not have this key present and is not full, `None` is returned.
+    ///
+    /// If the map did have this key present, the value is updated, and the old value is returned in a [`InsertReplaced::OldValue`].
+    /// The entry keeps its position in the LRU order, so re-inserting entries in use doesn't demote them.
+    ///
+    /// If the map is full, the least-recently used key-value pair is evicted and returned in a [`InsertReplaced::LruEvicted`].
+    pub fn insert_cold(&mut self, k: K, v: V) -> Option<InsertReplaced<K, V>> {
+        self.insert_at_fraction(k, v, 1.0)
+    }
+
+    /// Inserts a key-value pair into the map at `fraction` of the way from the most-recently-used slot (`0.0`)
+    /// to the least-recently-used one (`1.0`), like the midpoint insertion of database buffer pools.
+    /// New entries then have to be used again before they can displace the entries closer to the most-recently-used end.
+    ///
+    /// `fraction` is clamped to `[0.0, 1.0]`, NaN is treated as `0.0`.
+    /// The position is rounded down, and counts the new entry.
+    ///
+    /// Returns the same as [`Self::insert_cold`], and existing entries likewise keep their position.
+    pub fn insert_at_fraction(
+        &mut self,
+        k: K,
+        v: V,
+        fraction: f32,
+    ) -> Option<InsertReplaced<K, V>> {
+        if CAP == 0 {
+            return None;
+        }
+        let insert_bs_i = match self.get_index_of(&k) {
+            Ok((existing_index, _)) => {
+                let old_v = core::mem::replace(self.list.get_mut_by_index(existing_index), v);
+                return Some(InsertReplaced::OldValue(old_v));
+            }
+            Err(i) => i,
+        };
+        let (i, res) = if self.is_full() {
+            let ((i, _), (old_k, old_v)) = self.insert_evict_lru(insert_bs_i, k, v);
+            (i, Some(InsertReplaced::LruEvicted(old_k, old_v)))
+        } else {
+            (self.insert_alloc_new(insert_bs_i, k, v), None)
+        };
+        self.list
+            .move_to_position(i, self.position_at_fraction(fraction));
+        res
+    }
+
+    /// Assumes N > 0 and self is full
+    /// Moves newly inserted elem to MRU position
+    ///
+    /// Returns ((index entry was inserted into, its bs_index index), evicted entry)
+    fn insert_evict_lru(&mut self, insert_bs_i: I, k: K, v: V) -> ((I, I), (K, V)) {
+        // N > 0, tail must be valid
+        let evicted_k = self.list.get_key_by_index(self.list.tail);
+        let Ok((_should_be_tail, evicted_bs_i)) = self.get_index_of(evicted_k) else {
+            unreachable!()
+        };
+        let (i, evicted) = self.list.replace_tail(k, v);
+
+        // the gap left by the evicted entry is moved to insert_bs_i,
+        // which was found while the evicted entry was still in the index
+        self.bs_index.remove(evicted_bs_i.to_usize().unwrap());
+        let insert_bs_i = if insert_bs_i > evicted_bs_i {
+            insert_bs_i - I::one()
+        } else {
+            insert_bs_i
+        };
+        self.bs_index.insert(insert_bs_i.to_usize().unwrap(), i);
+
+        ((i, insert_bs_i), evicted)
     }
 
     /// Removes a key from the `ConstLru`, returning the value at the key if the key was previously in the `ConstLru`.
-    pub fn remove<Q: Ord + ?Sized>(&mut self, k: &Q) -> Option<V>
+    pub fn remove<Q: ?Sized>(&mut self, k: &Q) -> Option<V>
     where
         K: Borrow<Q>,
+        C: Comparator<Q>,
     {
         let tup = self.get_index_of(k).ok()?;
         Some(self.remove_by_index(tup).1)
@@ -476,115 +788,735 @@ impl<K: Ord, V, const CAP: usize, I: PrimInt + Unsigned> ConstLru<K, V, CAP, I>
     /// Returns a reference to the value corresponding to the key and moves entry to most-recently-used slot.
     ///
     /// To not update to most-recently-used, use [`Self::get_untouched`]
-    pub fn get<Q: Ord + ?Sized>(&mut self, k: &Q) -> Option<&V>
+    pub fn get<Q: ?Sized>(&mut self, k: &Q) -> Option<&V>
     where
         K: Borrow<Q>,
+        C: Comparator<Q>,
     {
         let (index, _) = self.get_index_of(k).ok()?;
-        self.move_to_head(index);
-        Some(self.get_by_index(index))
+        self.list.move_to_head(index);
+        Some(self.list.get_by_index(index))
     }
 
     /// Returns a mutable reference to the value corresponding to the key and moves entry to most-recently-used slot.
     ///
     /// To not update to most-recently-used, use [`Self::get_mut_untouched`]
-    pub fn get_mut<Q: Ord + ?Sized>(&mut self, k: &Q) -> Option<&mut V>
+    pub fn get_mut<Q: ?Sized>(&mut self, k: &Q) -> Option<&mut V>
     where
         K: Borrow<Q>,
+        C: Comparator<Q>,
     {
         let (index, _) = self.get_index_of(k).ok()?;
-        self.move_to_head(index);
-        Some(self.get_mut_by_index(index))
+        self.list.move_to_head(index);
+        Some(self.list.get_mut_by_index(index))
     }
 
     /// Ok(kv_i, bs_index_i)
     ///
     /// Err(bs_index_i)
-    fn get_index_of<Q: Ord + ?Sized>(&self, k: &Q) -> Result<(I, I), I>
+    fn get_index_of<Q: ?Sized>(&self, k: &Q) -> Result<(I, I), I>
     where
         K: Borrow<Q>,
+        C: Comparator<Q>,
     {
-        let l = self.len().to_usize().unwrap();
-        let valid_bs_index = &self.bs_index[0..l];
-        valid_bs_index
+        self.bs_index
             .binary_search_by(|probe_index| {
-                let p = probe_index.to_usize().unwrap();
-                let probe = unsafe { self.keys[p].assume_init_ref() };
-                probe.borrow().cmp(k)
+                let probe = self.list.get_key_by_index(*probe_index);
+                <C as Comparator<Q>>::cmp(probe.borrow(), k)
             })
-            .map(|bs_i| (self.bs_index[bs_i], I::from(bs_i).unwrap()))
+            .map(|bs_i| (self.bs_index.get(bs_i), I::from(bs_i).unwrap()))
             .map_err(|new_bsi| I::from(new_bsi).unwrap())
     }
 
     /// Returns a reference to the value corresponding to the key without updating the entry to most-recently-used slot
     ///
     /// To update to most-recently-used, use [`Self::get`]
-    pub fn get_untouched<Q: Ord + ?Sized>(&self, k: &Q) -> Option<&V>
+    pub fn get_untouched<Q: ?Sized>(&self, k: &Q) -> Option<&V>
     where
         K: Borrow<Q>,
+        C: Comparator<Q>,
     {
         let (index, _) = self.get_index_of(k).ok()?;
-        Some(self.get_by_index(index))
+        Some(self.list.get_by_index(index))
     }
 
     /// Returns a mutable reference to the value corresponding to the key without updating the entry to most-recently-used slot
     ///
     /// To update to most-recently-used, use [`Self::get_mut`]
-    pub fn get_mut_untouched<Q: Ord + ?Sized>(&mut self, k: &Q) -> Option<&mut V>
+    pub fn get_mut_untouched<Q: ?Sized>(&mut self, k: &Q) -> Option<&mut V>
     where
         K: Borrow<Q>,
+        C: Comparator<Q>,
     {
         let (index, _) = self.get_index_of(k).ok()?;
-        Some(self.get_mut_by_index(index))
+        Some(self.list.get_mut_by_index(index))
     }
 
-    /// Gets the given key’s corresponding entry in the map for in-place manipulation.
+    /// Returns references to the values corresponding to the keys and moves their entries to the most-recently-used slot in order,
+    /// so that the last key's entry is the most-recently-used.
     ///
-    /// **panics** if CAP == 0
-    pub fn entry(&mut self, k: K) -> Entry<'_, K, V, CAP, I> {
-        Entry::new(self, k)
+    /// Returns `None` without updating the LRU order if any of the keys is not present.
+    pub fn get_many<Q: ?Sized, const N: usize>(&mut self, keys: [&Q; N]) -> Option<[&V; N]>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        let indices = self.get_many_indices(keys)?;
+        for index in indices {
+            self.list.move_to_head(index);
+        }
+        Some(indices.map(|index| self.list.get_by_index(index)))
     }
-}
 
-impl<K: Clone, V: Clone, const CAP: usize, I: PrimInt + Unsigned> ConstLru<K, V, CAP, I> {
-    /// Clones the ConstLru to a region of allocated memory
+    /// Returns mutable references to the values corresponding to the keys and moves their entries to the most-recently-used slot in order,
+    /// so that the last key's entry is the most-recently-used.
     ///
-    /// # Safety
-    /// `dst` must point to uninitialized memory, since this
-    /// overwrites the data at `dst`
-    pub unsafe fn clone_to_alloc(&self, dst: *mut Self) {
-        addr_of_mut!((*dst).len).write(self.len);
-        addr_of_mut!((*dst).head).write(self.head);
-        addr_of_mut!((*dst).tail).write(self.tail);
-
-        // .write(self.nexts) result in stack overflow for large CAP, so use raw memmove
-        ptr::copy(
-            self.nexts.as_ptr(),
-            addr_of_mut!((*dst).nexts) as *mut I,
-            CAP,
-        );
-        ptr::copy(
-            self.prevs.as_ptr(),
-            addr_of_mut!((*dst).prevs) as *mut I,
-            CAP,
-        );
-        ptr::copy(
-            self.bs_index.as_ptr(),
-            addr_of_mut!((*dst).bs_index) as *mut I,
-            CAP,
-        );
-
-        for (index, k, v) in IterIndexed::new(self) {
-            let i = index.to_usize().unwrap();
-            addr_of_mut!((*dst).keys[i]).write(MaybeUninit::new(k.clone()));
-            addr_of_mut!((*dst).values[i]).write(MaybeUninit::new(v.clone()));
+    /// Returns `None` without updating the LRU order if any of the keys is not present or if any two keys are equal.
+    ///
+    /// To not update to most-recently-used, use [`Self::get_many_mut_untouched`]
+    pub fn get_many_mut<Q: ?Sized, const N: usize>(&mut self, keys: [&Q; N]) -> Option<[&mut V; N]>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        let indices = self.get_many_distinct_indices(keys)?;
+        for index in indices {
+            self.list.move_to_head(index);
         }
+        Some(self.list.get_many_mut_by_index(indices))
     }
-}
 
-/// WARNING: this might result in runtime stack overflow errors for large `CAP`.
-/// To clone a large `ConstLru`, use [`ConstLru::clone_to_alloc`]
-impl<K: Clone, V: Clone, const CAP: usize, I: PrimInt + Unsigned> Clone for ConstLru<K, V, CAP, I> {
+    /// Returns mutable references to the values corresponding to the keys without updating their entries to most-recently-used slot
+    ///
+    /// Returns `None` if any of the keys is not present or if any two keys are equal.
+    ///
+    /// To update to most-recently-used, use [`Self::get_many_mut`]
+    pub fn get_many_mut_untouched<Q: ?Sized, const N: usize>(
+        &mut self,
+        keys: [&Q; N],
+    ) -> Option<[&mut V; N]>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        let indices = self.get_many_distinct_indices(keys)?;
+        Some(self.list.get_many_mut_by_index(indices))
+    }
+
+    /// Looks up the keys in order and calls `f` with each key and a mutable reference to its value, if present,
+    /// moving the entries present to the most-recently-used slot in order.
+    ///
+    /// Each search continues from where the previous one ended if the key is not smaller than the previous one,
+    /// so that looking up keys sorted in ascending order costs `O(log(distance))` per key instead of `O(log(len))`.
+    ///
+    /// To not update to most-recently-used, use [`Self::get_batch_untouched`]
+    pub fn get_batch<Q, F: FnMut(&Q, Option<&mut V>)>(&mut self, keys: &[Q], mut f: F)
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        let mut prev = None;
+        for k in keys {
+            match self.batch_index_of(k, &mut prev) {
+                Some(index) => {
+                    self.list.move_to_head(index);
+                    f(k, Some(self.list.get_mut_by_index(index)));
+                }
+                None => f(k, None),
+            }
+        }
+    }
+
+    /// Same as [`Self::get_batch`], but calls `f` with shared references and does not update the LRU order
+    pub fn get_batch_untouched<Q, F: FnMut(&Q, Option<&V>)>(&self, keys: &[Q], mut f: F)
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        let mut prev = None;
+        for k in keys {
+            f(
+                k,
+                self.batch_index_of(k, &mut prev)
+                    .map(|index| self.list.get_by_index(index)),
+            );
+        }
+    }
+
+    /// Searches for `k` starting from where the search for the previous key `prev` ended if `k` is not smaller,
+    /// then updates `prev` to `k` and where its search ended.
+    fn batch_index_of<'q, Q>(&self, k: &'q Q, prev: &mut Option<(&'q Q, usize)>) -> Option<I>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        let from = match *prev {
+            Some((prev_k, bs_i))
+                if <C as Comparator<Q>>::cmp(prev_k, k) != core::cmp::Ordering::Greater =>
+            {
+                bs_i
+            }
+            _ => 0,
+        };
+        let res = self.bs_index.gallop_by(from, |probe| {
+            <C as Comparator<Q>>::cmp(self.list.get_key_by_index(*probe).borrow(), k)
+        });
+        let (Ok(bs_i) | Err(bs_i)) = res;
+        *prev = Some((k, bs_i));
+        res.ok().map(|bs_i| self.bs_index.get(bs_i))
+    }
+
+    /// Returns `None` if any of the keys is not present
+    fn get_many_indices<Q: ?Sized, const N: usize>(&self, keys: [&Q; N]) -> Option<[I; N]>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        let mut indices = [I::zero(); N];
+        for (index, k) in indices.iter_mut().zip(keys) {
+            *index = self.get_index_of(k).ok()?.0;
+        }
+        Some(indices)
+    }
+
+    /// Returns `None` if any of the keys is not present or if any two keys are equal
+    fn get_many_distinct_indices<Q: ?Sized, const N: usize>(&self, keys: [&Q; N]) -> Option<[I; N]>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        let indices = self.get_many_indices(keys)?;
+        let distinct = indices
+            .iter()
+            .enumerate()
+            .all(|(j, index)| !indices[..j].contains(index));
+        distinct.then_some(indices)
+    }
+
+    /// Moves the entry of the key to the most-recently-used slot without reading it.
+    ///
+    /// Returns `false` if the key is not present.
+    pub fn promote<Q: ?Sized>(&mut self, k: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        let Ok((index, _)) = self.get_index_of(k) else {
+            return false;
+        };
+        self.list.move_to_head(index);
+        true
+    }
+
+    /// Moves the entry of the key to the least-recently-used slot, so that it is evicted next.
+    ///
+    /// Returns `false` if the key is not present.
+    pub fn demote<Q: ?Sized>(&mut self, k: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        let Ok((index, _)) = self.get_index_of(k) else {
+            return false;
+        };
+        self.list.move_to_tail(index);
+        true
+    }
+
+    /// Promotes the entries of the keys in order, so that the last key present ends up most-recently-used.
+    ///
+    /// Keys that are not present are skipped. Returns the number of keys present.
+    pub fn touch_many<'q, Q: ?Sized + 'q, T: IntoIterator<Item = &'q Q>>(
+        &mut self,
+        keys: T,
+    ) -> usize
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        keys.into_iter().filter(|k| self.promote(*k)).count()
+    }
+
+    /// Moves the entry of the key to position `n` in LRU order,
+    /// where 0 is the most-recently-used slot and `len - 1` the least-recently-used one.
+    /// `n >= len` moves it to the least-recently-used slot.
+    ///
+    /// Takes `O(min(n, len - n))` to walk to the position.
+    ///
+    /// Returns `false` if the key is not present.
+    pub fn set_position<Q: ?Sized>(&mut self, k: &Q, n: I) -> bool
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        let Ok((index, _)) = self.get_index_of(k) else {
+            return false;
+        };
+        self.list
+            .move_to_position(index, n.min(self.len() - I::one()));
+        true
+    }
+
+    /// Returns `true` if the `ConstLru` contains the key. Does not update the LRU order.
+    pub fn contains_key<Q: ?Sized>(&self, k: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        self.get_index_of(k).is_ok()
+    }
+
+    /// Returns references to the key-value pair corresponding to the key and moves entry to most-recently-used slot.
+    ///
+    /// To not update to most-recently-used, use [`Self::get_key_value_untouched`]
+    pub fn get_key_value<Q: ?Sized>(&mut self, k: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        let (index, _) = self.get_index_of(k).ok()?;
+        self.list.move_to_head(index);
+        Some((
+            self.list.get_key_by_index(index),
+            self.list.get_by_index(index),
+        ))
+    }
+
+    /// Returns references to the key-value pair corresponding to the key without updating the entry to most-recently-used slot
+    ///
+    /// To update to most-recently-used, use [`Self::get_key_value`]
+    pub fn get_key_value_untouched<Q: ?Sized>(&self, k: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        let (index, _) = self.get_index_of(k).ok()?;
+        Some((
+            self.list.get_key_by_index(index),
+            self.list.get_by_index(index),
+        ))
+    }
+
+    /// Returns a [`CursorKeyOrder`] at the entry with the smallest key `>= k`,
+    /// or at the ghost if there's no such entry.
+    ///
+    /// Does not change the LRU order of the elements.
+    pub fn lower_bound<Q: ?Sized>(&self, k: &Q) -> CursorKeyOrder<'_, K, V, CAP, I, C>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        let bs_i = match self.get_index_of(k) {
+            Ok((_, bs_i)) | Err(bs_i) => bs_i,
+        };
+        CursorKeyOrder::new(self, bs_i.to_usize().unwrap())
+    }
+
+    /// Returns a [`CursorKeyOrder`] at the entry with the smallest key `> k`,
+    /// or at the ghost if there's no such entry.
+    ///
+    /// [`CursorKeyOrder::peek_prev`] then returns the entry with the largest key `<= k`.
+    ///
+    /// Does not change the LRU order of the elements.
+    pub fn upper_bound<Q: ?Sized>(&self, k: &Q) -> CursorKeyOrder<'_, K, V, CAP, I, C>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        let bs_i = match self.get_index_of(k) {
+            Ok((_, bs_i)) => bs_i + I::one(),
+            Err(bs_i) => bs_i,
+        };
+        CursorKeyOrder::new(self, bs_i.to_usize().unwrap())
+    }
+
+    /// Gets the given key’s corresponding entry in the map for in-place manipulation.
+    ///
+    /// **panics** if CAP == 0. Use [`Self::try_entry`] if `CAP` can be 0
+    pub fn entry(&mut self, k: K) -> Entry<'_, K, V, CAP, I, C> {
+        Entry::new(self, k)
+    }
+
+    /// Same as [`Self::entry`], but looks the entry up by a borrowed key,
+    /// so that the owned key is only created with `K::from(k)` if a value is inserted.
+    ///
+    /// **panics** if CAP == 0
+    pub fn entry_ref<'b, Q: ?Sized>(&mut self, k: &'b Q) -> EntryRef<'_, 'b, K, Q, V, CAP, I, C>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        EntryRef::new(self, k)
+    }
+
+    /// Same as [`Self::entry`], but returns [`TryEntry::ZeroCap`] instead of panicking if `CAP == 0`.
+    ///
+    /// Its `or_insert*()` methods return the value by value if `CAP == 0`,
+    /// so generic code can use `CAP == 0` to disable caching.
+    pub fn try_entry(&mut self, k: K) -> TryEntry<'_, K, V, CAP, I, C> {
+        TryEntry::new(self, k)
+    }
+
+    /// Returns a reference to the value corresponding to the key, inserting the result of `f` if it's not present.
+    /// The entry is moved to the most-recently-used slot.
+    ///
+    /// Only a single binary search is done, unlike a [`Self::get`] followed by a [`Self::insert`] on a miss.
+    ///
+    /// If the `ConstLru` is full, inserting evicts the least-recently used entry.
+    ///
+    /// **panics** if CAP == 0. If `CAP` can be 0, use [`Self::try_entry`] and [`TryEntry::or_insert_with_key`] instead
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use const_lru::ConstLru;
+    ///
+    /// let mut c: ConstLru<u32, u64, 8, u8> = ConstLru::new();
+    /// assert_eq!(*c.get_or_insert_with(3, |k| u64::from(*k) * 2), 6);
+    /// assert_eq!(*c.get_or_insert_with(3, |_| unreachable!()), 6);
+    /// ```
+    pub fn get_or_insert_with<F: FnOnce(&K) -> V>(&mut self, k: K, f: F) -> &V {
+        self.entry(k).or_insert_with_key(f)
+    }
+
+    /// Same as [`Self::get_or_insert_with`], but `f` can fail,
+    /// in which case nothing is inserted and the error is returned.
+    ///
+    /// **panics** if CAP == 0. If `CAP` can be 0, use [`Self::try_entry`] instead
+    pub fn get_or_try_insert_with<E, F: FnOnce(&K) -> Result<V, E>>(
+        &mut self,
+        k: K,
+        f: F,
+    ) -> Result<&V, E> {
+        match self.entry(k) {
+            Entry::Occupied(e) => Ok(e.into_mut()),
+            Entry::Vacant(e) => {
+                let v = f(e.key())?;
+                Ok(e.insert(v).0)
+            }
+        }
+    }
+
+    /// Creates a ConstLru from an `entries` array of at most `CAP` entries in MRU -> LRU order,
+    /// erroring on duplicate keys instead of keeping the most-recently-used one like [`From`] does.
+    ///
+    /// Returns the key of the more recently-used entry of the first duplicate found.
+    ///
+    /// `N > CAP` and invalid `CAP` and `I` combinations are rejected at compile time.
+    ///
+    /// Might overflow the stack like [`Self::new`].
+    pub fn try_from_array_strict<const N: usize>(
+        entries: [(K, V); N],
+    ) -> Result<Self, DuplicateKeysError<K>> {
+        #[allow(clippy::let_unit_value)]
+        let () = ArrayFitsInCap::<N, CAP>::ASSERT;
+        #[allow(clippy::let_unit_value)]
+        let () = LruList::<K, V, CAP, I>::CAP_FITS_IN_I;
+        Self::try_from_iter_strict(entries.into_iter().rev())
+    }
+
+    /// Splits the ConstLru in two at `k`, returning a new ConstLru with all entries with keys `>= k`.
+    ///
+    /// Both ConstLrus keep the relative LRU order of their entries.
+    ///
+    /// Might overflow the stack like [`Self::new`].
+    /// To split a large `ConstLru`, use [`Self::split_off_into_uninit`]
+    pub fn split_off<Q: ?Sized>(&mut self, k: &Q) -> Self
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        let mut res: MaybeUninit<Self> = MaybeUninit::uninit();
+        self.split_off_into_uninit(k, &mut res);
+        unsafe { res.assume_init() }
+    }
+
+    /// Same as [`Self::split_off`], but moves the entries with keys `>= k` into `slot`
+    /// and returns a mutable reference to it.
+    ///
+    /// Overwrites `slot` like [`Self::init_in`].
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use const_lru::ConstLru;
+    /// use core::mem::MaybeUninit;
+    ///
+    /// let mut c: ConstLru<u32, u16, 4, u8> = [(4, 4), (2, 2), (3, 3), (1, 1)].into();
+    /// let mut slot: Box<MaybeUninit<ConstLru<u32, u16, 4, u8>>> = Box::new_uninit();
+    /// let upper = c.split_off_into_uninit(&3, &mut slot);
+    /// assert!(c.keys().eq(&[2, 1]));
+    /// assert!(upper.keys().eq(&[4, 3]));
+    /// ```
+    pub fn split_off_into_uninit<'a, Q: ?Sized>(
+        &mut self,
+        k: &Q,
+        slot: &'a mut MaybeUninit<Self>,
+    ) -> &'a mut Self
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        // self exists, so CAP and I are valid
+        let dst = unsafe {
+            Self::init_at_alloc_unchecked(slot.as_mut_ptr());
+            slot.assume_init_mut()
+        };
+        let at = match self.get_index_of(k) {
+            Ok((_, bs_i)) | Err(bs_i) => bs_i.to_usize().unwrap(),
+        };
+        let mut remaining = self.len().to_usize().unwrap() - at;
+        if remaining == 0 {
+            return dst;
+        }
+
+        // entries keep their indices, so the moved part of the sorted index stays valid for dst.
+        // Moving from least-recently-used to most-recently-used keeps their relative order
+        let mut index = self.list.tail;
+        while remaining > 0 {
+            let prev = self.list.prevs[index.to_usize().unwrap()];
+            let key = self.list.get_key_by_index(index).borrow();
+            if <C as Comparator<Q>>::cmp(key, k) != core::cmp::Ordering::Less {
+                let (k, v) = self.list.free_by_index(index);
+                dst.list.alloc_at(index, k, v);
+                remaining -= 1;
+            }
+            index = prev;
+        }
+        self.bs_index.split_off(at, &mut dst.bs_index);
+        dst
+    }
+
+    /// Moves all entries of `other` into `self`, leaving `other` empty.
+    ///
+    /// `other`'s entries become more recently-used than `self`'s, keeping their relative LRU order,
+    /// like inserting them from least-recently-used to most-recently-used with [`Self::insert`] does.
+    /// If a key is present in both, the entry gets `other`'s value and LRU position and keeps `self`'s key.
+    ///
+    /// If there are more than `CAP` entries, the least-recently-used ones are evicted and dropped.
+    /// To get the evicted entries or interleave the LRU orders instead, use [`Self::merge_from`].
+    pub fn append(&mut self, other: &mut Self) {
+        self.merge_inner(other, true, |_, _, _| unreachable!(), |_, _| ());
+    }
+
+    /// Moves all entries of `other` into `self` by interleaving their LRU orders, leaving `other` empty.
+    ///
+    /// The `n`th most-recently-used entry of `self` becomes more recently-used than the `n`th most-recently-used entry of `other`,
+    /// which becomes more recently-used than the `n + 1`th most-recently-used entry of `self`.
+    ///
+    /// If a key is present in both, `resolve(k, self_v, other_v)` merges `other`'s value into `self`'s,
+    /// and the entry keeps `self`'s key and LRU position.
+    ///
+    /// If there are more than `CAP` entries, the least-recently-used ones in the interleaved order are evicted
+    /// and passed to `on_evicted`.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use const_lru::ConstLru;
+    ///
+    /// let mut a: ConstLru<u32, u16, 4, u8> = [(3, 3), (2, 2), (1, 1)].into();
+    /// let mut b: ConstLru<u32, u16, 4, u8> = [(4, 40), (5, 50), (2, 20)].into();
+    /// let mut evicted = Vec::new();
+    /// a.merge_from(&mut b, |_, a_v, b_v| *a_v += b_v, |k, v| evicted.push((k, v)));
+    /// assert!(b.is_empty());
+    /// assert!(a.iter().eq([(&3, &3), (&4, &40), (&2, &22), (&5, &50)]));
+    /// assert_eq!(evicted, [(1, 1)]);
+    /// ```
+    pub fn merge_from<R: FnMut(&K, &mut V, V), E: FnMut(K, V)>(
+        &mut self,
+        other: &mut Self,
+        resolve: R,
+        on_evicted: E,
+    ) {
+        self.merge_inner(other, false, resolve, on_evicted);
+    }
+
+    /// Moves all entries of `other` into `self`, in LRU order
+    /// - all of `other`'s entries, then all of `self`'s if `other_first`
+    /// - alternating between `self`'s and `other`'s, starting with `self`'s, otherwise
+    ///
+    /// Keys present in both always keep `self`'s key:
+    /// - if `other_first`, the entry keeps `other`'s value and LRU position, `self`'s value is dropped
+    ///   and `resolve` is never called
+    /// - otherwise, the entry keeps `self`'s LRU position and `resolve(k, self_v, other_v)` is called once
+    ///   to merge `other`'s value into `self`'s
+    fn merge_inner<R: FnMut(&K, &mut V, V), E: FnMut(K, V)>(
+        &mut self,
+        other: &mut Self,
+        other_first: bool,
+        mut resolve: R,
+        mut on_evicted: E,
+    ) {
+        if CAP == 0 {
+            return;
+        }
+        let cap = self.list.cap();
+
+        // pair up keys present in both in a single pass over both sorted indices, keeping only one node of each pair.
+        // The freed node is behind the pass in its sorted index, so it isn't read again
+        let self_len = self.len().to_usize().unwrap();
+        let other_len = other.len().to_usize().unwrap();
+        let (mut a, mut b) = (0, 0);
+        while a < self_len && b < other_len {
+            let s = self.bs_index.get(a);
+            let o = other.bs_index.get(b);
+            let ks = self.list.get_key_by_index(s);
+            let ko = other.list.get_key_by_index(o);
+            match <C as Comparator<K>>::cmp(ks, ko) {
+                core::cmp::Ordering::Less => a += 1,
+                core::cmp::Ordering::Greater => b += 1,
+                core::cmp::Ordering::Equal => {
+                    if other_first {
+                        let (k, _) = self.list.free_by_index(s);
+                        other.list.replace_key(o, k);
+                    } else {
+                        let (k, v) = other.list.free_by_index(o);
+                        resolve(&k, self.list.get_mut_by_index(s), v);
+                    }
+                    a += 1;
+                    b += 1;
+                }
+            }
+        }
+
+        // build the new LRU order from the most-recently-used end by moving placed entries to the tail.
+        // Unplaced entries of self stay in front of them in their old order, so the next one is always the head.
+        // Entries of other are taken out as they're placed, so the next one is always other's head
+        let mut n_unplaced = self.len().to_usize().unwrap();
+        let mut n_placed = 0;
+        let mut first_placed = cap;
+        let mut self_turn = !other_first;
+        while n_placed < CAP && (n_unplaced > 0 || !other.is_empty()) {
+            let take_self = n_unplaced > 0 && (self_turn || other.is_empty());
+            if !other_first {
+                self_turn = !self_turn;
+            }
+            let index = if take_self {
+                n_unplaced -= 1;
+                self.list.head
+            } else {
+                let (k, v) = other.list.free_by_index(other.list.head);
+                if self.is_full() {
+                    // n_placed < CAP, so there are unplaced entries of self, which won't all fit anymore.
+                    // The least-recently-used one is the last one that would be placed
+                    let (k, v) = self.free_last_unplaced(first_placed);
+                    n_unplaced -= 1;
+                    on_evicted(k, v);
+                }
+                self.list.alloc_new(k, v)
+            };
+            self.list.move_to_tail(index);
+            if first_placed == cap {
+                first_placed = index;
+            }
+            n_placed += 1;
+        }
+
+        // evict whatever didn't fit
+        for _ in 0..n_unplaced {
+            let (k, v) = self.free_last_unplaced(first_placed);
+            on_evicted(k, v);
+        }
+        while !other.is_empty() {
+            let (k, v) = other.list.free_by_index(other.list.head);
+            on_evicted(k, v);
+        }
+        other.clear();
+
+        let list = &self.list;
+        let mut index = list.head;
+        self.bs_index.rebuild(
+            (0..list.len().to_usize().unwrap()).map(|_| {
+                let i = index;
+                index = list.nexts[i.to_usize().unwrap()];
+                i
+            }),
+            |a, b| <C as Comparator<K>>::cmp(list.get_key_by_index(*a), list.get_key_by_index(*b)),
+        );
+    }
+
+    /// Takes out the least-recently-used entry of the ones in front of `first_placed`,
+    /// or the tail if nothing is placed yet, without updating the sorted index
+    ///
+    /// Requirements:
+    /// - there's at least 1 entry in front of `first_placed`
+    fn free_last_unplaced(&mut self, first_placed: I) -> (K, V) {
+        let index = if first_placed == self.list.cap() {
+            self.list.tail
+        } else {
+            self.list.prevs[first_placed.to_usize().unwrap()]
+        };
+        self.list.free_by_index(index)
+    }
+
+    /// Creates a ConstLru from `iter`, erroring on duplicate keys instead of overwriting them like [`FromIterator`] does.
+    ///
+    /// Later items are more recently-used. If `iter` has more than `CAP` items,
+    /// the least-recently-used ones are evicted, so duplicates of evicted keys are not detected.
+    ///
+    /// Returns the first duplicate key found.
+    ///
+    /// Panics and might overflow the stack like [`Self::new`].
+    pub fn try_from_iter_strict<T: IntoIterator<Item = (K, V)>>(
+        iter: T,
+    ) -> Result<Self, DuplicateKeysError<K>> {
+        let mut res = Self::new();
+        if CAP == 0 {
+            return Ok(res);
+        }
+        for (k, v) in iter {
+            let insert_bs_i = match res.get_index_of(&k) {
+                Ok(_) => return Err(DuplicateKeysError(k)),
+                Err(i) => i,
+            };
+            if res.is_full() {
+                res.insert_evict_lru(insert_bs_i, k, v);
+            } else {
+                res.insert_alloc_new(insert_bs_i, k, v);
+            }
+        }
+        Ok(res)
+    }
+}
+
+impl<K: Clone, V: Clone, const CAP: usize, I: PrimInt + Unsigned, C> ConstLru<K, V, CAP, I, C> {
+    /// Clones the ConstLru to a region of allocated memory
+    ///
+    /// # Safety
+    /// `dst` must point to uninitialized memory, since this
+    /// overwrites the data at `dst`
+    pub unsafe fn clone_to_alloc(&self, dst: *mut Self) {
+        let dst_list = addr_of_mut!((*dst).list);
+        self.list.clone_links_to_alloc(dst_list);
+
+        self.bs_index.clone_to_alloc(addr_of_mut!((*dst).bs_index));
+
+        addr_of_mut!((*dst).comparator).write(PhantomData);
+
+        for (index, _, _) in IterIndexed::new(self) {
+            self.list.clone_entry_to_alloc(dst_list, index);
+        }
+    }
+
+    /// Clones the ConstLru into `slot` and returns a mutable reference to the clone.
+    ///
+    /// Safe alternative to [`Self::clone_to_alloc`].
+    ///
+    /// Overwrites `slot` like [`Self::init_in`].
+    pub fn clone_into_uninit<'a>(&self, slot: &'a mut MaybeUninit<Self>) -> &'a mut Self {
+        unsafe {
+            self.clone_to_alloc(slot.as_mut_ptr());
+            slot.assume_init_mut()
+        }
+    }
+}
+
+/// WARNING: this might result in runtime stack overflow errors for large `CAP`.
+/// To clone a large `ConstLru`, use [`ConstLru::clone_to_alloc`]
+impl<K: Clone, V: Clone, const CAP: usize, I: PrimInt + Unsigned, C> Clone
+    for ConstLru<K, V, CAP, I, C>
+{
     fn clone(&self) -> Self {
         let mut res: MaybeUninit<Self> = MaybeUninit::uninit();
         unsafe {
@@ -594,19 +1526,85 @@ impl<K: Clone, V: Clone, const CAP: usize, I: PrimInt + Unsigned> Clone for Cons
     }
 }
 
-impl<K, V, const CAP: usize, I: PrimInt + Unsigned> Default for ConstLru<K, V, CAP, I> {
-    fn default() -> Self {
-        Self::new()
+// not derived so that `C` doesn't have to be `Debug`
+/// Formats the entries as a map from most-recently-used to least-recently-used
+impl<K: Debug, V: Debug, const CAP: usize, I: PrimInt + Unsigned, C> Debug
+    for ConstLru<K, V, CAP, I, C>
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Map equality that ignores LRU order, see [`ConstLru::eq_as_map`].
+/// Use [`ConstLru::eq_with_order`] to also compare LRU order.
+impl<K: PartialEq, V: PartialEq, const CAP: usize, I: PrimInt + Unsigned, C> PartialEq
+    for ConstLru<K, V, CAP, I, C>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.eq_as_map(other)
+    }
+}
+
+impl<K: Eq, V: Eq, const CAP: usize, I: PrimInt + Unsigned, C> Eq for ConstLru<K, V, CAP, I, C> {}
+
+/// Hashes the length and the entries in key order, consistent with `PartialEq` ignoring LRU order
+impl<K: Hash, V: Hash, const CAP: usize, I: PrimInt + Unsigned, C> Hash
+    for ConstLru<K, V, CAP, I, C>
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len().to_usize().unwrap().hash(state);
+        for entry in self.iter_key_order() {
+            entry.hash(state);
+        }
+    }
+}
+
+/// Returns a reference to the value corresponding to the key without updating the LRU order,
+/// same as [`ConstLru::get_untouched`].
+///
+/// **panics** if the key is not present in the `ConstLru`
+impl<
+        K: Borrow<Q>,
+        V,
+        Q: ?Sized,
+        const CAP: usize,
+        I: PrimInt + Unsigned,
+        C: Comparator<K> + Comparator<Q>,
+    > Index<&Q> for ConstLru<K, V, CAP, I, C>
+{
+    type Output = V;
+
+    fn index(&self, k: &Q) -> &V {
+        self.get_untouched(k).expect("key not in ConstLru")
     }
 }
 
-impl<K, V, const CAP: usize, I: PrimInt + Unsigned> Drop for ConstLru<K, V, CAP, I> {
-    fn drop(&mut self) {
-        self.drop_cleanup();
+/// Returns a mutable reference to the value corresponding to the key without updating the LRU order,
+/// same as [`ConstLru::get_mut_untouched`].
+///
+/// **panics** if the key is not present in the `ConstLru`
+impl<
+        K: Borrow<Q>,
+        V,
+        Q: ?Sized,
+        const CAP: usize,
+        I: PrimInt + Unsigned,
+        C: Comparator<K> + Comparator<Q>,
+    > IndexMut<&Q> for ConstLru<K, V, CAP, I, C>
+{
+    fn index_mut(&mut self, k: &Q) -> &mut V {
+        self.get_mut_untouched(k).expect("key not in ConstLru")
     }
 }
 
-impl<K, V, const CAP: usize, I: PrimInt + Unsigned> IntoIterator for ConstLru<K, V, CAP, I> {
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned, C> Default for ConstLru<K, V, CAP, I, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned, C> IntoIterator for ConstLru<K, V, CAP, I, C> {
     type Item = <IntoIter<K, V, CAP, I> as Iterator>::Item;
 
     type IntoIter = IntoIter<K, V, CAP, I>;
@@ -623,63 +1621,63 @@ pub enum InsertReplaced<K, V> {
     OldValue(V),
 }
 
-/// Creates a full ConstLru cache from an `entries` array.
+/// Creates a ConstLru from an iterator of key-value pairs.
 ///
-/// Assumes `entries` is in MRU -> LRU order.
+/// Later items are more recently-used. If `iter` has more than `CAP` items, the least-recently-used ones are evicted.
+/// Duplicate keys overwrite the values of earlier ones, see [`ConstLru::insert`].
+/// Use [`ConstLru::try_from_iter_strict`] to error on duplicate keys instead.
 ///
-/// Returns error if duplicate keys found.
-///
-/// WARNING: this might result in runtime stack overflow errors for large `CAP`.
-impl<K: Ord, V, const CAP: usize, I: PrimInt + Unsigned> TryFrom<[(K, V); CAP]>
-    for ConstLru<K, V, CAP, I>
+/// Panics and might overflow the stack like [`ConstLru::new`].
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned, C: Comparator<K>> FromIterator<(K, V)>
+    for ConstLru<K, V, CAP, I, C>
 {
-    type Error = DuplicateKeysError<K>;
-
-    fn try_from(entries: [(K, V); CAP]) -> Result<Self, Self::Error> {
-        // entries need to fit on the stack too, so Self::new() shouldn't stack overflow
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
         let mut res = Self::new();
-        res.len = res.cap();
-        res.head = I::zero();
-        res.tail = if CAP > 0 {
-            res.len - I::one()
-        } else {
-            I::zero()
-        };
+        res.extend(iter);
+        res
+    }
+}
 
-        for (i, (k, v)) in entries.into_iter().enumerate() {
-            res.keys[i].write(k);
-            res.values[i].write(v);
+/// Inserts all key-value pairs of an iterator in order, see [`ConstLru::insert`].
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned, C: Comparator<K>> Extend<(K, V)>
+    for ConstLru<K, V, CAP, I, C>
+{
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        for (k, v) in iter {
+            self.insert(k, v);
         }
+    }
+}
 
-        for (i, val) in res.bs_index.iter_mut().enumerate() {
-            *val = I::from(i).unwrap();
-        }
-        res.bs_index.sort_unstable_by(|a, b| {
-            let k_a = unsafe { res.keys[a.to_usize().unwrap()].assume_init_ref() };
-            let k_b = unsafe { res.keys[b.to_usize().unwrap()].assume_init_ref() };
-            k_a.cmp(k_b)
-        });
+struct ArrayFitsInCap<const N: usize, const CAP: usize>;
 
-        if CAP > 1 {
-            for w in res.bs_index.windows(2) {
-                let index_1 = w[0];
-                let i1 = index_1.to_usize().unwrap();
-                let i2 = w[1].to_usize().unwrap();
-                let k1 = unsafe { res.keys[i1].assume_init_ref() };
-                let k2 = unsafe { res.keys[i2].assume_init_ref() };
-                if k1 == k2 {
-                    // remove from list so no double free
-                    res.unlink_node(index_1);
-                    res.len = res.len - I::one();
-
-                    // cleanup value
-                    unsafe { res.values[i1].assume_init_drop() };
-                    let k_copied_out = unsafe { res.keys[i1].assume_init_read() };
-                    return Err(DuplicateKeysError(k_copied_out));
-                }
-            }
-        }
+impl<const N: usize, const CAP: usize> ArrayFitsInCap<N, CAP> {
+    const ASSERT: () = assert!(N <= CAP, "N > CAP");
+}
 
-        Ok(res)
+/// Creates a ConstLru from an `entries` array of at most `CAP` entries.
+///
+/// Assumes `entries` is in MRU -> LRU order, the reverse of [`FromIterator`].
+/// Duplicate keys keep the value of their most-recently-used entry.
+/// Use [`ConstLru::try_from_array_strict`] to error on duplicate keys instead.
+///
+/// `N > CAP` and invalid `CAP` and `I` combinations are rejected at compile time.
+///
+/// WARNING: this might result in runtime stack overflow errors for large `CAP`.
+///
+/// ```compile_fail
+/// use const_lru::ConstLru;
+///
+/// let _c: ConstLru<u8, u8, 1, u8> = [(1, 1), (2, 2)].into();
+/// ```
+impl<K, V, const N: usize, const CAP: usize, I: PrimInt + Unsigned, C: Comparator<K>>
+    From<[(K, V); N]> for ConstLru<K, V, CAP, I, C>
+{
+    fn from(entries: [(K, V); N]) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = ArrayFitsInCap::<N, CAP>::ASSERT;
+        let mut res = Self::new_checked();
+        res.extend(entries.into_iter().rev());
+        res
     }
 }
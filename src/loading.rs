@@ -0,0 +1,341 @@
+use core::borrow::Borrow;
+use core::cell::UnsafeCell;
+use core::cmp::Ordering;
+use core::future::Future;
+use core::mem::MaybeUninit;
+use core::pin::Pin;
+use core::ptr::addr_of_mut;
+use core::task::{Context, Poll, Waker};
+use num_traits::{PrimInt, Unsigned};
+use std::vec::Vec;
+
+use crate::lock::RawLock;
+use crate::lru_list::LruList;
+use crate::{CapacityError, Comparator, ConstLru, InsertReplaced, NaturalOrder};
+
+/// A [`ConstLru`] shared between async tasks that loads missing values with an async loader,
+/// running only one load at a time for each key.
+///
+/// Requires the `std` feature. It doesn't depend on any async runtime.
+///
+/// If [`Self::get_or_load`] is called for a key that's already being loaded,
+/// it waits for that load to finish instead of starting another one.
+/// Failed loads are not cached: callers that were waiting on a failed load
+/// retry with their own loader, which is again only run once for all of them.
+///
+/// The cache is locked with a `std::sync::Mutex`, but never while a loader is running.
+///
+/// Generics:
+/// - `K`. Type of key. `C` is used for lookup and to address entries.
+/// - `V`. Type of value.
+/// - `CAP`. Capacity of the cache.
+/// - `I`. Type of the index used. Must be an unsigned primitive type with bitwidth <= `usize`'s bitwidth.
+/// - `C`. [`Comparator`] that orders the keys. Defaults to [`NaturalOrder`], which uses `K`'s `Ord` impl.
+pub struct LoadingCache<K, V, const CAP: usize, I: PrimInt + Unsigned = usize, C = NaturalOrder> {
+    lock: RawLock,
+
+    /// only accessed while `lock` is held
+    state: UnsafeCell<LoadingState<K, V, CAP, I, C>>,
+}
+
+struct LoadingState<K, V, const CAP: usize, I: PrimInt + Unsigned, C> {
+    const_lru: ConstLru<K, V, CAP, I, C>,
+
+    /// loads that haven't finished yet, at most 1 per key
+    in_flight: Vec<InFlight<K>>,
+
+    /// id of the next load
+    next_load_id: u64,
+}
+
+struct InFlight<K> {
+    key: K,
+
+    /// distinguishes this load from later loads of the same key
+    load_id: u64,
+
+    /// woken when the load finishes
+    waiters: Vec<Waker>,
+}
+
+// safety: `state` is only accessed while `lock` is held, same as `Mutex<T>: Sync` if `T: Send`
+unsafe impl<K, V, const CAP: usize, I: PrimInt + Unsigned, C> Sync for LoadingCache<K, V, CAP, I, C> where
+    ConstLru<K, V, CAP, I, C>: Send
+{
+}
+
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned, C> LoadingCache<K, V, CAP, I, C> {
+    /// Creates a new empty `LoadingCache` on the stack
+    ///
+    /// Panics and might overflow the stack like [`ConstLru::new`].
+    /// Use [`Self::init_at_alloc`] to initialize larger variants at preallocated memory
+    pub fn new() -> Self {
+        let mut res: MaybeUninit<Self> = MaybeUninit::uninit();
+        unsafe {
+            Self::init_at_alloc(res.as_mut_ptr());
+            res.assume_init()
+        }
+    }
+
+    /// Initializes the LoadingCache at a region of allocated memory
+    ///
+    /// # Safety
+    /// `ptr` must point to uninitialized memory, since init()
+    /// overwrites the data at `ptr`
+    ///
+    /// Panics like [`ConstLru::init_at_alloc`].
+    ///
+    /// Use [`Self::init_in`] for a safe, non-panicking alternative.
+    pub unsafe fn init_at_alloc(ptr: *mut Self) {
+        if let Err(e) = LruList::<K, V, CAP, I>::check_cap() {
+            panic!("{e}");
+        }
+        Self::init_at_alloc_unchecked(ptr);
+    }
+
+    /// Initializes the LoadingCache in `slot` and returns a mutable reference to it.
+    ///
+    /// Errors and overwrites `slot` like [`ConstLru::init_in`].
+    pub fn init_in(slot: &mut MaybeUninit<Self>) -> Result<&mut Self, CapacityError> {
+        LruList::<K, V, CAP, I>::check_cap()?;
+        unsafe {
+            Self::init_at_alloc_unchecked(slot.as_mut_ptr());
+            Ok(slot.assume_init_mut())
+        }
+    }
+
+    /// Requirements:
+    /// - `LruList::check_cap()` is `Ok`
+    /// - `ptr` points to allocated memory that can be overwritten
+    unsafe fn init_at_alloc_unchecked(ptr: *mut Self) {
+        addr_of_mut!((*ptr).lock).write(RawLock::new());
+        let state = UnsafeCell::raw_get(addr_of_mut!((*ptr).state));
+        ConstLru::init_at_alloc_unchecked(addr_of_mut!((*state).const_lru));
+        addr_of_mut!((*state).in_flight).write(Vec::new());
+        addr_of_mut!((*state).next_load_id).write(0);
+    }
+
+    /// Locks the cache and calls `f` with its state
+    fn with_state<T>(&self, f: impl FnOnce(&mut LoadingState<K, V, CAP, I, C>) -> T) -> T {
+        let _guard = self.lock.lock();
+        // safety: lock is held until _guard is dropped at the end of this fn
+        f(unsafe { &mut *self.state.get() })
+    }
+
+    /// Locks the cache and calls `f` with the `ConstLru`.
+    ///
+    /// Use this to perform multiple operations on the cache atomically, or to use the [`Entry`](crate::Entry) API.
+    ///
+    /// Accessing the cache from within `f` deadlocks.
+    pub fn with<T>(&self, f: impl FnOnce(&mut ConstLru<K, V, CAP, I, C>) -> T) -> T {
+        self.with_state(|state| f(&mut state.const_lru))
+    }
+
+    /// Clears the cache, removing all key-value pairs. Loads in flight are not affected.
+    pub fn clear(&self) {
+        self.with(|c| c.clear())
+    }
+
+    /// Returns the number of elements in the cache.
+    pub fn len(&self) -> I {
+        self.with(|c| c.len())
+    }
+
+    /// Returns `true` if the cache contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.with(|c| c.is_empty())
+    }
+}
+
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned, C: Comparator<K>>
+    LoadingCache<K, V, CAP, I, C>
+{
+    /// Inserts a key-value pair into the cache.
+    ///
+    /// See [`ConstLru::insert`].
+    pub fn insert(&self, k: K, v: V) -> Option<InsertReplaced<K, V>> {
+        self.with(|c| c.insert(k, v))
+    }
+
+    /// Removes a key from the cache, returning the value at the key if the key was previously in the cache.
+    pub fn remove<Q: ?Sized>(&self, k: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        self.with(|c| c.remove(k))
+    }
+
+    /// Returns a clone of the value corresponding to the key and moves entry to most-recently-used slot.
+    ///
+    /// Does not wait for loads in flight.
+    pub fn get_cloned<Q: ?Sized>(&self, k: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+        V: Clone,
+    {
+        self.with(|c| c.get(k).cloned())
+    }
+}
+
+impl<K: Clone, V: Clone, const CAP: usize, I: PrimInt + Unsigned, C: Comparator<K>>
+    LoadingCache<K, V, CAP, I, C>
+{
+    /// Returns a clone of the value corresponding to the key, loading it with `loader` if it's not cached.
+    ///
+    /// If the key is already being loaded, waits for that load to finish instead of calling `loader`.
+    /// If `loader` succeeds, its value is inserted into the cache, evicting the least-recently-used entry if full.
+    /// If it fails, nothing is cached and the error is returned only to this caller.
+    ///
+    /// If the returned future is dropped while `loader` is running,
+    /// the load is abandoned and one of the callers waiting on it loads the key instead.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use const_lru::LoadingCache;
+    /// # use core::{future::Future, pin::pin, task::{Context, Poll, Waker}};
+    /// # fn block_on<F: Future>(f: F) -> F::Output {
+    /// #     let mut f = pin!(f);
+    /// #     loop {
+    /// #         if let Poll::Ready(res) = f.as_mut().poll(&mut Context::from_waker(Waker::noop())) {
+    /// #             return res;
+    /// #         }
+    /// #     }
+    /// # }
+    ///
+    /// async fn fetch(k: u32) -> Result<String, std::io::Error> {
+    ///     Ok(format!("value of {k}"))
+    /// }
+    ///
+    /// let cache: LoadingCache<u32, String, 64, u8> = LoadingCache::new();
+    /// let v = block_on(cache.get_or_load(1, |k| fetch(*k)));
+    /// assert_eq!(v.unwrap(), "value of 1");
+    /// assert_eq!(cache.get_cloned(&1).unwrap(), "value of 1");
+    /// ```
+    pub async fn get_or_load<E, F, Fut>(&self, k: K, loader: F) -> Result<V, E>
+    where
+        F: FnOnce(&K) -> Fut,
+        Fut: Future<Output = Result<V, E>>,
+    {
+        let load_id = loop {
+            let wait_for = self.with_state(|state| {
+                if let Some(v) = state.const_lru.get(&k) {
+                    return Err(Ok(v.clone()));
+                }
+                if let Some(in_flight) = state.find_in_flight(&k) {
+                    return Ok(in_flight.load_id);
+                }
+                let load_id = state.next_load_id;
+                state.next_load_id += 1;
+                state.in_flight.push(InFlight {
+                    key: k.clone(),
+                    load_id,
+                    waiters: Vec::new(),
+                });
+                Err(Err(load_id))
+            });
+            match wait_for {
+                Ok(load_id) => {
+                    WaitForLoad {
+                        cache: self,
+                        load_id,
+                        key: &k,
+                    }
+                    .await
+                }
+                Err(Ok(v)) => return Ok(v),
+                Err(Err(load_id)) => break load_id,
+            }
+        };
+
+        let guard = InFlightGuard {
+            cache: self,
+            load_id,
+        };
+        let res = loader(&k).await;
+        self.with_state(|state| {
+            if let Ok(v) = &res {
+                state.const_lru.insert(k, v.clone());
+            }
+            state.finish_load(load_id);
+        });
+        core::mem::forget(guard);
+        res
+    }
+}
+
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned, C> LoadingState<K, V, CAP, I, C> {
+    fn find_in_flight<Q: ?Sized>(&self, k: &Q) -> Option<&InFlight<K>>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        self.in_flight
+            .iter()
+            .find(|in_flight| C::cmp(in_flight.key.borrow(), k) == Ordering::Equal)
+    }
+
+    /// Removes the load from the loads in flight and wakes its waiters
+    fn finish_load(&mut self, load_id: u64) {
+        let Some(i) = self.in_flight.iter().position(|f| f.load_id == load_id) else {
+            return;
+        };
+        for waker in self.in_flight.swap_remove(i).waiters {
+            waker.wake();
+        }
+    }
+}
+
+/// Finishes the load if the loading future is dropped before the loader completes
+struct InFlightGuard<'a, K, V, const CAP: usize, I: PrimInt + Unsigned, C> {
+    cache: &'a LoadingCache<K, V, CAP, I, C>,
+
+    load_id: u64,
+}
+
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned, C> Drop for InFlightGuard<'_, K, V, CAP, I, C> {
+    fn drop(&mut self) {
+        self.cache
+            .with_state(|state| state.finish_load(self.load_id));
+    }
+}
+
+/// Resolves once the load with `load_id` finishes
+struct WaitForLoad<'a, K, V, const CAP: usize, I: PrimInt + Unsigned, C> {
+    cache: &'a LoadingCache<K, V, CAP, I, C>,
+
+    load_id: u64,
+
+    key: &'a K,
+}
+
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned, C: Comparator<K>> Future
+    for WaitForLoad<'_, K, V, CAP, I, C>
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.cache.with_state(|state| {
+            let in_flight = state
+                .in_flight
+                .iter_mut()
+                .find(|f| f.load_id == self.load_id);
+            let Some(in_flight) = in_flight else {
+                return Poll::Ready(());
+            };
+            debug_assert!(C::cmp(&in_flight.key, self.key) == Ordering::Equal);
+            if !in_flight.waiters.iter().any(|w| w.will_wake(cx.waker())) {
+                in_flight.waiters.push(cx.waker().clone());
+            }
+            Poll::Pending
+        })
+    }
+}
+
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned, C> Default for LoadingCache<K, V, CAP, I, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,75 @@
+//! The lock used by the thread-safe wrappers of `ConstLru`:
+//! a `std::sync::Mutex` under the `std` feature, a spin lock otherwise.
+
+#[cfg(feature = "std")]
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+#[cfg(not(feature = "std"))]
+use core::{
+    hint::spin_loop,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// Guards data that is stored separately from it,
+/// so that the data can be initialized in place.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub(crate) struct RawLock(Mutex<()>);
+
+/// Releases the lock on drop
+#[cfg(feature = "std")]
+pub(crate) type RawLockGuard<'a> = MutexGuard<'a, ()>;
+
+#[cfg(feature = "std")]
+impl RawLock {
+    pub(crate) const fn new() -> Self {
+        Self(Mutex::new(()))
+    }
+
+    /// Blocks until the lock is acquired.
+    ///
+    /// Poisoning is ignored: the guarded data is only accessed through the safe `ConstLru` API,
+    /// so a panic while the lock is held can't leave it in an invalid state.
+    pub(crate) fn lock(&self) -> RawLockGuard<'_> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Guards data that is stored separately from it,
+/// so that the data can be initialized in place.
+#[cfg(not(feature = "std"))]
+#[derive(Debug)]
+pub(crate) struct RawLock(AtomicBool);
+
+/// Releases the lock on drop
+#[cfg(not(feature = "std"))]
+pub(crate) struct RawLockGuard<'a>(&'a AtomicBool);
+
+#[cfg(not(feature = "std"))]
+impl RawLock {
+    pub(crate) const fn new() -> Self {
+        Self(AtomicBool::new(false))
+    }
+
+    /// Spins until the lock is acquired
+    pub(crate) fn lock(&self) -> RawLockGuard<'_> {
+        while self
+            .0
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            // only retry the write once the lock looks free to avoid contending for the cache line
+            while self.0.load(Ordering::Relaxed) {
+                spin_loop();
+            }
+        }
+        RawLockGuard(&self.0)
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl Drop for RawLockGuard<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
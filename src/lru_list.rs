@@ -0,0 +1,443 @@
+use core::mem::{size_of, MaybeUninit};
+use core::ptr::{self, addr_of_mut};
+
+use num_traits::{PrimInt, Unsigned};
+
+use crate::iters::iter_maybe_uninit::IterMaybeUninit;
+use crate::CapacityError;
+
+/// Keys and values in struct-of-arrays format,
+/// ordered by a doubly-linked list that uses array indices instead of pointers.
+///
+/// Only handles LRU-ordering and the free-list,
+/// looking up entries by key is left to the cache that owns this.
+#[derive(Debug)]
+pub(crate) struct LruList<K, V, const CAP: usize, I: PrimInt + Unsigned> {
+    pub(crate) len: I,
+
+    /// head is index of most recently used
+    ///
+    /// can be any value if list is empty
+    pub(crate) head: I,
+
+    /// tail is index of least recently used
+    ///
+    /// if list is empty, tail is the first slot of unallocated memory / "free-list"
+    /// else, next of the tail is the first slot of unallocated memory / "free-list"
+    ///
+    /// tail is always < CAP
+    pub(crate) tail: I,
+
+    /// disregard if value == CAP
+    pub(crate) nexts: [I; CAP],
+
+    /// disregard if value == CAP
+    pub(crate) prevs: [I; CAP],
+
+    pub(crate) keys: [MaybeUninit<K>; CAP],
+
+    pub(crate) values: [MaybeUninit<V>; CAP],
+}
+
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned> LruList<K, V, CAP, I> {
+    /// Compile-time equivalent of [`Self::check_cap`].
+    ///
+    /// `I` is an unsigned primitive int, so `I::MAX == 2^bits - 1`
+    /// and both checks can be expressed in terms of `size_of::<I>()`
+    pub(crate) const CAP_FITS_IN_I: () = {
+        assert!(size_of::<I>() <= size_of::<usize>(), "I::MAX > usize::MAX");
+        assert!(
+            size_of::<I>() == size_of::<usize>() || CAP >> (8 * size_of::<I>()) == 0,
+            "CAP > I::MAX"
+        );
+    };
+
+    /// Checks `CAP <= I::MAX <= usize::MAX`
+    pub(crate) fn check_cap() -> Result<(), CapacityError> {
+        let i_max = I::max_value()
+            .to_usize()
+            .ok_or(CapacityError::IndexExceedsUsizeMax)?;
+        if CAP > i_max {
+            return Err(CapacityError::CapExceedsIndexMax);
+        }
+        Ok(())
+    }
+
+    /// Initializes an empty LruList at a region of allocated memory
+    ///
+    /// Requirements:
+    /// - `CAP <= I::MAX <= usize::MAX`
+    /// - `ptr` points to allocated memory that can be overwritten
+    pub(crate) unsafe fn init_at_alloc(ptr: *mut Self) {
+        // using as_mut_ptr from MaybeUninit is UB,
+        // initialize fields using addr_of_mut!()
+
+        let cap = I::from(CAP).unwrap();
+
+        addr_of_mut!((*ptr).len).write(I::zero());
+        addr_of_mut!((*ptr).head).write(cap);
+        addr_of_mut!((*ptr).tail).write(I::zero());
+
+        // nexts = [1, 2, ..., cap-1, cap]
+        for i in 0..CAP {
+            addr_of_mut!((*ptr).nexts[i]).write(I::from(i + 1).unwrap());
+        }
+
+        // prevs = [cap, 0, 1, ..., cap-2]
+        if CAP > 0 {
+            addr_of_mut!((*ptr).prevs[0]).write(cap);
+            for i in 1..CAP {
+                addr_of_mut!((*ptr).prevs[i]).write(I::from(i - 1).unwrap());
+            }
+        }
+
+        // keys and values should remain uninitialized
+    }
+
+    pub(crate) fn cap(&self) -> I {
+        I::from(CAP).unwrap()
+    }
+
+    pub(crate) fn len(&self) -> I {
+        self.len
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.len() == I::zero()
+    }
+
+    pub(crate) fn is_full(&self) -> bool {
+        self.len() == self.cap()
+    }
+
+    /// Unlinks the node at `index` from the doubly-linked list,
+    /// patching its previous and next nodes, as well as self.head and self.tail if required.
+    ///
+    /// Can be used on both valid and invalid nodes.
+    ///
+    /// When this fn returns, `index`'s next and prev should be treated as invalid
+    ///
+    /// `self.head` and `self.tail` are not modified if only 1 elem in list
+    ///
+    /// Requirements:
+    /// - index < CAP
+    pub(crate) fn unlink_node(&mut self, index: I) {
+        let i = index.to_usize().unwrap();
+        let next = self.nexts[i];
+        let prev = self.prevs[i];
+
+        // index.next.prev = index.prev
+        if next != self.cap() {
+            self.prevs[next.to_usize().unwrap()] = prev;
+        }
+
+        // index.prev.next = index.next
+        if prev != self.cap() {
+            self.nexts[prev.to_usize().unwrap()] = next;
+        }
+
+        let is_one_elem_list = self.head == self.tail;
+
+        if self.head == index && !is_one_elem_list {
+            self.head = next;
+        }
+
+        if self.tail == index && !is_one_elem_list {
+            self.tail = prev;
+        }
+    }
+
+    /// Moves the element at index to the most-recently-used position.
+    ///
+    /// Requirements:
+    /// - !self.is_empty()
+    /// - index must be that of a valid node
+    pub(crate) fn move_to_head(&mut self, index: I) {
+        if self.head == index {
+            return;
+        }
+
+        self.unlink_node(index);
+        let i = index.to_usize().unwrap();
+
+        // since self.head != index
+        // and index is valid,
+        // head must be valid
+        let head = self.head;
+        self.prevs[i] = self.cap();
+        self.nexts[i] = head;
+
+        self.prevs[head.to_usize().unwrap()] = index;
+
+        self.head = index;
+    }
+
+    /// Moves the element at index to the least-recently-used position.
+    ///
+    /// Requirements:
+    /// - !self.is_empty()
+    /// - index must be that of a valid node
+    pub(crate) fn move_to_tail(&mut self, index: I) {
+        if self.tail == index {
+            return;
+        }
+
+        // since self.tail != index
+        // and index is valid,
+        // len > 1 and tail stays the same
+        self.unlink_node(index);
+        let i = index.to_usize().unwrap();
+
+        // insert between tail and the first slot of the free-list
+        let t = self.tail.to_usize().unwrap();
+        let first_free = self.nexts[t];
+        if first_free < self.cap() {
+            self.prevs[first_free.to_usize().unwrap()] = index;
+        }
+        self.nexts[i] = first_free;
+
+        self.prevs[i] = self.tail;
+        self.nexts[t] = index;
+
+        self.tail = index;
+    }
+
+    /// Moves the element at index to position `n` in LRU order,
+    /// where 0 is the most-recently-used position and `len - 1` the least-recently-used one.
+    ///
+    /// Requirements:
+    /// - index must be that of a valid node
+    /// - n < len
+    pub(crate) fn move_to_position(&mut self, index: I, n: I) {
+        let last = self.len() - I::one();
+        if n == I::zero() {
+            return self.move_to_head(index);
+        }
+        if n == last {
+            return self.move_to_tail(index);
+        }
+
+        // 0 < n < len - 1, so len > 2
+        // and after unlinking, there are len - 1 elements with a valid one at positions n - 1 and n
+        self.unlink_node(index);
+        let after = if n <= last / (I::one() + I::one()) {
+            let mut after = self.head;
+            for _ in 0..n.to_usize().unwrap() {
+                after = self.nexts[after.to_usize().unwrap()];
+            }
+            after
+        } else {
+            // new last position is len - 2
+            let mut after = self.tail;
+            for _ in 0..(last - I::one() - n).to_usize().unwrap() {
+                after = self.prevs[after.to_usize().unwrap()];
+            }
+            after
+        };
+        let a = after.to_usize().unwrap();
+        let prev = self.prevs[a];
+        let i = index.to_usize().unwrap();
+
+        self.prevs[i] = prev;
+        self.nexts[i] = after;
+        self.nexts[prev.to_usize().unwrap()] = index;
+        self.prevs[a] = index;
+    }
+
+    /// Cleanup for drop impl. Drops keys and values.
+    /// Other fields should be all primitive types
+    pub(crate) fn drop_cleanup(&mut self) {
+        for (k, v) in IterMaybeUninit::new(self) {
+            unsafe {
+                k.assume_init_drop();
+                v.assume_init_drop();
+            }
+        }
+    }
+
+    /// Writes the entry into the first slot of the free-list
+    /// and moves it to the most-recently-used position.
+    ///
+    /// Returns index entry was inserted into
+    ///
+    /// Requirements:
+    /// - CAP > 0
+    /// - !self.is_full()
+    pub(crate) fn alloc_new(&mut self, k: K, v: V) -> I {
+        let free_index = if self.is_empty() {
+            self.head = self.tail;
+            self.tail
+        } else {
+            self.nexts[self.tail.to_usize().unwrap()]
+        };
+        self.tail = free_index;
+        let f = free_index.to_usize().unwrap();
+        self.keys[f].write(k);
+        self.values[f].write(v);
+
+        self.len = self.len + I::one();
+
+        self.move_to_head(self.tail);
+        free_index
+    }
+
+    /// Same as [`Self::alloc_new`], but writes the entry into the free slot `index`
+    /// instead of the first slot of the free-list.
+    ///
+    /// Requirements:
+    /// - index < CAP
+    /// - index must not be that of a valid node
+    pub(crate) fn alloc_at(&mut self, index: I, k: K, v: V) -> I {
+        let first_free = if self.is_empty() {
+            self.tail
+        } else {
+            self.nexts[self.tail.to_usize().unwrap()]
+        };
+        if first_free != index {
+            // move index to the front of the free-list, so that alloc_new() takes it.
+            // index isn't the first free slot, so it's neither head nor tail
+            self.unlink_node(index);
+            let i = index.to_usize().unwrap();
+            self.nexts[i] = first_free;
+            self.prevs[first_free.to_usize().unwrap()] = index;
+            if self.is_empty() {
+                self.prevs[i] = self.cap();
+                self.tail = index;
+            } else {
+                let t = self.tail.to_usize().unwrap();
+                self.prevs[i] = self.tail;
+                self.nexts[t] = index;
+            }
+        }
+        self.alloc_new(k, v)
+    }
+
+    /// Takes the entry at `index` out and moves `index` to the front of the free-list
+    ///
+    /// Requirements:
+    /// - index must be that of a valid node
+    pub(crate) fn free_by_index(&mut self, index: I) -> (K, V) {
+        let i = index.to_usize().unwrap();
+
+        let key = unsafe { self.keys[i].assume_init_read() };
+        let val = unsafe { self.values[i].assume_init_read() };
+
+        // if len == 1, correct links are already in place
+        if self.len() > I::one() {
+            // len > 1
+            // move to front of free list
+            self.unlink_node(index);
+            let t = self.tail.to_usize().unwrap();
+            let first_free = self.nexts[t];
+
+            if first_free < self.cap() {
+                self.prevs[first_free.to_usize().unwrap()] = index;
+            }
+            self.nexts[i] = first_free;
+
+            self.prevs[i] = self.tail;
+            self.nexts[t] = index;
+        }
+
+        self.len = self.len - I::one();
+        (key, val)
+    }
+
+    /// Replaces the least-recently-used entry with the given entry
+    /// and moves it to the most-recently-used position.
+    ///
+    /// Returns (index entry was inserted into, evicted entry)
+    ///
+    /// Requirements:
+    /// - !self.is_empty()
+    pub(crate) fn replace_tail(&mut self, k: K, v: V) -> (I, (K, V)) {
+        let i = self.tail;
+        let t = i.to_usize().unwrap();
+        let evicted_k = unsafe { self.keys[t].assume_init_read() };
+        let evicted_v = unsafe { self.values[t].assume_init_read() };
+        self.keys[t].write(k);
+        self.values[t].write(v);
+        self.move_to_head(i);
+        (i, (evicted_k, evicted_v))
+    }
+
+    /// Assumes `index` is of a valid node
+    /// Moves `index` to MRU position
+    pub(crate) fn replace_value(&mut self, index: I, replacement: V) -> V {
+        let old_v = self.get_mut_by_index(index);
+        let old_v_out = core::mem::replace(old_v, replacement);
+        self.move_to_head(index);
+        old_v_out
+    }
+
+    /// Assumes `index` is of a valid node
+    /// Does not change LRU order
+    pub(crate) fn replace_key(&mut self, index: I, replacement: K) -> K {
+        let old_k = unsafe { self.keys[index.to_usize().unwrap()].assume_init_mut() };
+        core::mem::replace(old_k, replacement)
+    }
+
+    /// Assumes `indices` are of valid nodes and distinct
+    pub(crate) fn get_many_mut_by_index<const N: usize>(&mut self, indices: [I; N]) -> [&mut V; N] {
+        let values = self.values.as_mut_ptr();
+        // indices are distinct, so the references don't alias
+        indices.map(|index| unsafe { (*values.add(index.to_usize().unwrap())).assume_init_mut() })
+    }
+
+    /// Assumes index is valid
+    pub(crate) fn get_key_by_index(&self, index: I) -> &K {
+        unsafe { self.keys[index.to_usize().unwrap()].assume_init_ref() }
+    }
+
+    /// Assumes index is valid
+    pub(crate) fn get_by_index(&self, index: I) -> &V {
+        unsafe { self.values[index.to_usize().unwrap()].assume_init_ref() }
+    }
+
+    /// Assumes index is valid
+    pub(crate) fn get_mut_by_index(&mut self, index: I) -> &mut V {
+        unsafe { self.values[index.to_usize().unwrap()].assume_init_mut() }
+    }
+}
+
+impl<K: Clone, V: Clone, const CAP: usize, I: PrimInt + Unsigned> LruList<K, V, CAP, I> {
+    /// Copies the links of the LruList to a region of allocated memory.
+    ///
+    /// Keys and values are left uninitialized,
+    /// each valid index must then be cloned over with [`Self::clone_entry_to_alloc`]
+    ///
+    /// Requirements:
+    /// - `dst` points to allocated memory that can be overwritten
+    pub(crate) unsafe fn clone_links_to_alloc(&self, dst: *mut Self) {
+        addr_of_mut!((*dst).len).write(self.len);
+        addr_of_mut!((*dst).head).write(self.head);
+        addr_of_mut!((*dst).tail).write(self.tail);
+
+        // .write(self.nexts) result in stack overflow for large CAP, so use raw memmove
+        ptr::copy(
+            self.nexts.as_ptr(),
+            addr_of_mut!((*dst).nexts) as *mut I,
+            CAP,
+        );
+        ptr::copy(
+            self.prevs.as_ptr(),
+            addr_of_mut!((*dst).prevs) as *mut I,
+            CAP,
+        );
+    }
+
+    /// Requirements:
+    /// - `dst` points to allocated memory
+    /// - `index` is of a valid node
+    pub(crate) unsafe fn clone_entry_to_alloc(&self, dst: *mut Self, index: I) {
+        let i = index.to_usize().unwrap();
+        addr_of_mut!((*dst).keys[i]).write(MaybeUninit::new(self.get_key_by_index(index).clone()));
+        addr_of_mut!((*dst).values[i]).write(MaybeUninit::new(self.get_by_index(index).clone()));
+    }
+}
+
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned> Drop for LruList<K, V, CAP, I> {
+    fn drop(&mut self) {
+        self.drop_cleanup();
+    }
+}
@@ -0,0 +1,89 @@
+use core::mem::MaybeUninit;
+use core::ptr::addr_of_mut;
+use num_traits::{PrimInt, Unsigned};
+
+use crate::lru_list::LruList;
+use crate::{CapacityError, Comparator, ConstLru, MaybeStored, NaturalOrder};
+
+/// Caches the results of a pure function `f: FnMut(&K) -> V` in a [`ConstLru`],
+/// evicting the least-recently-used results once `CAP` are cached.
+/// With `CAP == 0`, nothing is cached and `f` is called every time.
+///
+/// Generics:
+/// - `F`. Type of the function.
+/// - `K`. Type of the function's argument. `C` is used to look up cached results.
+/// - `V`. Type of the function's result.
+/// - `CAP`. Maximum number of results cached.
+/// - `I`. Type of the index used. Must be an unsigned primitive type with bitwidth <= `usize`'s bitwidth.
+/// - `C`. [`Comparator`] that orders the arguments. Defaults to [`NaturalOrder`], which uses `K`'s `Ord` impl.
+///
+/// Example:
+///
+/// ```
+/// use const_lru::Memoized;
+///
+/// let mut calls = 0;
+/// let mut square: Memoized<_, u32, u64, 16, u8> = Memoized::new(|x: &u32| {
+///     calls += 1;
+///     u64::from(*x) * u64::from(*x)
+/// });
+/// assert_eq!(*square.call(3), 9);
+/// assert_eq!(*square.call(3), 9);
+/// drop(square);
+/// assert_eq!(calls, 1);
+/// ```
+pub struct Memoized<F, K, V, const CAP: usize, I: PrimInt + Unsigned = usize, C = NaturalOrder> {
+    f: F,
+
+    cache: ConstLru<K, V, CAP, I, C>,
+}
+
+impl<F, K, V, const CAP: usize, I: PrimInt + Unsigned, C> Memoized<F, K, V, CAP, I, C> {
+    /// Creates a new `Memoized` with an empty cache on the stack
+    ///
+    /// Panics and might overflow the stack like [`ConstLru::new`].
+    /// Use [`Self::init_in`] to initialize larger variants at preallocated memory
+    pub fn new(f: F) -> Self {
+        let mut res: MaybeUninit<Self> = MaybeUninit::uninit();
+        if let Err(e) = Self::init_in(&mut res, f) {
+            panic!("{e}");
+        }
+        unsafe { res.assume_init() }
+    }
+
+    /// Initializes the Memoized in `slot` with an empty cache and returns a mutable reference to it.
+    ///
+    /// Errors and overwrites `slot` like [`ConstLru::init_in`].
+    pub fn init_in(slot: &mut MaybeUninit<Self>, f: F) -> Result<&mut Self, CapacityError> {
+        LruList::<K, V, CAP, I>::check_cap()?;
+        let ptr = slot.as_mut_ptr();
+        unsafe {
+            addr_of_mut!((*ptr).f).write(f);
+            ConstLru::init_at_alloc_unchecked(addr_of_mut!((*ptr).cache));
+            Ok(slot.assume_init_mut())
+        }
+    }
+
+    /// Returns a reference to the cached results
+    pub fn cache(&self) -> &ConstLru<K, V, CAP, I, C> {
+        &self.cache
+    }
+
+    /// Returns a mutable reference to the cached results, e.g. to invalidate some of them
+    pub fn cache_mut(&mut self) -> &mut ConstLru<K, V, CAP, I, C> {
+        &mut self.cache
+    }
+}
+
+impl<F: FnMut(&K) -> V, K, V, const CAP: usize, I: PrimInt + Unsigned, C: Comparator<K>>
+    Memoized<F, K, V, CAP, I, C>
+{
+    /// Returns the cached result of `f(&k)`, calling `f` and caching its result if it's not cached.
+    ///
+    /// The result is moved to the most-recently-used slot.
+    /// If `CAP == 0`, the result of `f` is returned by value instead.
+    pub fn call(&mut self, k: K) -> MaybeStored<'_, V> {
+        let f = &mut self.f;
+        self.cache.try_entry(k).or_insert_with_key(|k| f(k))
+    }
+}
@@ -0,0 +1,701 @@
+use core::borrow::Borrow;
+use core::cell::UnsafeCell;
+use core::cmp::Ordering;
+use core::hint::spin_loop;
+use core::mem::{align_of, size_of, MaybeUninit};
+use core::ptr::{self, addr_of_mut};
+use core::sync::atomic::{fence, AtomicBool, AtomicU8, AtomicUsize, Ordering as AtomicOrdering};
+use num_traits::{PrimInt, Unsigned};
+
+use crate::bs_index::ring_slot;
+use crate::iters::iter_key_order::IterIndexed;
+use crate::lock::RawLock;
+use crate::lru_list::LruList;
+use crate::{CapacityError, Comparator, ConstLru, InsertReplaced, NaturalOrder};
+
+/// A [`ConstLru`] of [`NoUninit`] keys and values whose readers don't take any locks.
+///
+/// Readers don't search the `ConstLru` itself: its methods write its arrays with plain stores,
+/// so a reader racing with them would be a data race even if it retried afterwards.
+/// Instead, readers search a copy of its sorted index, keys and values with the same layout,
+/// which writes update with atomic stores.
+/// Writers are serialized by a lock and bump a sequence number before and after updating the copy.
+/// Readers copy what they need out of the copy and retry if the sequence number changed in the meantime,
+/// so reads never block writers and only spin while the copy is being updated,
+/// not while the write itself runs. This suits read-heavy caches shared between many threads.
+///
+/// The copy costs another `CAP * (size_of::<K>() + size_of::<V>() + size_of::<I>())` bytes on top of the `ConstLru`.
+/// Since it has the same layout, a write only stores what changed:
+/// [`Self::insert`] stores the inserted entry and the index slots that the `ConstLru` shifted,
+/// [`Self::remove`] and [`Self::clear`] only the shifted index slots.
+/// Finding those still compares every published index slot, so writes are `O(len)` loads of `I`.
+/// [`Self::with`] can't tell what `f` changed, so it compares every published entry too.
+/// Applying recorded reads only reorders the LRU list, which readers don't see, so it doesn't touch the copy.
+/// Keys, values and indices are copied with atomics in word- or byte-sized chunks, hence the [`NoUninit`] bounds.
+///
+/// Readers can't move entries to the most-recently-used slot themselves,
+/// so [`Self::get`] records the key in a buffer of `BUF` keys instead, which the next writer drains before writing.
+/// If the buffer is full or another reader is recording at the same time, the record is dropped,
+/// so LRU order is only approximate under heavy read load.
+///
+/// Writers are locked out with a `std::sync::Mutex` under the `std` feature, or a spin lock otherwise.
+///
+/// Generics:
+/// - `K`. Type of key. `C` is used for lookup and to address entries.
+/// - `V`. Type of value.
+/// - `CAP`. Capacity of the cache.
+/// - `I`. Type of the index used. Must be an unsigned primitive type with bitwidth <= `usize`'s bitwidth.
+/// - `C`. [`Comparator`] that orders the keys. Defaults to [`NaturalOrder`], which uses `K`'s `Ord` impl.
+/// - `BUF`. Number of reads that can be recorded between writes. Defaults to 32.
+///
+/// Example:
+///
+/// ```
+/// use const_lru::SeqLockConstLru;
+/// use std::thread;
+///
+/// let c: SeqLockConstLru<u32, u64, 64, u8> = SeqLockConstLru::new();
+/// c.insert(1, 2);
+/// thread::scope(|s| {
+///     s.spawn(|| c.insert(3, 4));
+///     s.spawn(|| assert_eq!(c.get(&1), Some(2)));
+/// });
+/// assert_eq!(c.get_untouched(&3), Some(4));
+/// ```
+pub struct SeqLockConstLru<
+    K,
+    V,
+    const CAP: usize,
+    I: PrimInt + Unsigned = usize,
+    C = NaturalOrder,
+    const BUF: usize = 32,
+> {
+    /// odd while the published copy is being updated
+    seq: AtomicUsize,
+
+    write_lock: RawLock,
+
+    /// only accessed while `write_lock` is held
+    const_lru: UnsafeCell<ConstLru<K, V, CAP, I, C>>,
+
+    /// only written to while `write_lock` is held and `seq` is odd
+    published: Published<K, V, CAP, I>,
+
+    recency: RecencyBuffer<K, BUF>,
+}
+
+/// Types without any uninitialized bytes, e.g. padding, so that they can be copied chunk by chunk.
+///
+/// [`SeqLockConstLru`] copies its keys and values with atomic loads and stores
+/// so that readers can race with writers, which is only sound if every byte is initialized.
+///
+/// # Safety
+/// Every byte of every value of the type must be initialized.
+pub unsafe trait NoUninit: Copy {}
+
+macro_rules! impl_no_uninit {
+    ($($t:ty),*) => {
+        $(unsafe impl NoUninit for $t {})*
+    };
+}
+
+impl_no_uninit!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64
+);
+
+unsafe impl<T: NoUninit, const N: usize> NoUninit for [T; N] {}
+
+/// Copy of the `ConstLru`'s sorted index, keys and values for readers to search,
+/// laid out the same way so that a write only stores what it changed
+struct Published<K, V, const CAP: usize, I> {
+    /// `base`, `gap` and `gap_len` of the sorted index, see [`ring_slot`]
+    base: AtomicUsize,
+    gap: AtomicUsize,
+    gap_len: AtomicUsize,
+
+    /// only accessed with [`atomic_store`] and [`atomic_load`]
+    ///
+    /// the sorted index's slots, disregard if in the gap
+    slots: [UnsafeCell<MaybeUninit<I>>; CAP],
+
+    /// only accessed with [`atomic_store`] and [`atomic_load`]
+    ///
+    /// at the same indices as in the `ConstLru`, valid if the index is in `slots`.
+    /// Zeroed at init, so that writers can compare them with the `ConstLru`'s before storing.
+    keys: [UnsafeCell<MaybeUninit<K>>; CAP],
+
+    /// only accessed with [`atomic_store`] and [`atomic_load`]
+    ///
+    /// same as `keys`
+    values: [UnsafeCell<MaybeUninit<V>>; CAP],
+}
+
+/// What a write changed in the `ConstLru`, so that only that is stored to the published copy
+enum Changed<I> {
+    /// the sorted index and the entry at index `I`
+    Entry(I),
+
+    /// only the sorted index
+    Index,
+
+    /// possibly anything
+    Unknown,
+}
+
+/// Returns `true` if `T` is copied in `usize` chunks instead of bytes
+// `is_multiple_of()` needs Rust 1.87
+#[allow(clippy::manual_is_multiple_of)]
+const fn is_word_copied<T>() -> bool {
+    align_of::<T>() >= align_of::<usize>() && size_of::<T>() % size_of::<usize>() == 0
+}
+
+/// Copies `src` to `dst` with relaxed atomic stores
+///
+/// Requirements:
+/// - `dst` is valid for writes
+/// - `dst` is only ever accessed with `atomic_store` and `atomic_load`, so that all accesses are the same size
+unsafe fn atomic_store<T: NoUninit>(dst: *mut MaybeUninit<T>, src: &T) {
+    let src: *const T = src;
+    if is_word_copied::<T>() {
+        let (src, dst) = (src.cast::<usize>(), dst.cast::<usize>());
+        for i in 0..size_of::<T>() / size_of::<usize>() {
+            AtomicUsize::from_ptr(dst.add(i)).store(src.add(i).read(), AtomicOrdering::Relaxed);
+        }
+    } else {
+        let (src, dst) = (src.cast::<u8>(), dst.cast::<u8>());
+        for i in 0..size_of::<T>() {
+            AtomicU8::from_ptr(dst.add(i)).store(src.add(i).read(), AtomicOrdering::Relaxed);
+        }
+    }
+}
+
+/// Copies `src` to `dst` with [`atomic_store`] unless `dst` already has the same bytes
+///
+/// Requirements:
+/// - same as [`atomic_store`] and [`atomic_load`]
+/// - no other thread stores to `dst` concurrently
+unsafe fn atomic_store_if_changed<T: NoUninit>(dst: *mut MaybeUninit<T>, src: &T) {
+    let old = atomic_load(dst);
+    // safety: NoUninit, and not torn since only this thread stores to `dst`
+    let (old, new) = (
+        core::slice::from_raw_parts(old.as_ptr().cast::<u8>(), size_of::<T>()),
+        core::slice::from_raw_parts((src as *const T).cast::<u8>(), size_of::<T>()),
+    );
+    if old != new {
+        atomic_store(dst, src);
+    }
+}
+
+/// Copies `src` out with relaxed atomic loads.
+///
+/// The result may be torn by concurrent [`atomic_store`]s, so validate it before `assume_init()`.
+///
+/// Requirements:
+/// - `src` is initialized
+/// - `src` is only ever accessed with `atomic_store` and `atomic_load`, so that all accesses are the same size
+unsafe fn atomic_load<T: NoUninit>(src: *mut MaybeUninit<T>) -> MaybeUninit<T> {
+    let mut res = MaybeUninit::<T>::uninit();
+    let dst = res.as_mut_ptr();
+    if is_word_copied::<T>() {
+        let (src, dst) = (src.cast::<usize>(), dst.cast::<usize>());
+        for i in 0..size_of::<T>() / size_of::<usize>() {
+            dst.add(i)
+                .write(AtomicUsize::from_ptr(src.add(i)).load(AtomicOrdering::Relaxed));
+        }
+    } else {
+        let (src, dst) = (src.cast::<u8>(), dst.cast::<u8>());
+        for i in 0..size_of::<T>() {
+            dst.add(i)
+                .write(AtomicU8::from_ptr(src.add(i)).load(AtomicOrdering::Relaxed));
+        }
+    }
+    res
+}
+
+/// Keys read since the last write, in the order they were read
+struct RecencyBuffer<K, const BUF: usize> {
+    /// held while pushing or draining, readers drop their record instead of waiting for it
+    is_locked: AtomicBool,
+
+    /// only accessed while `is_locked`
+    ///
+    /// keys[..len] are valid
+    len: UnsafeCell<usize>,
+
+    /// only accessed while `is_locked`
+    keys: UnsafeCell<[MaybeUninit<K>; BUF]>,
+}
+
+impl<K: Copy, const BUF: usize> RecencyBuffer<K, BUF> {
+    fn try_lock(&self) -> bool {
+        self.is_locked
+            .compare_exchange(
+                false,
+                true,
+                AtomicOrdering::Acquire,
+                AtomicOrdering::Relaxed,
+            )
+            .is_ok()
+    }
+
+    fn unlock(&self) {
+        self.is_locked.store(false, AtomicOrdering::Release);
+    }
+
+    /// Records `k` unless the buffer is full or locked
+    fn try_push(&self, k: K) {
+        if !self.try_lock() {
+            return;
+        }
+        // safety: is_locked is held
+        unsafe {
+            let len = &mut *self.len.get();
+            if *len < BUF {
+                let keys = &mut *self.keys.get();
+                keys[*len].write(k);
+                *len += 1;
+            }
+        }
+        self.unlock();
+    }
+
+    /// Calls `f` with each recorded key in the order they were recorded, then empties the buffer
+    fn drain(&self, mut f: impl FnMut(K)) {
+        while !self.try_lock() {
+            spin_loop();
+        }
+        // safety: is_locked is held
+        unsafe {
+            let len = &mut *self.len.get();
+            let keys = &*self.keys.get();
+            for k in keys[..*len].iter() {
+                f(k.assume_init());
+            }
+            *len = 0;
+        }
+        self.unlock();
+    }
+}
+
+/// Makes `seq` odd for as long as it's alive, even if the write panics
+struct SeqWriteGuard<'a>(&'a AtomicUsize);
+
+impl<'a> SeqWriteGuard<'a> {
+    /// Requirements:
+    /// - the write lock is held
+    fn begin(seq: &'a AtomicUsize) -> Self {
+        let s = seq.load(AtomicOrdering::Relaxed);
+        seq.store(s.wrapping_add(1), AtomicOrdering::Relaxed);
+        // writes after this can't be reordered before the increment
+        fence(AtomicOrdering::Release);
+        Self(seq)
+    }
+}
+
+impl Drop for SeqWriteGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_add(1, AtomicOrdering::Release);
+    }
+}
+
+/// Publishes what the write changed on drop, so that it's published even if the write panics
+struct PublishOnDrop<
+    'a,
+    K: NoUninit,
+    V: NoUninit,
+    const CAP: usize,
+    I: PrimInt + Unsigned + NoUninit,
+    C: Comparator<K>,
+    const BUF: usize,
+> {
+    cache: &'a SeqLockConstLru<K, V, CAP, I, C, BUF>,
+
+    /// [`Changed::Unknown`] until the write returns, in case it panics
+    changed: Changed<I>,
+}
+
+impl<
+        K: NoUninit,
+        V: NoUninit,
+        const CAP: usize,
+        I: PrimInt + Unsigned + NoUninit,
+        C: Comparator<K>,
+        const BUF: usize,
+    > Drop for PublishOnDrop<'_, K, V, CAP, I, C, BUF>
+{
+    fn drop(&mut self) {
+        // safety: only created while the write lock is held,
+        // and dropped after the write's reference to the `ConstLru` is gone
+        unsafe { self.cache.publish(&self.changed) }
+    }
+}
+
+// safety: readers only ever copy out of the published copy with atomics,
+// and writers have exclusive access to the `ConstLru` while holding the write lock
+unsafe impl<K, V, const CAP: usize, I: PrimInt + Unsigned, C, const BUF: usize> Sync
+    for SeqLockConstLru<K, V, CAP, I, C, BUF>
+where
+    ConstLru<K, V, CAP, I, C>: Send,
+{
+}
+
+impl<
+        K: NoUninit,
+        V: NoUninit,
+        const CAP: usize,
+        I: PrimInt + Unsigned + NoUninit,
+        C,
+        const BUF: usize,
+    > SeqLockConstLru<K, V, CAP, I, C, BUF>
+{
+    /// Creates a new empty `SeqLockConstLru` on the stack
+    ///
+    /// Panics and might overflow the stack like [`ConstLru::new`].
+    /// Use [`Self::init_at_alloc`] to initialize larger variants at preallocated memory
+    pub fn new() -> Self {
+        let mut res: MaybeUninit<Self> = MaybeUninit::uninit();
+        unsafe {
+            Self::init_at_alloc(res.as_mut_ptr());
+            res.assume_init()
+        }
+    }
+
+    /// Initializes the SeqLockConstLru at a region of allocated memory
+    ///
+    /// # Safety
+    /// `ptr` must point to uninitialized memory, since init()
+    /// overwrites the data at `ptr`
+    ///
+    /// Panics like [`ConstLru::init_at_alloc`].
+    ///
+    /// Use [`Self::init_in`] for a safe, non-panicking alternative.
+    pub unsafe fn init_at_alloc(ptr: *mut Self) {
+        if let Err(e) = LruList::<K, V, CAP, I>::check_cap() {
+            panic!("{e}");
+        }
+        Self::init_at_alloc_unchecked(ptr);
+    }
+
+    /// Initializes the SeqLockConstLru in `slot` and returns a mutable reference to it.
+    ///
+    /// Errors and overwrites `slot` like [`ConstLru::init_in`].
+    pub fn init_in(slot: &mut MaybeUninit<Self>) -> Result<&mut Self, CapacityError> {
+        LruList::<K, V, CAP, I>::check_cap()?;
+        unsafe {
+            Self::init_at_alloc_unchecked(slot.as_mut_ptr());
+            Ok(slot.assume_init_mut())
+        }
+    }
+
+    /// Requirements:
+    /// - `LruList::check_cap()` is `Ok`
+    /// - `ptr` points to allocated memory that can be overwritten
+    unsafe fn init_at_alloc_unchecked(ptr: *mut Self) {
+        addr_of_mut!((*ptr).seq).write(AtomicUsize::new(0));
+        addr_of_mut!((*ptr).write_lock).write(RawLock::new());
+        ConstLru::init_at_alloc_unchecked(UnsafeCell::raw_get(addr_of_mut!((*ptr).const_lru)));
+
+        // mirrors BsIndex::init_at_alloc
+        let published = addr_of_mut!((*ptr).published);
+        addr_of_mut!((*published).base).write(AtomicUsize::new(0));
+        addr_of_mut!((*published).gap).write(AtomicUsize::new(0));
+        addr_of_mut!((*published).gap_len).write(AtomicUsize::new(CAP));
+        let cap = I::from(CAP).unwrap();
+        for i in 0..CAP {
+            addr_of_mut!((*published).slots[i]).write(UnsafeCell::new(MaybeUninit::new(cap)));
+        }
+        ptr::write_bytes(addr_of_mut!((*published).keys), 0, 1);
+        ptr::write_bytes(addr_of_mut!((*published).values), 0, 1);
+
+        // recency.keys can be left uninitialized
+        addr_of_mut!((*ptr).recency.is_locked).write(AtomicBool::new(false));
+        addr_of_mut!((*ptr).recency.len).write(UnsafeCell::new(0));
+    }
+
+    /// Spins until no write is in progress, then returns the sequence number to validate reads against
+    fn begin_read(&self) -> usize {
+        loop {
+            let seq = self.seq.load(AtomicOrdering::Acquire);
+            if seq & 1 == 0 {
+                return seq;
+            }
+            spin_loop();
+        }
+    }
+
+    /// Returns `true` if no write started since `seq` was returned by [`Self::begin_read`],
+    /// so everything read in between is consistent
+    fn validate_read(&self, seq: usize) -> bool {
+        // reads before this can't be reordered after the load
+        fence(AtomicOrdering::Acquire);
+        self.seq.load(AtomicOrdering::Relaxed) == seq
+    }
+
+    /// Runs `read` with the sequence number to validate against
+    /// until it returns `Some`, i.e. until it didn't overlap with a write
+    fn read<T>(&self, mut read: impl FnMut(usize) -> Option<T>) -> T {
+        loop {
+            let seq = self.begin_read();
+            if let Some(res) = read(seq) {
+                return res;
+            }
+        }
+    }
+
+    /// Returns the number of elements in the cache.
+    pub fn len(&self) -> I {
+        let gap_len = self.published.gap_len.load(AtomicOrdering::Relaxed);
+        I::from(CAP - gap_len).unwrap()
+    }
+
+    /// Returns `true` if the cache contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == I::zero()
+    }
+
+    /// Returns the maximum number of elements the cache can hold
+    pub fn cap(&self) -> I {
+        I::from(CAP).unwrap()
+    }
+}
+
+impl<
+        K: NoUninit,
+        V: NoUninit,
+        const CAP: usize,
+        I: PrimInt + Unsigned + NoUninit,
+        C: Comparator<K>,
+        const BUF: usize,
+    > SeqLockConstLru<K, V, CAP, I, C, BUF>
+{
+    /// Binary searches the published copy for `k` without taking any locks.
+    ///
+    /// Returns `None` if a write overlapped with the search.
+    /// Everything loaded is validated against `seq` before it's used,
+    /// since an overlapping write may have left it torn.
+    fn try_search<Q: ?Sized>(&self, seq: usize, k: &Q) -> Option<Option<(K, V)>>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        let published = &self.published;
+        let base = published.base.load(AtomicOrdering::Relaxed);
+        let gap = published.gap.load(AtomicOrdering::Relaxed);
+        let gap_len = published.gap_len.load(AtomicOrdering::Relaxed);
+        if !self.validate_read(seq) {
+            return None;
+        }
+        let mut lo = 0;
+        let mut hi = CAP - gap_len;
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            let slot = ring_slot::<CAP>(base, gap, gap_len, mid);
+            // safety: slots are initialized at init
+            let index = unsafe { atomic_load(published.slots[slot].get()) };
+            if !self.validate_read(seq) {
+                return None;
+            }
+            // safety: validated, so not torn and the index of a valid entry
+            let index = unsafe { index.assume_init() }.to_usize().unwrap();
+            // safety: keys are zeroed at init
+            let key = unsafe { atomic_load(published.keys[index].get()) };
+            if !self.validate_read(seq) {
+                return None;
+            }
+            // safety: validated, so not torn
+            let key = unsafe { key.assume_init() };
+            match C::cmp(key.borrow(), k) {
+                Ordering::Less => lo = mid + 1,
+                Ordering::Greater => hi = mid,
+                Ordering::Equal => {
+                    // safety: values are zeroed at init
+                    let value = unsafe { atomic_load(published.values[index].get()) };
+                    if !self.validate_read(seq) {
+                        return None;
+                    }
+                    // safety: validated, so not torn
+                    return Some(Some((key, unsafe { value.assume_init() })));
+                }
+            }
+        }
+        Some(None)
+    }
+
+    /// Stores what `changed` in the `ConstLru` to the published copy for readers to search
+    ///
+    /// Requirements:
+    /// - the write lock is held and there are no other references to the `ConstLru`
+    unsafe fn publish(&self, changed: &Changed<I>) {
+        let _seq = SeqWriteGuard::begin(&self.seq);
+        let const_lru = &*self.const_lru.get();
+        let published = &self.published;
+        // safety: indices < CAP, and writers are serialized by the write lock
+        match changed {
+            Changed::Entry(index) => {
+                let i = index.to_usize().unwrap();
+                atomic_store(
+                    published.keys[i].get(),
+                    const_lru.list.get_key_by_index(*index),
+                );
+                atomic_store(
+                    published.values[i].get(),
+                    const_lru.list.get_by_index(*index),
+                );
+            }
+            Changed::Index => (),
+            Changed::Unknown => {
+                for (index, k, v) in IterIndexed::new(const_lru) {
+                    let i = index.to_usize().unwrap();
+                    atomic_store_if_changed(published.keys[i].get(), k);
+                    atomic_store_if_changed(published.values[i].get(), v);
+                }
+            }
+        }
+        let (base, gap, gap_len, slots) = const_lru.bs_index.raw_parts();
+        published.base.store(base, AtomicOrdering::Relaxed);
+        published.gap.store(gap, AtomicOrdering::Relaxed);
+        published.gap_len.store(gap_len, AtomicOrdering::Relaxed);
+        for bs_i in 0..CAP - gap_len {
+            let slot = ring_slot::<CAP>(base, gap, gap_len, bs_i);
+            atomic_store_if_changed(published.slots[slot].get(), &slots[slot]);
+        }
+    }
+
+    /// Returns a copy of the value corresponding to the key without taking any locks.
+    ///
+    /// The entry is moved to the most-recently-used slot by the next write,
+    /// unless the record is dropped because too many reads happened since the last write.
+    ///
+    /// To not update to most-recently-used, use [`Self::get_untouched`]
+    pub fn get<Q: ?Sized>(&self, k: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        let (key, value) = self.read(|seq| self.try_search(seq, k))?;
+        self.recency.try_push(key);
+        Some(value)
+    }
+
+    /// Returns a copy of the value corresponding to the key without taking any locks
+    /// or changing the LRU order.
+    pub fn get_untouched<Q: ?Sized>(&self, k: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        self.read(|seq| self.try_search(seq, k))
+            .map(|(_, value)| value)
+    }
+
+    /// Locks out other writers and calls `f` with the `ConstLru`.
+    ///
+    /// Reads recorded since the last write are applied first.
+    /// Readers keep seeing the entries from before the write until `f` returns or panics
+    /// and the changes are published, so other writers wait for `f` but readers don't.
+    /// Since it can't tell what `f` changed, publishing compares every entry with its published copy.
+    ///
+    /// Use this to perform multiple operations on the cache atomically, or to use the [`Entry`](crate::Entry) API.
+    ///
+    /// Writing to the cache from within `f` deadlocks. Reading from it sees the entries from before the write.
+    pub fn with<T>(&self, f: impl FnOnce(&mut ConstLru<K, V, CAP, I, C>) -> T) -> T {
+        self.write(|c| (f(c), Changed::Unknown))
+    }
+
+    /// Like [`Self::with`], but `f` also returns what it changed so that only that is published
+    fn write<T>(&self, f: impl FnOnce(&mut ConstLru<K, V, CAP, I, C>) -> (T, Changed<I>)) -> T {
+        let _lock = self.write_lock.lock();
+        // safety: write lock is held, and readers only access the published copy
+        let const_lru = unsafe { &mut *self.const_lru.get() };
+        self.apply_reads(const_lru);
+        // dropped before `_lock`, so the changes are published while the write lock is still held
+        let mut publish = PublishOnDrop {
+            cache: self,
+            changed: Changed::Unknown,
+        };
+        let (res, changed) = f(const_lru);
+        publish.changed = changed;
+        res
+    }
+
+    /// Moves the keys read since the last write to the most-recently-used slot
+    ///
+    /// Only the LRU order changes, which readers don't see, so nothing needs to be published.
+    fn apply_reads(&self, const_lru: &mut ConstLru<K, V, CAP, I, C>) {
+        self.recency.drain(|k| {
+            const_lru.get(&k);
+        });
+    }
+
+    /// Applies the reads recorded since the last write to the LRU order
+    /// so that more reads can be recorded.
+    ///
+    /// Doesn't touch the published copy, so readers never retry because of it.
+    pub fn flush_reads(&self) {
+        let _lock = self.write_lock.lock();
+        // safety: write lock is held, and readers only access the published copy
+        self.apply_reads(unsafe { &mut *self.const_lru.get() });
+    }
+
+    /// Inserts a key-value pair into the cache.
+    ///
+    /// See [`ConstLru::insert`].
+    pub fn insert(&self, k: K, v: V) -> Option<InsertReplaced<K, V>> {
+        self.write(|c| {
+            let res = c.insert(k, v);
+            // only misses if CAP == 0
+            let changed = match c.get_index_of(&k) {
+                Ok((index, _)) => Changed::Entry(index),
+                Err(_) => Changed::Index,
+            };
+            (res, changed)
+        })
+    }
+
+    /// Removes a key from the cache, returning the value at the key if the key was previously in the cache.
+    pub fn remove<Q: ?Sized>(&self, k: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        self.write(|c| (c.remove(k), Changed::Index))
+    }
+
+    /// Clears the cache, removing all key-value pairs.
+    pub fn clear(&self) {
+        self.write(|c| {
+            c.clear();
+            ((), Changed::Index)
+        })
+    }
+}
+
+impl<
+        K: NoUninit,
+        V: NoUninit,
+        const CAP: usize,
+        I: PrimInt + Unsigned + NoUninit,
+        C,
+        const BUF: usize,
+    > Default for SeqLockConstLru<K, V, CAP, I, C, BUF>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,363 @@
+//! [`ShardedConstLru`], an array of [`ConstLru`]s behind per-shard locks that can be shared between threads.
+
+use core::borrow::Borrow;
+use core::cell::UnsafeCell;
+use core::mem::MaybeUninit;
+use core::ptr::addr_of_mut;
+use num_traits::{PrimInt, Unsigned};
+
+mod router;
+
+pub use router::*;
+
+use crate::lock::RawLock;
+use crate::lru_list::LruList;
+use crate::{CapacityError, Comparator, ConstLru, InsertReplaced, NaturalOrder};
+
+/// Fixed number of [`ConstLru`]s, each behind its own lock, with keys routed to them by a [`ShardRouter`].
+///
+/// Wrapping a single `ConstLru` in a lock makes every thread contend for it.
+/// Splitting it into `SHARDS` independent caches means threads only contend when they access keys in the same shard.
+/// In exchange, each shard evicts its own least-recently-used entry when it's full,
+/// so the cache as a whole only approximates LRU order.
+///
+/// Each shard is locked with a `std::sync::Mutex` under the `std` feature, or a spin lock otherwise.
+/// All methods take `&self`, so the cache can be shared in a `static` or an `Arc`.
+///
+/// Generics:
+/// - `K`. Type of key. `C` is used for lookup and to address entries, `R` to find their shard.
+/// - `V`. Type of value.
+/// - `SHARDS`. Number of shards. Must not be 0.
+/// - `CAP_PER_SHARD`. Capacity of each shard. The cache can hold up to `SHARDS * CAP_PER_SHARD` entries.
+/// - `I`. Type of the index used by each shard. Must be an unsigned primitive type with bitwidth <= `usize`'s bitwidth.
+/// - `C`. [`Comparator`] that orders the keys. Defaults to [`NaturalOrder`], which uses `K`'s `Ord` impl.
+/// - `R`. [`ShardRouter`] that decides which shard each key belongs to. Defaults to [`HashRouter`].
+///
+/// Example:
+///
+/// ```
+/// use const_lru::ShardedConstLru;
+/// use std::thread;
+///
+/// let c: ShardedConstLru<u32, u32, 4, 16, u8> = ShardedConstLru::new();
+/// thread::scope(|s| {
+///     for t in 0..4 {
+///         let c = &c;
+///         s.spawn(move || {
+///             for k in (t * 10)..(t * 10 + 10) {
+///                 c.insert(k, k * 2);
+///             }
+///         });
+///     }
+/// });
+/// assert_eq!(c.len(), 40);
+/// assert_eq!(c.get_cloned(&21), Some(42));
+/// ```
+pub struct ShardedConstLru<
+    K,
+    V,
+    const SHARDS: usize,
+    const CAP_PER_SHARD: usize,
+    I: PrimInt + Unsigned = usize,
+    C = NaturalOrder,
+    R = HashRouter,
+> {
+    shards: [Shard<K, V, CAP_PER_SHARD, I, C>; SHARDS],
+
+    router: R,
+}
+
+/// The lock is stored beside the `ConstLru` instead of around it
+/// so that the `ConstLru` can be initialized in place
+struct Shard<K, V, const CAP: usize, I: PrimInt + Unsigned, C> {
+    lock: RawLock,
+
+    const_lru: UnsafeCell<ConstLru<K, V, CAP, I, C>>,
+}
+
+// safety: `const_lru` is only accessed while `lock` is held, same as `Mutex<T>: Sync` if `T: Send`
+unsafe impl<K, V, const SHARDS: usize, const CAP_PER_SHARD: usize, I: PrimInt + Unsigned, C, R: Sync>
+    Sync for ShardedConstLru<K, V, SHARDS, CAP_PER_SHARD, I, C, R>
+where
+    ConstLru<K, V, CAP_PER_SHARD, I, C>: Send,
+{
+}
+
+impl<
+        K,
+        V,
+        const SHARDS: usize,
+        const CAP_PER_SHARD: usize,
+        I: PrimInt + Unsigned,
+        C,
+        R: Default,
+    > ShardedConstLru<K, V, SHARDS, CAP_PER_SHARD, I, C, R>
+{
+    /// Creates a new empty `ShardedConstLru` on the stack
+    ///
+    /// Panics like [`ConstLru::new`] with `CAP_PER_SHARD` as `CAP`,
+    /// and might overflow the stack for large `SHARDS * CAP_PER_SHARD`.
+    /// Use [`Self::init_at_alloc`] to initialize larger variants at preallocated memory
+    pub fn new() -> Self {
+        Self::with_router(R::default())
+    }
+
+    /// Initializes the ShardedConstLru at a region of allocated memory
+    ///
+    /// # Safety
+    /// `ptr` must point to uninitialized memory, since init()
+    /// overwrites the data at `ptr`
+    ///
+    /// Panics like [`ConstLru::init_at_alloc`] with `CAP_PER_SHARD` as `CAP`.
+    ///
+    /// Use [`Self::init_in`] for a safe, non-panicking alternative.
+    pub unsafe fn init_at_alloc(ptr: *mut Self) {
+        Self::init_at_alloc_with_router(ptr, R::default());
+    }
+
+    /// Initializes the ShardedConstLru in `slot` and returns a mutable reference to it.
+    ///
+    /// Errors and overwrites `slot` like [`ConstLru::init_in`] with `CAP_PER_SHARD` as `CAP`.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use const_lru::ShardedConstLru;
+    /// use core::mem::MaybeUninit;
+    ///
+    /// let mut slot: Box<MaybeUninit<ShardedConstLru<u32, u16, 8, 1_000, u16>>> = Box::new_uninit();
+    /// let c = ShardedConstLru::init_in(&mut slot).unwrap();
+    /// assert!(c.insert(1, 2).is_none());
+    /// ```
+    pub fn init_in(slot: &mut MaybeUninit<Self>) -> Result<&mut Self, CapacityError> {
+        Self::init_in_with_router(slot, R::default())
+    }
+}
+
+impl<K, V, const SHARDS: usize, const CAP_PER_SHARD: usize, I: PrimInt + Unsigned, C, R>
+    ShardedConstLru<K, V, SHARDS, CAP_PER_SHARD, I, C, R>
+{
+    const SHARDS_NOT_ZERO: () = assert!(SHARDS > 0, "SHARDS == 0");
+
+    /// Creates a new empty `ShardedConstLru` on the stack that uses `router` to route keys to shards
+    ///
+    /// Panics like [`ConstLru::new`] with `CAP_PER_SHARD` as `CAP`,
+    /// and might overflow the stack for large `SHARDS * CAP_PER_SHARD`.
+    /// Use [`Self::init_at_alloc_with_router`] to initialize larger variants at preallocated memory
+    pub fn with_router(router: R) -> Self {
+        let mut res: MaybeUninit<Self> = MaybeUninit::uninit();
+        unsafe {
+            Self::init_at_alloc_with_router(res.as_mut_ptr(), router);
+            res.assume_init()
+        }
+    }
+
+    /// Initializes the ShardedConstLru at a region of allocated memory with the given `router`
+    ///
+    /// # Safety
+    /// `ptr` must point to uninitialized memory, since init()
+    /// overwrites the data at `ptr`
+    ///
+    /// Panics like [`ConstLru::init_at_alloc`] with `CAP_PER_SHARD` as `CAP`.
+    pub unsafe fn init_at_alloc_with_router(ptr: *mut Self, router: R) {
+        if let Err(e) = LruList::<K, V, CAP_PER_SHARD, I>::check_cap() {
+            panic!("{e}");
+        }
+        Self::init_at_alloc_unchecked(ptr, router);
+    }
+
+    /// Same as [`Self::init_in`], but with the given `router`
+    pub fn init_in_with_router(
+        slot: &mut MaybeUninit<Self>,
+        router: R,
+    ) -> Result<&mut Self, CapacityError> {
+        LruList::<K, V, CAP_PER_SHARD, I>::check_cap()?;
+        unsafe {
+            Self::init_at_alloc_unchecked(slot.as_mut_ptr(), router);
+            Ok(slot.assume_init_mut())
+        }
+    }
+
+    /// Requirements:
+    /// - `LruList::check_cap()` is `Ok`
+    /// - `ptr` points to allocated memory that can be overwritten
+    unsafe fn init_at_alloc_unchecked(ptr: *mut Self, router: R) {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::SHARDS_NOT_ZERO;
+
+        for i in 0..SHARDS {
+            let shard = addr_of_mut!((*ptr).shards[i]);
+            addr_of_mut!((*shard).lock).write(RawLock::new());
+            ConstLru::init_at_alloc_unchecked(UnsafeCell::raw_get(addr_of_mut!(
+                (*shard).const_lru
+            )));
+        }
+
+        addr_of_mut!((*ptr).router).write(router);
+    }
+
+    /// Returns a reference to the cache's [`ShardRouter`]
+    pub fn router(&self) -> &R {
+        &self.router
+    }
+
+    /// Returns the index of the shard `k` belongs to
+    pub fn shard_index<Q: ?Sized>(&self, k: &Q) -> usize
+    where
+        R: ShardRouter<Q>,
+    {
+        self.router.route(k, SHARDS)
+    }
+
+    /// Locks the shard `k` belongs to and calls `f` with it.
+    ///
+    /// Use this to perform multiple operations on the shard atomically, or to use the [`Entry`](crate::Entry) API.
+    ///
+    /// Other accesses to the same shard wait until `f` returns,
+    /// so accessing the same shard from `f`, e.g. through [`Self::get_cloned`], deadlocks.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use const_lru::ShardedConstLru;
+    ///
+    /// let c: ShardedConstLru<u32, u32, 4, 16, u8> = ShardedConstLru::new();
+    /// c.with_shard(&1, |shard| *shard.entry(1).or_insert(0) += 1);
+    /// c.with_shard(&1, |shard| *shard.entry(1).or_insert(0) += 1);
+    /// assert_eq!(c.get_cloned(&1), Some(2));
+    /// ```
+    pub fn with_shard<Q: ?Sized, T>(
+        &self,
+        k: &Q,
+        f: impl FnOnce(&mut ConstLru<K, V, CAP_PER_SHARD, I, C>) -> T,
+    ) -> T
+    where
+        R: ShardRouter<Q>,
+    {
+        self.with_shard_at(self.shard_index(k), f)
+    }
+
+    /// Locks the shard at index `shard` and calls `f` with it.
+    ///
+    /// See [`Self::with_shard`].
+    ///
+    /// panics if `shard >= SHARDS`
+    pub fn with_shard_at<T>(
+        &self,
+        shard: usize,
+        f: impl FnOnce(&mut ConstLru<K, V, CAP_PER_SHARD, I, C>) -> T,
+    ) -> T {
+        let shard = &self.shards[shard];
+        let _guard = shard.lock.lock();
+        // safety: lock is held until _guard is dropped at the end of this fn
+        f(unsafe { &mut *shard.const_lru.get() })
+    }
+
+    /// Returns a mutable reference to the shard at index `shard`.
+    ///
+    /// No locking is needed since `&mut self` guarantees exclusive access.
+    ///
+    /// panics if `shard >= SHARDS`
+    pub fn shard_mut(&mut self, shard: usize) -> &mut ConstLru<K, V, CAP_PER_SHARD, I, C> {
+        self.shards[shard].const_lru.get_mut()
+    }
+
+    /// Clears all shards, locking each in turn
+    pub fn clear(&self) {
+        for i in 0..SHARDS {
+            self.with_shard_at(i, |c| c.clear());
+        }
+    }
+
+    /// Returns the maximum number of elements the cache can hold, `SHARDS * CAP_PER_SHARD`
+    pub fn cap(&self) -> usize {
+        SHARDS * CAP_PER_SHARD
+    }
+
+    /// Returns `true` if all shards are empty, locking each in turn.
+    ///
+    /// Other threads may modify shards that were already checked before this returns.
+    pub fn is_empty(&self) -> bool {
+        (0..SHARDS).all(|i| self.with_shard_at(i, |c| c.is_empty()))
+    }
+
+    /// Returns the total number of elements in all shards, locking each in turn.
+    ///
+    /// Other threads may modify shards that were already counted before this returns.
+    pub fn len(&self) -> usize {
+        (0..SHARDS)
+            .map(|i| self.with_shard_at(i, |c| c.len().to_usize().unwrap()))
+            .sum()
+    }
+}
+
+impl<
+        K,
+        V,
+        const SHARDS: usize,
+        const CAP_PER_SHARD: usize,
+        I: PrimInt + Unsigned,
+        C: Comparator<K>,
+        R: ShardRouter<K>,
+    > ShardedConstLru<K, V, SHARDS, CAP_PER_SHARD, I, C, R>
+{
+    /// Inserts a key-value pair into the shard `k` belongs to. The entry is moved to the shard's most-recently-used slot
+    ///
+    /// Same as [`ConstLru::insert`], but if the shard is full,
+    /// the shard's least-recently used key-value pair is evicted and returned in a [`InsertReplaced::LruEvicted`].
+    pub fn insert(&self, k: K, v: V) -> Option<InsertReplaced<K, V>> {
+        self.with_shard_at(self.shard_index(&k), |c| c.insert(k, v))
+    }
+
+    /// Removes a key from the cache, returning the value at the key if the key was previously in the cache.
+    pub fn remove<Q: ?Sized>(&self, k: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+        R: ShardRouter<Q>,
+    {
+        self.with_shard(k, |c| c.remove(k))
+    }
+
+    /// Returns a clone of the value corresponding to the key and moves entry to the shard's most-recently-used slot.
+    ///
+    /// The value is cloned so that the shard's lock can be released before returning.
+    /// Use [`Self::with_shard`] to access the value by reference instead.
+    pub fn get_cloned<Q: ?Sized>(&self, k: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+        R: ShardRouter<Q>,
+        V: Clone,
+    {
+        self.with_shard(k, |c| c.get(k).cloned())
+    }
+
+    /// Returns a clone of the value corresponding to the key without changing the LRU order.
+    ///
+    /// See [`Self::get_cloned`].
+    pub fn get_untouched_cloned<Q: ?Sized>(&self, k: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+        R: ShardRouter<Q>,
+        V: Clone,
+    {
+        self.with_shard(k, |c| c.get_untouched(k).cloned())
+    }
+}
+
+impl<
+        K,
+        V,
+        const SHARDS: usize,
+        const CAP_PER_SHARD: usize,
+        I: PrimInt + Unsigned,
+        C,
+        R: Default,
+    > Default for ShardedConstLru<K, V, SHARDS, CAP_PER_SHARD, I, C, R>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,102 @@
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::hash::{BuildHasher, Hash};
+use core::marker::PhantomData;
+
+use crate::hash::DefaultHashBuilder;
+use crate::{Comparator, NaturalOrder};
+
+/// Decides which shard of a [`ShardedConstLru`](crate::ShardedConstLru) a key belongs to.
+///
+/// To look up entries by a borrowed form `Q` of the key type `K`, also implement `ShardRouter<Q>`.
+/// It must route `Q`s to the same shard that `ShardRouter<K>` routes the corresponding `K`s to.
+pub trait ShardRouter<K: ?Sized> {
+    /// Returns the index of the shard `k` belongs to out of `shards` shards.
+    ///
+    /// Must be `< shards` and the same every time for equal keys.
+    fn route(&self, k: &K, shards: usize) -> usize;
+}
+
+/// The default [`ShardRouter`], routes keys by their hash.
+///
+/// Spreads keys evenly over the shards, at the cost of having to hash every key.
+///
+/// Generics:
+/// - `S`. `BuildHasher` used to hash the keys. Defaults to [`DefaultHashBuilder`].
+#[derive(Debug, Default, Copy, Clone)]
+pub struct HashRouter<S = DefaultHashBuilder> {
+    hash_builder: S,
+}
+
+impl<S> HashRouter<S> {
+    /// Creates a new `HashRouter` that uses `hash_builder` to hash the keys
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self { hash_builder }
+    }
+
+    /// Returns a reference to the router's `BuildHasher`
+    pub fn hasher(&self) -> &S {
+        &self.hash_builder
+    }
+}
+
+impl<K: Hash + ?Sized, S: BuildHasher> ShardRouter<K> for HashRouter<S> {
+    fn route(&self, k: &K, shards: usize) -> usize {
+        let hash = self.hash_builder.hash_one(k);
+        // maps the hash to [0, shards) using its high bits, which are better mixed than its low bits
+        ((u128::from(hash) * shards as u128) >> 64) as usize
+    }
+}
+
+/// A [`ShardRouter`] that routes keys by which of the ranges between `bounds` they fall into.
+///
+/// Keys `< bounds[0]` go to shard 0, keys in `[bounds[i - 1], bounds[i])` go to shard `i`
+/// and keys `>= bounds[BOUNDS - 1]` go to shard `BOUNDS`, so `BOUNDS` should be `SHARDS - 1`.
+/// Keys that would go to a shard `>= SHARDS` go to the last shard instead.
+///
+/// Keeps nearby keys in the same shard, but the bounds must be chosen to spread the keys evenly.
+///
+/// Generics:
+/// - `K`. Type of the bounds.
+/// - `BOUNDS`. Number of bounds.
+/// - `C`. [`Comparator`] that orders the keys. Defaults to [`NaturalOrder`], which uses `K`'s `Ord` impl.
+#[derive(Debug, Copy, Clone)]
+pub struct RangeRouter<K, const BOUNDS: usize, C = NaturalOrder> {
+    /// sorted in ascending order
+    bounds: [K; BOUNDS],
+
+    comparator: PhantomData<C>,
+}
+
+impl<K, const BOUNDS: usize, C: Comparator<K>> RangeRouter<K, BOUNDS, C> {
+    /// Creates a new `RangeRouter` with the given `bounds`
+    ///
+    /// panics if `bounds` is not sorted in strictly ascending order
+    pub fn new(bounds: [K; BOUNDS]) -> Self {
+        for w in bounds.windows(2) {
+            if C::cmp(&w[0], &w[1]) != Ordering::Less {
+                panic!("bounds not sorted in strictly ascending order");
+            }
+        }
+        Self {
+            bounds,
+            comparator: PhantomData,
+        }
+    }
+
+    /// Returns the router's bounds
+    pub fn bounds(&self) -> &[K; BOUNDS] {
+        &self.bounds
+    }
+}
+
+impl<K: Borrow<Q>, Q: ?Sized, const BOUNDS: usize, C: Comparator<Q>> ShardRouter<Q>
+    for RangeRouter<K, BOUNDS, C>
+{
+    fn route(&self, k: &Q, shards: usize) -> usize {
+        let shard = self
+            .bounds
+            .partition_point(|b| C::cmp(b.borrow(), k) != Ordering::Greater);
+        shard.min(shards.saturating_sub(1))
+    }
+}
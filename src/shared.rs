@@ -0,0 +1,176 @@
+use core::borrow::Borrow;
+use core::cell::RefCell;
+use core::mem::MaybeUninit;
+use critical_section::{CriticalSection, Mutex};
+use num_traits::{PrimInt, Unsigned};
+
+use crate::lru_list::LruList;
+use crate::{Comparator, ConstLru, InsertReplaced, NaturalOrder};
+
+/// A [`ConstLru`] that can be shared between thread mode and interrupt handlers,
+/// accessed only from within a [`critical_section`].
+///
+/// Requires the `critical-section` feature.
+/// The final binary must provide a `critical-section` implementation, e.g. from the HAL or the `cortex-m` crate.
+///
+/// [`Self::new`] is a `const fn` so the cache can be placed in a `static`.
+/// The `ConstLru` is only initialized on first access: placed in a `static`,
+/// the `ConstLru` is initialized in place and never moved through the stack.
+/// Bound to a local or returned by value, the whole `SharedConstLru` is still moved like any other value.
+///
+/// Example:
+///
+/// ```
+/// use const_lru::SharedConstLru;
+///
+/// static CACHE: SharedConstLru<u8, u16, 16, u8> = SharedConstLru::new();
+///
+/// // e.g. in an interrupt handler
+/// fn on_sample(channel: u8, sample: u16) {
+///     CACHE.insert(channel, sample);
+/// }
+///
+/// on_sample(3, 1023);
+/// assert_eq!(CACHE.get_cloned(&3), Some(1023));
+/// CACHE.with(|c| *c.get_mut(&3).unwrap() += 1);
+/// assert_eq!(CACHE.get_cloned(&3), Some(1024));
+/// ```
+pub struct SharedConstLru<K, V, const CAP: usize, I: PrimInt + Unsigned = usize, C = NaturalOrder> {
+    inner: Mutex<RefCell<LazyConstLru<K, V, CAP, I, C>>>,
+}
+
+/// `ConstLru::new` is not `const`, so the `ConstLru` is initialized on first access instead
+struct LazyConstLru<K, V, const CAP: usize, I: PrimInt + Unsigned, C> {
+    is_init: bool,
+
+    /// only valid if `is_init`
+    const_lru: MaybeUninit<ConstLru<K, V, CAP, I, C>>,
+}
+
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned, C> LazyConstLru<K, V, CAP, I, C> {
+    fn get_mut(&mut self) -> &mut ConstLru<K, V, CAP, I, C> {
+        if !self.is_init {
+            // safety: CAP was checked in SharedConstLru::new()
+            unsafe { ConstLru::init_at_alloc_unchecked(self.const_lru.as_mut_ptr()) };
+            self.is_init = true;
+        }
+        unsafe { self.const_lru.assume_init_mut() }
+    }
+}
+
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned, C> Drop for LazyConstLru<K, V, CAP, I, C> {
+    fn drop(&mut self) {
+        if self.is_init {
+            unsafe { self.const_lru.assume_init_drop() };
+        }
+    }
+}
+
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned, C> SharedConstLru<K, V, CAP, I, C> {
+    /// Creates a new empty `SharedConstLru`
+    ///
+    /// Invalid `CAP` and `I` combinations are rejected at compile time:
+    ///
+    /// ```compile_fail
+    /// use const_lru::SharedConstLru;
+    ///
+    /// static CACHE: SharedConstLru<u8, u8, 256, u8> = SharedConstLru::new();
+    /// ```
+    pub const fn new() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = LruList::<K, V, CAP, I>::CAP_FITS_IN_I;
+        Self {
+            inner: Mutex::new(RefCell::new(LazyConstLru {
+                is_init: false,
+                const_lru: MaybeUninit::uninit(),
+            })),
+        }
+    }
+
+    /// Enters a critical section and calls `f` with the `ConstLru`.
+    ///
+    /// Use this to perform multiple operations on the cache atomically, or to use the [`Entry`](crate::Entry) API.
+    ///
+    /// Interrupts are disabled until `f` returns, so keep it short.
+    ///
+    /// panics if the cache is accessed again from within `f`
+    pub fn with<T>(&self, f: impl FnOnce(&mut ConstLru<K, V, CAP, I, C>) -> T) -> T {
+        critical_section::with(|cs| self.with_cs(cs, f))
+    }
+
+    /// Same as [`Self::with`], but for when already in a critical section
+    pub fn with_cs<T>(
+        &self,
+        cs: CriticalSection<'_>,
+        f: impl FnOnce(&mut ConstLru<K, V, CAP, I, C>) -> T,
+    ) -> T {
+        f(self.inner.borrow_ref_mut(cs).get_mut())
+    }
+
+    /// Clears the cache, removing all key-value pairs.
+    pub fn clear(&self) {
+        self.with(|c| c.clear())
+    }
+
+    /// Returns `true` if the cache contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.with(|c| c.is_empty())
+    }
+
+    /// Returns the number of elements in the cache.
+    pub fn len(&self) -> I {
+        self.with(|c| c.len())
+    }
+}
+
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned, C: Comparator<K>>
+    SharedConstLru<K, V, CAP, I, C>
+{
+    /// Inserts a key-value pair into the cache in a critical section.
+    ///
+    /// See [`ConstLru::insert`].
+    pub fn insert(&self, k: K, v: V) -> Option<InsertReplaced<K, V>> {
+        self.with(|c| c.insert(k, v))
+    }
+
+    /// Removes a key from the cache in a critical section,
+    /// returning the value at the key if the key was previously in the cache.
+    pub fn remove<Q: ?Sized>(&self, k: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        self.with(|c| c.remove(k))
+    }
+
+    /// Returns a clone of the value corresponding to the key and moves entry to most-recently-used slot.
+    ///
+    /// The value is cloned so that the critical section can end before returning.
+    /// Use [`Self::with`] to access the value by reference instead.
+    pub fn get_cloned<Q: ?Sized>(&self, k: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+        V: Clone,
+    {
+        self.with(|c| c.get(k).cloned())
+    }
+
+    /// Returns a clone of the value corresponding to the key without changing the LRU order.
+    ///
+    /// See [`Self::get_cloned`].
+    pub fn get_untouched_cloned<Q: ?Sized>(&self, k: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+        V: Clone,
+    {
+        self.with(|c| c.get_untouched(k).cloned())
+    }
+}
+
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned, C> Default for SharedConstLru<K, V, CAP, I, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
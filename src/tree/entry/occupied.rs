@@ -0,0 +1,82 @@
+use num_traits::{PrimInt, Unsigned};
+
+use crate::tree::ConstLruTree;
+use crate::NaturalOrder;
+
+/// A view into an occupied entry in a ConstLruTree. It is part of the Entry enum.
+#[derive(Debug)]
+pub struct OccupiedEntry<'a, K, V, const CAP: usize, I: PrimInt + Unsigned, C = NaturalOrder> {
+    const_lru: &'a mut ConstLruTree<K, V, CAP, I, C>,
+    key: K,
+    index: I,
+}
+
+impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned, C> OccupiedEntry<'a, K, V, CAP, I, C> {
+    pub(crate) fn new(const_lru: &'a mut ConstLruTree<K, V, CAP, I, C>, key: K, index: I) -> Self {
+        Self {
+            const_lru,
+            key,
+            index,
+        }
+    }
+
+    /// Gets a reference to the value in the entry and moves entry to most recently used slot
+    ///
+    /// To not update to most-recently-used, use [`Self::get_untouched`]
+    pub fn get(&mut self) -> &V {
+        self.const_lru.list.move_to_head(self.index);
+        self.const_lru.list.get_by_index(self.index)
+    }
+
+    /// Gets a reference to the value in the entry
+    pub fn get_untouched(&self) -> &V {
+        self.const_lru.list.get_by_index(self.index)
+    }
+
+    /// Gets a mutable reference to the value in the entry and moves entry to most recently used slot
+    ///
+    /// To not update to most-recently-used, use [`Self::get_mut_untouched`]
+    pub fn get_mut(&mut self) -> &mut V {
+        self.const_lru.list.move_to_head(self.index);
+        self.const_lru.list.get_mut_by_index(self.index)
+    }
+
+    /// Gets a mutable reference to the value in the entry
+    pub fn get_mut_untouched(&mut self) -> &mut V {
+        self.const_lru.list.get_mut_by_index(self.index)
+    }
+
+    /// Sets the value of the entry, and returns the entry's old value.
+    pub fn insert(&mut self, v: V) -> V {
+        self.const_lru.list.replace_value(self.index, v)
+    }
+
+    /// Converts the `OccupiedEntry` into a mutable reference to the value in the entry with a lifetime bound to the ConstLruTree itself.
+    /// Also moves the entry to most recently used slot
+    ///
+    /// To not update to most-recently-used, use [`Self::into_mut_untouched`]
+    pub fn into_mut(self) -> &'a mut V {
+        self.const_lru.list.move_to_head(self.index);
+        self.const_lru.list.get_mut_by_index(self.index)
+    }
+
+    /// Converts the `OccupiedEntry` into a mutable reference to the value in the entry with a lifetime bound to the ConstLruTree itself.
+    pub fn into_mut_untouched(self) -> &'a mut V {
+        self.const_lru.list.get_mut_by_index(self.index)
+    }
+
+    /// Gets a reference to the key in the entry.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Takes the value out of the entry, and returns it.
+    pub fn remove(self) -> V {
+        self.const_lru.remove_by_index(self.index).1
+    }
+
+    /// Take the ownership of the key and value from the ConstLruTree
+    pub fn remove_entry(self) -> (K, V) {
+        self.const_lru.remove_by_index(self.index)
+    }
+}
@@ -0,0 +1,54 @@
+use num_traits::{PrimInt, Unsigned};
+
+use crate::tree::{rb_tree::InsertPos, ConstLruTree};
+use crate::{Comparator, NaturalOrder};
+
+/// A view into an vacant entry in a ConstLruTree. It is part of the Entry enum.
+#[derive(Debug)]
+pub struct VacantEntry<'a, K, V, const CAP: usize, I: PrimInt + Unsigned, C = NaturalOrder> {
+    const_lru: &'a mut ConstLruTree<K, V, CAP, I, C>,
+    key: K,
+    pos: InsertPos,
+}
+
+impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned, C> VacantEntry<'a, K, V, CAP, I, C> {
+    pub(crate) fn new(
+        const_lru: &'a mut ConstLruTree<K, V, CAP, I, C>,
+        key: K,
+        pos: InsertPos,
+    ) -> Self {
+        Self {
+            const_lru,
+            key,
+            pos,
+        }
+    }
+
+    /// Take ownership of the key.
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Gets a reference to the key that would be used when inserting a value through the `VacantEntry`.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+}
+
+impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned, C: Comparator<K>>
+    VacantEntry<'a, K, V, CAP, I, C>
+{
+    /// Sets the value of the entry with the `VacantEntry`’s key, and returns:
+    /// - a mutable reference to the new value
+    /// - LRU evicted entry, if ConstLruTree is full
+    pub fn insert(self, v: V) -> (&'a mut V, Option<(K, V)>) {
+        let (i, opt) = if self.const_lru.is_full() {
+            let (i, (old_k, old_v)) = self.const_lru.insert_evict_lru(self.pos, self.key, v);
+            (i, Some((old_k, old_v)))
+        } else {
+            let i = self.const_lru.insert_alloc_new(self.pos, self.key, v);
+            (i, None)
+        };
+        (self.const_lru.list.get_mut_by_index(i), opt)
+    }
+}
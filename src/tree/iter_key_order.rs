@@ -0,0 +1,166 @@
+use num_traits::{PrimInt, Unsigned};
+
+use crate::lru_list::LruList;
+
+use super::rb_tree::RbTree;
+
+/// In-order cursors over a [`RbTree`] that can advance from both ends
+struct InOrderCursors<'a, I: PrimInt + Unsigned, const CAP: usize> {
+    tree: &'a RbTree<I, CAP>,
+    from_smallest: usize,
+    from_largest: usize,
+    /// 0 means ended
+    remaining: usize,
+}
+
+impl<'a, I: PrimInt + Unsigned, const CAP: usize> InOrderCursors<'a, I, CAP> {
+    fn new(tree: &'a RbTree<I, CAP>, len: I) -> Self {
+        Self {
+            tree,
+            from_smallest: tree.first(),
+            from_largest: tree.last(),
+            remaining: len.to_usize().unwrap(),
+        }
+    }
+
+    /// consume then increment
+    fn next(&mut self) -> Option<usize> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let i = self.from_smallest;
+        self.remaining -= 1;
+        if self.remaining > 0 {
+            self.from_smallest = self.tree.next(i);
+        }
+        Some(i)
+    }
+
+    /// consume then decrement
+    fn next_back(&mut self) -> Option<usize> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let i = self.from_largest;
+        self.remaining -= 1;
+        if self.remaining > 0 {
+            self.from_largest = self.tree.prev(i);
+        }
+        Some(i)
+    }
+}
+
+/// Iterates through the keys and values of the `ConstLruTree` in the keys' sorted order
+///
+/// Does not change the LRU order of the elements.
+pub struct IterKeyOrder<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> {
+    cursors: InOrderCursors<'a, I, CAP>,
+    list: &'a LruList<K, V, CAP, I>,
+}
+
+impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> IterKeyOrder<'a, K, V, CAP, I> {
+    pub(crate) fn new(list: &'a LruList<K, V, CAP, I>, tree: &'a RbTree<I, CAP>) -> Self {
+        Self {
+            cursors: InOrderCursors::new(tree, list.len()),
+            list,
+        }
+    }
+
+    /// Returns the index of the next element as well
+    ///
+    /// Used to impl clone()
+    pub(crate) fn next_indexed(&mut self) -> Option<(I, &'a K, &'a V)> {
+        self.cursors.next().map(|i| {
+            let (k, v) = self.get_entry(i);
+            (I::from(i).unwrap(), k, v)
+        })
+    }
+
+    fn get_entry(&self, i: usize) -> (&'a K, &'a V) {
+        let key = unsafe { self.list.keys[i].assume_init_ref() };
+        let val = unsafe { self.list.values[i].assume_init_ref() };
+        (key, val)
+    }
+}
+
+impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> Iterator
+    for IterKeyOrder<'a, K, V, CAP, I>
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cursors.next().map(|i| self.get_entry(i))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.cursors.remaining, Some(self.cursors.remaining))
+    }
+}
+
+// TODO: look into https://doc.rust-lang.org/std/iter/trait.TrustedLen.html when it lands in stable
+impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> ExactSizeIterator
+    for IterKeyOrder<'a, K, V, CAP, I>
+{
+}
+
+impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> DoubleEndedIterator
+    for IterKeyOrder<'a, K, V, CAP, I>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.cursors.next_back().map(|i| self.get_entry(i))
+    }
+}
+
+/// Iterates through the keys and mutable values of the `ConstLruTree` in the keys' sorted order
+///
+/// Does not change the LRU order of the elements.
+pub struct IterKeyOrderMut<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> {
+    cursors: InOrderCursors<'a, I, CAP>,
+    list: &'a mut LruList<K, V, CAP, I>,
+}
+
+impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> IterKeyOrderMut<'a, K, V, CAP, I> {
+    pub(crate) fn new(list: &'a mut LruList<K, V, CAP, I>, tree: &'a RbTree<I, CAP>) -> Self {
+        Self {
+            cursors: InOrderCursors::new(tree, list.len()),
+            list,
+        }
+    }
+
+    fn get_entry_mut(&mut self, i: usize) -> (&'a K, &'a mut V) {
+        // each index is only yielded once, so no aliasing &mut
+        let key_ptr = unsafe { self.list.keys[i].assume_init_ref() } as *const _;
+        let key: &'a K = unsafe { &*key_ptr };
+        let val_ptr = unsafe { self.list.values[i].assume_init_mut() } as *mut _;
+        let val: &'a mut V = unsafe { &mut *val_ptr };
+        (key, val)
+    }
+}
+
+impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> Iterator
+    for IterKeyOrderMut<'a, K, V, CAP, I>
+{
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cursors.next().map(|i| self.get_entry_mut(i))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.cursors.remaining, Some(self.cursors.remaining))
+    }
+}
+
+// TODO: look into https://doc.rust-lang.org/std/iter/trait.TrustedLen.html when it lands in stable
+impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> ExactSizeIterator
+    for IterKeyOrderMut<'a, K, V, CAP, I>
+{
+}
+
+impl<'a, K, V, const CAP: usize, I: PrimInt + Unsigned> DoubleEndedIterator
+    for IterKeyOrderMut<'a, K, V, CAP, I>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.cursors.next_back().map(|i| self.get_entry_mut(i))
+    }
+}
@@ -0,0 +1,445 @@
+//! [`ConstLruTree`], a variant of [`ConstLru`](crate::ConstLru) indexed by a red-black tree instead of a sorted index.
+
+use core::borrow::Borrow;
+use core::fmt::Debug;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+use core::ptr::addr_of_mut;
+use num_traits::{PrimInt, Unsigned};
+
+mod entry;
+mod iter_key_order;
+mod rb_tree;
+
+pub use entry::*;
+pub use iter_key_order::*;
+
+use crate::lru_list::LruList;
+use crate::{CapacityError, Comparator, InsertReplaced, IntoIter, Iter, IterMut, NaturalOrder};
+use rb_tree::{InsertPos, RbTree};
+
+/// Constant capacity key-addressed LRU cache with `O(log N)` lookups, inserts and removes.
+///
+/// Same as [`ConstLru`](crate::ConstLru), but keys are indexed by a red-black tree instead of a sorted array.
+/// This avoids the `O(N)` shifting of the sorted index on inserts and removes
+/// at the cost of slower lookups and 3x the sorted index's memory usage plus a `bool` per entry.
+///
+/// Generics:
+/// - `K`. Type of key. `C` is used for lookup and to address entries.
+/// - `V`. Type of value.
+/// - `CAP`. Capacity of the cache.
+/// - `I`. Type of the index used. Must be an unsigned primitive type with bitwidth <= `usize`'s bitwidth.
+/// - `C`. [`Comparator`] that orders the keys. Defaults to [`NaturalOrder`], which uses `K`'s `Ord` impl.
+pub struct ConstLruTree<K, V, const CAP: usize, I: PrimInt + Unsigned = usize, C = NaturalOrder> {
+    list: LruList<K, V, CAP, I>,
+
+    tree: RbTree<I, CAP>,
+
+    comparator: PhantomData<C>,
+}
+
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned, C> ConstLruTree<K, V, CAP, I, C> {
+    /// Creates a new empty `ConstLruTree` on the stack
+    ///
+    /// Panics and might overflow the stack like [`ConstLru::new`](crate::ConstLru::new).
+    /// Use [`Self::init_at_alloc`] to initialize larger variants at preallocated memory
+    pub fn new() -> Self {
+        let mut res: MaybeUninit<Self> = MaybeUninit::uninit();
+        unsafe {
+            Self::init_at_alloc(res.as_mut_ptr());
+            res.assume_init()
+        }
+    }
+
+    /// Initializes the ConstLruTree at a region of allocated memory
+    ///
+    /// # Safety
+    /// `ptr` must point to uninitialized memory, since init()
+    /// overwrites the data at `ptr`
+    ///
+    /// Panics like [`ConstLru::init_at_alloc`](crate::ConstLru::init_at_alloc).
+    ///
+    /// Use [`Self::init_in`] for a safe, non-panicking alternative.
+    pub unsafe fn init_at_alloc(ptr: *mut Self) {
+        if let Err(e) = LruList::<K, V, CAP, I>::check_cap() {
+            panic!("{e}");
+        }
+        Self::init_at_alloc_unchecked(ptr);
+    }
+
+    /// Initializes the ConstLruTree in `slot` and returns a mutable reference to it.
+    ///
+    /// Errors and overwrites `slot` like [`ConstLru::init_in`](crate::ConstLru::init_in).
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use const_lru::ConstLruTree;
+    /// use core::mem::MaybeUninit;
+    ///
+    /// let mut slot: Box<MaybeUninit<ConstLruTree<u32, u16, 1_000, u16>>> = Box::new_uninit();
+    /// let c = ConstLruTree::init_in(&mut slot).unwrap();
+    /// assert!(c.insert(1, 2).is_none());
+    /// ```
+    pub fn init_in(slot: &mut MaybeUninit<Self>) -> Result<&mut Self, CapacityError> {
+        LruList::<K, V, CAP, I>::check_cap()?;
+        unsafe {
+            Self::init_at_alloc_unchecked(slot.as_mut_ptr());
+            Ok(slot.assume_init_mut())
+        }
+    }
+
+    /// Requirements:
+    /// - `LruList::check_cap()` is `Ok`
+    /// - `ptr` points to allocated memory that can be overwritten
+    unsafe fn init_at_alloc_unchecked(ptr: *mut Self) {
+        LruList::init_at_alloc(addr_of_mut!((*ptr).list));
+        RbTree::init_at_alloc(addr_of_mut!((*ptr).tree));
+        addr_of_mut!((*ptr).comparator).write(PhantomData);
+    }
+
+    /// Creates an iterator that iterates through the keys and values of the `ConstLruTree` from most-recently-used to least-recently-used
+    ///
+    /// Does not change the LRU order of the elements.
+    ///
+    /// Double-ended: reversing iterates from least-recently-used to most-recently-used
+    pub fn iter(&self) -> Iter<'_, K, V, CAP, I> {
+        Iter::from_list(&self.list)
+    }
+
+    /// Creates an iterator that iterates through the keys and mutable values of the `ConstLruTree` from most-recently-used to least-recently-used
+    ///
+    /// Does not change the LRU order of the elements, even if mutated.
+    ///
+    /// Double-ended: reversing iterates from least-recently-used to most-recently-used
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V, CAP, I> {
+        IterMut::from_list(&mut self.list)
+    }
+
+    /// Creates an iterator that iterates through the keys and values of the `ConstLruTree` in the order of its keys
+    ///
+    /// Does not change the LRU order of the elements.
+    ///
+    /// Double-ended: reversing iterates from descending order of its keys
+    pub fn iter_key_order(&self) -> IterKeyOrder<'_, K, V, CAP, I> {
+        IterKeyOrder::new(&self.list, &self.tree)
+    }
+
+    /// Creates an iterator that iterates through the keys and mutable values of the `ConstLruTree` in the order of its keys
+    ///
+    /// Does not change the LRU order of the elements, even if mutated.
+    ///
+    /// Double-ended: reversing iterates from descending order of its keys
+    pub fn iter_key_order_mut(&mut self) -> IterKeyOrderMut<'_, K, V, CAP, I> {
+        IterKeyOrderMut::new(&mut self.list, &self.tree)
+    }
+
+    /// Clears the `ConstLruTree`, removing all key-value pairs.
+    pub fn clear(&mut self) {
+        self.list.drop_cleanup();
+        let ptr_to_self: *mut Self = self;
+        unsafe { Self::init_at_alloc_unchecked(ptr_to_self) }
+    }
+
+    /// Returns the maximum number of elements this `ConstLruTree` can hold
+    pub fn cap(&self) -> I {
+        self.list.cap()
+    }
+
+    /// Returns `true` if the `ConstLruTree` contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == I::zero()
+    }
+
+    /// Returns `true` if the `ConstLruTree` has reached max capacity.
+    pub fn is_full(&self) -> bool {
+        self.len() == self.cap()
+    }
+
+    /// Returns the number of elements in the `ConstLruTree`.
+    pub fn len(&self) -> I {
+        self.list.len()
+    }
+
+    /// Returns `true` if both `ConstLruTree`s contain the same key-value pairs, regardless of their LRU order.
+    ///
+    /// This is what `==` compares. The entries are compared in key order,
+    /// so `C` must be consistent with `K`'s `PartialEq`.
+    pub fn eq_as_map<const CAP2: usize, I2: PrimInt + Unsigned>(
+        &self,
+        other: &ConstLruTree<K, V, CAP2, I2, C>,
+    ) -> bool
+    where
+        K: PartialEq,
+        V: PartialEq,
+    {
+        self.len().to_usize() == other.len().to_usize()
+            && self.iter_key_order().eq(other.iter_key_order())
+    }
+
+    /// Returns `true` if both `ConstLruTree`s contain the same key-value pairs in the same LRU order.
+    pub fn eq_with_order<const CAP2: usize, I2: PrimInt + Unsigned>(
+        &self,
+        other: &ConstLruTree<K, V, CAP2, I2, C>,
+    ) -> bool
+    where
+        K: PartialEq,
+        V: PartialEq,
+    {
+        self.len().to_usize() == other.len().to_usize() && self.iter().eq(other.iter())
+    }
+
+    // Assumes N > 0 and self is not full
+    // Moves newly inserted elem to MRU position
+    // Returns index entry was inserted into
+    fn insert_alloc_new(&mut self, pos: InsertPos, k: K, v: V) -> I {
+        let free_index = self.list.alloc_new(k, v);
+        self.tree.insert(free_index, pos);
+        free_index
+    }
+
+    // Assumes index is of a valid node. Should be result of Ok returned by self.get_index_of()
+    fn remove_by_index(&mut self, index: I) -> (K, V) {
+        self.tree.remove(index);
+        self.list.free_by_index(index)
+    }
+}
+
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned, C: Comparator<K>>
+    ConstLruTree<K, V, CAP, I, C>
+{
+    /// Inserts a key-value pair into the map. The entry is moved to the most-recently-used slot
+    ///
+    /// If `CAP == 0`, `None` is returned.
+    ///
+    /// If the map did not have this key present and is not full, `None` is returned.
+    ///
+    /// If the map did have this key present, the value is updated, and the old value is returned in a [`InsertReplaced::OldValue`].
+    /// The key is not updated, though; this matters for types that can be `==` without being identical.
+    ///
+    /// If the map is full, the least-recently used key-value pair is evicted and returned in a [`InsertReplaced::LruEvicted`].
+    pub fn insert(&mut self, k: K, v: V) -> Option<InsertReplaced<K, V>> {
+        if CAP == 0 {
+            return None;
+        }
+        let pos = match self.get_index_of(&k) {
+            Ok(existing_index) => {
+                return Some(InsertReplaced::OldValue(
+                    self.list.replace_value(existing_index, v),
+                ))
+            }
+            Err(pos) => pos,
+        };
+        if self.is_full() {
+            let (_, (old_k, old_v)) = self.insert_evict_lru(pos, k, v);
+            Some(InsertReplaced::LruEvicted(old_k, old_v))
+        } else {
+            self.insert_alloc_new(pos, k, v);
+            None
+        }
+    }
+
+    /// Assumes N > 0, self is full and `k` is not in self
+    /// Moves newly inserted elem to MRU position
+    ///
+    /// Returns (index entry was inserted into, evicted entry)
+    ///
+    /// Requirements:
+    /// - `pos` was returned by [`Self::get_index_of`] for `k` and the tree has not been modified since
+    fn insert_evict_lru(&mut self, pos: InsertPos, k: K, v: V) -> (I, (K, V)) {
+        // N > 0, tail must be valid
+        let tail = self.list.tail;
+        // removing the tail rebalances the tree and invalidates `pos`, so link `k` in after its predecessor instead.
+        // This doesn't search with the comparator again, which could panic with the tail already unlinked
+        let mut pred = self.tree.pred_of_pos(pos);
+        if pred == tail.to_usize().unwrap() {
+            pred = self.tree.prev(pred);
+        }
+        self.tree.remove(tail);
+        let (i, evicted) = self.list.replace_tail(k, v);
+        let pos = self.tree.pos_after(pred);
+        self.tree.insert(i, pos);
+        (i, evicted)
+    }
+
+    /// Removes a key from the `ConstLruTree`, returning the value at the key if the key was previously in the `ConstLruTree`.
+    pub fn remove<Q: ?Sized>(&mut self, k: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        let index = self.get_index_of(k).ok()?;
+        Some(self.remove_by_index(index).1)
+    }
+
+    /// Returns a reference to the value corresponding to the key and moves entry to most-recently-used slot.
+    ///
+    /// To not update to most-recently-used, use [`Self::get_untouched`]
+    pub fn get<Q: ?Sized>(&mut self, k: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        let index = self.get_index_of(k).ok()?;
+        self.list.move_to_head(index);
+        Some(self.list.get_by_index(index))
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key and moves entry to most-recently-used slot.
+    ///
+    /// To not update to most-recently-used, use [`Self::get_mut_untouched`]
+    pub fn get_mut<Q: ?Sized>(&mut self, k: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        let index = self.get_index_of(k).ok()?;
+        self.list.move_to_head(index);
+        Some(self.list.get_mut_by_index(index))
+    }
+
+    /// Ok(kv_i)
+    ///
+    /// Err(tree position to insert k at)
+    fn get_index_of<Q: ?Sized>(&self, k: &Q) -> Result<I, InsertPos>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        self.tree.find(|probe_index| {
+            let probe = self.list.get_key_by_index(probe_index);
+            <C as Comparator<Q>>::cmp(probe.borrow(), k)
+        })
+    }
+
+    /// Returns a reference to the value corresponding to the key without updating the entry to most-recently-used slot
+    ///
+    /// To update to most-recently-used, use [`Self::get`]
+    pub fn get_untouched<Q: ?Sized>(&self, k: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        let index = self.get_index_of(k).ok()?;
+        Some(self.list.get_by_index(index))
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key without updating the entry to most-recently-used slot
+    ///
+    /// To update to most-recently-used, use [`Self::get_mut`]
+    pub fn get_mut_untouched<Q: ?Sized>(&mut self, k: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        C: Comparator<Q>,
+    {
+        let index = self.get_index_of(k).ok()?;
+        Some(self.list.get_mut_by_index(index))
+    }
+
+    /// Gets the given key’s corresponding entry in the map for in-place manipulation.
+    ///
+    /// **panics** if CAP == 0
+    pub fn entry(&mut self, k: K) -> Entry<'_, K, V, CAP, I, C> {
+        Entry::new(self, k)
+    }
+}
+
+impl<K: Clone, V: Clone, const CAP: usize, I: PrimInt + Unsigned, C> ConstLruTree<K, V, CAP, I, C> {
+    /// Clones the ConstLruTree to a region of allocated memory
+    ///
+    /// # Safety
+    /// `dst` must point to uninitialized memory, since this
+    /// overwrites the data at `dst`
+    pub unsafe fn clone_to_alloc(&self, dst: *mut Self) {
+        let dst_list = addr_of_mut!((*dst).list);
+        self.list.clone_links_to_alloc(dst_list);
+        self.tree.clone_to_alloc(addr_of_mut!((*dst).tree));
+        addr_of_mut!((*dst).comparator).write(PhantomData);
+
+        let mut iter = self.iter_key_order();
+        while let Some((index, _, _)) = iter.next_indexed() {
+            self.list.clone_entry_to_alloc(dst_list, index);
+        }
+    }
+
+    /// Clones the ConstLruTree into `slot` and returns a mutable reference to the clone.
+    ///
+    /// Safe alternative to [`Self::clone_to_alloc`].
+    ///
+    /// Overwrites `slot` like [`ConstLru::init_in`](crate::ConstLru::init_in).
+    pub fn clone_into_uninit<'a>(&self, slot: &'a mut MaybeUninit<Self>) -> &'a mut Self {
+        unsafe {
+            self.clone_to_alloc(slot.as_mut_ptr());
+            slot.assume_init_mut()
+        }
+    }
+}
+
+/// To clone a large `ConstLruTree` without overflowing the stack, use [`ConstLruTree::clone_into_uninit`]
+impl<K: Clone, V: Clone, const CAP: usize, I: PrimInt + Unsigned, C> Clone
+    for ConstLruTree<K, V, CAP, I, C>
+{
+    fn clone(&self) -> Self {
+        let mut res: MaybeUninit<Self> = MaybeUninit::uninit();
+        unsafe {
+            self.clone_to_alloc(res.as_mut_ptr());
+            res.assume_init()
+        }
+    }
+}
+
+// not derived so that `C` doesn't have to be `Debug`
+/// Formats the entries as a map from most-recently-used to least-recently-used
+impl<K: Debug, V: Debug, const CAP: usize, I: PrimInt + Unsigned, C> Debug
+    for ConstLruTree<K, V, CAP, I, C>
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Map equality that ignores LRU order, see [`ConstLruTree::eq_as_map`].
+/// Use [`ConstLruTree::eq_with_order`] to also compare LRU order.
+impl<K: PartialEq, V: PartialEq, const CAP: usize, I: PrimInt + Unsigned, C> PartialEq
+    for ConstLruTree<K, V, CAP, I, C>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.eq_as_map(other)
+    }
+}
+
+impl<K: Eq, V: Eq, const CAP: usize, I: PrimInt + Unsigned, C> Eq
+    for ConstLruTree<K, V, CAP, I, C>
+{
+}
+
+/// Hashes the length and the entries in key order, consistent with `PartialEq` ignoring LRU order
+impl<K: Hash, V: Hash, const CAP: usize, I: PrimInt + Unsigned, C> Hash
+    for ConstLruTree<K, V, CAP, I, C>
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len().to_usize().unwrap().hash(state);
+        for entry in self.iter_key_order() {
+            entry.hash(state);
+        }
+    }
+}
+
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned, C> Default for ConstLruTree<K, V, CAP, I, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, const CAP: usize, I: PrimInt + Unsigned, C> IntoIterator
+    for ConstLruTree<K, V, CAP, I, C>
+{
+    type Item = <IntoIter<K, V, CAP, I> as Iterator>::Item;
+
+    type IntoIter = IntoIter<K, V, CAP, I>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::from_list(self.list)
+    }
+}
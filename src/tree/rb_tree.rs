@@ -0,0 +1,466 @@
+use core::cmp::Ordering;
+use core::ptr::{self, addr_of_mut};
+
+use num_traits::{PrimInt, Unsigned};
+
+/// Where a node not yet in the tree should be linked in, result of [`RbTree::find`]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct InsertPos {
+    /// CAP if tree is empty
+    parent: usize,
+    is_left: bool,
+}
+
+/// Red-black tree whose nodes are the indices of the entries in the LruList.
+///
+/// Keys are not stored in the tree, comparing a node against a key is left to the cache that owns this.
+/// Nodes are relinked instead of having their keys swapped, so an entry's index never changes.
+///
+/// Links use CAP as nil.
+#[derive(Debug)]
+pub(crate) struct RbTree<I: PrimInt + Unsigned, const CAP: usize> {
+    root: I,
+
+    /// disregard if node not in tree
+    lefts: [I; CAP],
+
+    /// disregard if node not in tree
+    rights: [I; CAP],
+
+    /// disregard if node not in tree
+    parents: [I; CAP],
+
+    /// disregard if node not in tree
+    is_reds: [bool; CAP],
+}
+
+impl<I: PrimInt + Unsigned, const CAP: usize> RbTree<I, CAP> {
+    /// Initializes an empty tree at a region of allocated memory
+    ///
+    /// Requirements:
+    /// - `CAP <= I::MAX <= usize::MAX`
+    /// - `ptr` points to allocated memory that can be overwritten
+    pub(crate) unsafe fn init_at_alloc(ptr: *mut Self) {
+        let nil = I::from(CAP).unwrap();
+        addr_of_mut!((*ptr).root).write(nil);
+        // UB if not initialized
+        for i in 0..CAP {
+            addr_of_mut!((*ptr).lefts[i]).write(nil);
+            addr_of_mut!((*ptr).rights[i]).write(nil);
+            addr_of_mut!((*ptr).parents[i]).write(nil);
+            addr_of_mut!((*ptr).is_reds[i]).write(false);
+        }
+    }
+
+    /// Copies the tree to a region of allocated memory
+    ///
+    /// Requirements:
+    /// - `dst` points to allocated memory that can be overwritten
+    pub(crate) unsafe fn clone_to_alloc(&self, dst: *mut Self) {
+        addr_of_mut!((*dst).root).write(self.root);
+        // raw memmove for the same reason as in `LruList::clone_links_to_alloc`
+        ptr::copy(
+            self.lefts.as_ptr(),
+            addr_of_mut!((*dst).lefts) as *mut I,
+            CAP,
+        );
+        ptr::copy(
+            self.rights.as_ptr(),
+            addr_of_mut!((*dst).rights) as *mut I,
+            CAP,
+        );
+        ptr::copy(
+            self.parents.as_ptr(),
+            addr_of_mut!((*dst).parents) as *mut I,
+            CAP,
+        );
+        ptr::copy(
+            self.is_reds.as_ptr(),
+            addr_of_mut!((*dst).is_reds) as *mut bool,
+            CAP,
+        );
+    }
+
+    /// Searches the tree using `cmp`, which returns the ordering of the probed node relative to the target.
+    ///
+    /// Returns Ok(index of matching node) or Err(position to insert target at)
+    pub(crate) fn find(&self, mut cmp: impl FnMut(I) -> Ordering) -> Result<I, InsertPos> {
+        let mut pos = InsertPos {
+            parent: CAP,
+            is_left: false,
+        };
+        let mut n = self.root();
+        while n != CAP {
+            let node = I::from(n).unwrap();
+            pos.parent = n;
+            n = match cmp(node) {
+                Ordering::Equal => return Ok(node),
+                Ordering::Greater => {
+                    pos.is_left = true;
+                    self.left(n)
+                }
+                Ordering::Less => {
+                    pos.is_left = false;
+                    self.right(n)
+                }
+            };
+        }
+        Err(pos)
+    }
+
+    /// Links `node` in at `pos` and rebalances the tree.
+    ///
+    /// Requirements:
+    /// - `node` is not in the tree
+    /// - `pos` was returned by [`Self::find`] and the tree has not been modified since
+    pub(crate) fn insert(&mut self, node: I, InsertPos { parent, is_left }: InsertPos) {
+        let mut z = node.to_usize().unwrap();
+        self.set_parent(z, parent);
+        self.set_left(z, CAP);
+        self.set_right(z, CAP);
+        self.is_reds[z] = true;
+        if parent == CAP {
+            self.set_root(z);
+        } else if is_left {
+            self.set_left(parent, z);
+        } else {
+            self.set_right(parent, z);
+        }
+
+        while z != self.root() && self.is_red(self.parent(z)) {
+            // parent is red so it can't be root, grandparent must be valid
+            let mut p = self.parent(z);
+            let g = self.parent(p);
+            if p == self.left(g) {
+                let uncle = self.right(g);
+                if self.is_red(uncle) {
+                    self.is_reds[p] = false;
+                    self.is_reds[uncle] = false;
+                    self.is_reds[g] = true;
+                    z = g;
+                } else {
+                    if z == self.right(p) {
+                        z = p;
+                        self.rotate_left(z);
+                        p = self.parent(z);
+                    }
+                    self.is_reds[p] = false;
+                    self.is_reds[g] = true;
+                    self.rotate_right(g);
+                }
+            } else {
+                let uncle = self.left(g);
+                if self.is_red(uncle) {
+                    self.is_reds[p] = false;
+                    self.is_reds[uncle] = false;
+                    self.is_reds[g] = true;
+                    z = g;
+                } else {
+                    if z == self.left(p) {
+                        z = p;
+                        self.rotate_right(z);
+                        p = self.parent(z);
+                    }
+                    self.is_reds[p] = false;
+                    self.is_reds[g] = true;
+                    self.rotate_left(g);
+                }
+            }
+        }
+        let root = self.root();
+        self.is_reds[root] = false;
+    }
+
+    /// Unlinks `node` from the tree and rebalances the tree.
+    ///
+    /// Requirements:
+    /// - `node` is in the tree
+    pub(crate) fn remove(&mut self, node: I) {
+        let z = node.to_usize().unwrap();
+        // x is the node that moves into the removed position, may be nil,
+        // so track its parent separately
+        let x;
+        let x_parent;
+        let removed_red;
+        if self.left(z) == CAP {
+            x = self.right(z);
+            x_parent = self.parent(z);
+            removed_red = self.is_reds[z];
+            self.transplant(z, x);
+        } else if self.right(z) == CAP {
+            x = self.left(z);
+            x_parent = self.parent(z);
+            removed_red = self.is_reds[z];
+            self.transplant(z, x);
+        } else {
+            // replace z with its successor y
+            let y = self.min_from(self.right(z));
+            removed_red = self.is_reds[y];
+            x = self.right(y);
+            if self.parent(y) == z {
+                x_parent = y;
+            } else {
+                x_parent = self.parent(y);
+                self.transplant(y, x);
+                let z_right = self.right(z);
+                self.set_right(y, z_right);
+                self.set_parent(z_right, y);
+            }
+            self.transplant(z, y);
+            let z_left = self.left(z);
+            self.set_left(y, z_left);
+            self.set_parent(z_left, y);
+            self.is_reds[y] = self.is_reds[z];
+        }
+        if !removed_red {
+            self.remove_fixup(x, x_parent);
+        }
+    }
+
+    fn remove_fixup(&mut self, mut x: usize, mut x_parent: usize) {
+        while x != self.root() && !self.is_red(x) {
+            // x is doubly-black so its sibling w must be valid
+            if x == self.left(x_parent) {
+                let mut w = self.right(x_parent);
+                if self.is_red(w) {
+                    self.is_reds[w] = false;
+                    self.is_reds[x_parent] = true;
+                    self.rotate_left(x_parent);
+                    w = self.right(x_parent);
+                }
+                if !self.is_red(self.left(w)) && !self.is_red(self.right(w)) {
+                    self.is_reds[w] = true;
+                    x = x_parent;
+                    x_parent = self.parent(x);
+                } else {
+                    if !self.is_red(self.right(w)) {
+                        let w_left = self.left(w);
+                        self.is_reds[w_left] = false;
+                        self.is_reds[w] = true;
+                        self.rotate_right(w);
+                        w = self.right(x_parent);
+                    }
+                    self.is_reds[w] = self.is_reds[x_parent];
+                    self.is_reds[x_parent] = false;
+                    let w_right = self.right(w);
+                    self.is_reds[w_right] = false;
+                    self.rotate_left(x_parent);
+                    x = self.root();
+                }
+            } else {
+                let mut w = self.left(x_parent);
+                if self.is_red(w) {
+                    self.is_reds[w] = false;
+                    self.is_reds[x_parent] = true;
+                    self.rotate_right(x_parent);
+                    w = self.left(x_parent);
+                }
+                if !self.is_red(self.right(w)) && !self.is_red(self.left(w)) {
+                    self.is_reds[w] = true;
+                    x = x_parent;
+                    x_parent = self.parent(x);
+                } else {
+                    if !self.is_red(self.left(w)) {
+                        let w_right = self.right(w);
+                        self.is_reds[w_right] = false;
+                        self.is_reds[w] = true;
+                        self.rotate_left(w);
+                        w = self.left(x_parent);
+                    }
+                    self.is_reds[w] = self.is_reds[x_parent];
+                    self.is_reds[x_parent] = false;
+                    let w_left = self.left(w);
+                    self.is_reds[w_left] = false;
+                    self.rotate_right(x_parent);
+                    x = self.root();
+                }
+            }
+        }
+        if x != CAP {
+            self.is_reds[x] = false;
+        }
+    }
+
+    /// Returns the in-order predecessor of a node linked in at `pos`, CAP if it would be the first node
+    pub(crate) fn pred_of_pos(&self, InsertPos { parent, is_left }: InsertPos) -> usize {
+        if parent == CAP {
+            CAP
+        } else if is_left {
+            // parent has no left child, so its predecessor is above it
+            self.prev(parent)
+        } else {
+            parent
+        }
+    }
+
+    /// Returns the position to link a node in at so that it comes right after `pred` in order,
+    /// or first if `pred` is CAP
+    ///
+    /// Requirements:
+    /// - `pred` is in the tree or CAP
+    pub(crate) fn pos_after(&self, pred: usize) -> InsertPos {
+        if pred == CAP {
+            // parent is CAP if the tree is empty
+            InsertPos {
+                parent: self.first(),
+                is_left: true,
+            }
+        } else if self.right(pred) == CAP {
+            InsertPos {
+                parent: pred,
+                is_left: false,
+            }
+        } else {
+            InsertPos {
+                parent: self.min_from(self.right(pred)),
+                is_left: true,
+            }
+        }
+    }
+
+    /// Returns the node with the smallest key, CAP if empty
+    pub(crate) fn first(&self) -> usize {
+        let root = self.root();
+        if root == CAP {
+            return CAP;
+        }
+        self.min_from(root)
+    }
+
+    /// Returns the node with the largest key, CAP if empty
+    pub(crate) fn last(&self) -> usize {
+        let root = self.root();
+        if root == CAP {
+            return CAP;
+        }
+        self.max_from(root)
+    }
+
+    /// Returns the in-order successor of `n`, CAP if `n` is the last node
+    ///
+    /// Requirements:
+    /// - `n` is in the tree
+    pub(crate) fn next(&self, mut n: usize) -> usize {
+        let r = self.right(n);
+        if r != CAP {
+            return self.min_from(r);
+        }
+        let mut p = self.parent(n);
+        while p != CAP && n == self.right(p) {
+            n = p;
+            p = self.parent(p);
+        }
+        p
+    }
+
+    /// Returns the in-order predecessor of `n`, CAP if `n` is the first node
+    ///
+    /// Requirements:
+    /// - `n` is in the tree
+    pub(crate) fn prev(&self, mut n: usize) -> usize {
+        let l = self.left(n);
+        if l != CAP {
+            return self.max_from(l);
+        }
+        let mut p = self.parent(n);
+        while p != CAP && n == self.left(p) {
+            n = p;
+            p = self.parent(p);
+        }
+        p
+    }
+
+    fn min_from(&self, mut n: usize) -> usize {
+        while self.left(n) != CAP {
+            n = self.left(n);
+        }
+        n
+    }
+
+    fn max_from(&self, mut n: usize) -> usize {
+        while self.right(n) != CAP {
+            n = self.right(n);
+        }
+        n
+    }
+
+    /// Replaces the subtree rooted at `u` with the subtree rooted at `v`
+    fn transplant(&mut self, u: usize, v: usize) {
+        let p = self.parent(u);
+        if p == CAP {
+            self.set_root(v);
+        } else if u == self.left(p) {
+            self.set_left(p, v);
+        } else {
+            self.set_right(p, v);
+        }
+        if v != CAP {
+            self.set_parent(v, p);
+        }
+    }
+
+    /// Requirements:
+    /// - right of `x` is valid
+    fn rotate_left(&mut self, x: usize) {
+        let y = self.right(x);
+        let y_left = self.left(y);
+        self.set_right(x, y_left);
+        if y_left != CAP {
+            self.set_parent(y_left, x);
+        }
+        self.transplant(x, y);
+        self.set_left(y, x);
+        self.set_parent(x, y);
+    }
+
+    /// Requirements:
+    /// - left of `x` is valid
+    fn rotate_right(&mut self, x: usize) {
+        let y = self.left(x);
+        let y_right = self.right(y);
+        self.set_left(x, y_right);
+        if y_right != CAP {
+            self.set_parent(y_right, x);
+        }
+        self.transplant(x, y);
+        self.set_right(y, x);
+        self.set_parent(x, y);
+    }
+
+    /// nil is black
+    fn is_red(&self, n: usize) -> bool {
+        n != CAP && self.is_reds[n]
+    }
+
+    fn root(&self) -> usize {
+        self.root.to_usize().unwrap()
+    }
+
+    fn left(&self, n: usize) -> usize {
+        self.lefts[n].to_usize().unwrap()
+    }
+
+    fn right(&self, n: usize) -> usize {
+        self.rights[n].to_usize().unwrap()
+    }
+
+    fn parent(&self, n: usize) -> usize {
+        self.parents[n].to_usize().unwrap()
+    }
+
+    fn set_root(&mut self, v: usize) {
+        self.root = I::from(v).unwrap();
+    }
+
+    fn set_left(&mut self, n: usize, v: usize) {
+        self.lefts[n] = I::from(v).unwrap();
+    }
+
+    fn set_right(&mut self, n: usize, v: usize) {
+        self.rights[n] = I::from(v).unwrap();
+    }
+
+    fn set_parent(&mut self, n: usize, v: usize) {
+        self.parents[n] = I::from(v).unwrap();
+    }
+}
@@ -0,0 +1,204 @@
+//! Helpers shared by the integration tests: a deterministic RNG, a reference model of an LRU cache
+//! and a comparator that panics on demand
+//!
+//! Not every test crate uses every helper
+#![allow(dead_code)]
+
+use core::cmp::Ordering;
+use core::hash::BuildHasher;
+use std::cell::Cell;
+use std::collections::BTreeMap;
+
+use const_lru::{Comparator, ConstHashLru, ConstLru, ConstLruTree, InsertReplaced};
+
+/// Deterministic pseudo-random sequence
+pub struct Lcg(u32);
+
+impl Lcg {
+    pub fn new(seed: u32) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u16(&mut self) -> u16 {
+        self.0 = self.0.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+        (self.0 >> 16) as u16
+    }
+
+    /// Returns a number in `[0, n)`
+    pub fn below(&mut self, n: u32) -> u32 {
+        u32::from(self.next_u16()) % n
+    }
+}
+
+/// A cache of `u16` keys and values that can be checked against the reference model
+pub trait ModelCache {
+    fn len(&self) -> usize;
+
+    fn insert(&mut self, k: u16, v: u16) -> Option<InsertReplaced<u16, u16>>;
+
+    fn remove(&mut self, k: &u16) -> Option<u16>;
+
+    fn get(&mut self, k: &u16) -> Option<u16>;
+
+    fn get_untouched(&self, k: &u16) -> Option<u16>;
+
+    /// Entries in MRU -> LRU order
+    fn entries(&self) -> Vec<(u16, u16)>;
+
+    /// Entries in key order, and in reverse key order, if the cache keeps one
+    #[allow(clippy::type_complexity)]
+    fn key_order(&self) -> Option<(Vec<(u16, u16)>, Vec<(u16, u16)>)>;
+}
+
+/// Implements [`ModelCache`] for a cache type. `key_order` is whether the type can iterate in key order
+macro_rules! impl_model_cache {
+    (@key_order $self:ident, true) => {
+        Some((
+            $self.iter_key_order().map(|(k, v)| (*k, *v)).collect(),
+            $self.iter_key_order().rev().map(|(k, v)| (*k, *v)).collect(),
+        ))
+    };
+    (@key_order $self:ident, false) => {
+        None
+    };
+    ([$($generics:tt)*] $ty:ty, key_order: $key_order:tt) => {
+        impl<$($generics)*> ModelCache for $ty {
+            fn len(&self) -> usize {
+                self.len().into()
+            }
+
+            fn insert(&mut self, k: u16, v: u16) -> Option<InsertReplaced<u16, u16>> {
+                self.insert(k, v)
+            }
+
+            fn remove(&mut self, k: &u16) -> Option<u16> {
+                self.remove(k)
+            }
+
+            fn get(&mut self, k: &u16) -> Option<u16> {
+                self.get(k).copied()
+            }
+
+            fn get_untouched(&self, k: &u16) -> Option<u16> {
+                self.get_untouched(k).copied()
+            }
+
+            fn entries(&self) -> Vec<(u16, u16)> {
+                self.iter().map(|(k, v)| (*k, *v)).collect()
+            }
+
+            fn key_order(&self) -> Option<(Vec<(u16, u16)>, Vec<(u16, u16)>)> {
+                impl_model_cache!(@key_order self, $key_order)
+            }
+        }
+    };
+}
+
+impl_model_cache!([const CAP: usize] ConstLru<u16, u16, CAP, u16>, key_order: true);
+impl_model_cache!([const CAP: usize] ConstLruTree<u16, u16, CAP, u16>, key_order: true);
+impl_model_cache!([S: BuildHasher, const CAP: usize] ConstHashLru<u16, u16, CAP, u16, S>, key_order: false);
+
+/// Checks `c` against the reference model: Vec of entries in MRU -> LRU order
+pub fn check_against_model<C: ModelCache>(c: &C, model: &[(u16, u16)]) {
+    assert_eq!(c.len(), model.len());
+    assert_eq!(c.entries(), model);
+    for (k, v) in model {
+        assert_eq!(c.get_untouched(k), Some(*v));
+    }
+    if let Some((key_order, rev_key_order)) = c.key_order() {
+        let sorted: Vec<(u16, u16)> = model
+            .iter()
+            .copied()
+            .collect::<BTreeMap<u16, u16>>()
+            .into_iter()
+            .collect();
+        assert_eq!(key_order, sorted);
+        assert!(rev_key_order.into_iter().eq(sorted.into_iter().rev()));
+    }
+}
+
+/// Performs `n_ops` random inserts, removes and gets of keys in `[0, key_range)` on both `c` and the reference model,
+/// checking that they agree after each one with [`check_against_model`] and `check`
+pub fn randomized_ops<C: ModelCache>(
+    c: &mut C,
+    cap: usize,
+    rng: &mut Lcg,
+    key_range: u16,
+    n_ops: usize,
+    mut check: impl FnMut(&C, &[(u16, u16)], &mut Lcg),
+) {
+    let mut model: Vec<(u16, u16)> = Vec::new();
+    for _ in 0..n_ops {
+        let k = rng.next_u16() % key_range;
+        let v = rng.next_u16();
+        match rng.next_u16() % 3 {
+            0 => {
+                let res = c.insert(k, v);
+                match model.iter().position(|(mk, _)| *mk == k) {
+                    Some(p) => {
+                        let (_, old_v) = model.remove(p);
+                        assert_eq!(res, Some(InsertReplaced::OldValue(old_v)));
+                    }
+                    None if model.len() == cap => {
+                        let (old_k, old_v) = model.pop().unwrap();
+                        assert_eq!(res, Some(InsertReplaced::LruEvicted(old_k, old_v)));
+                    }
+                    None => assert!(res.is_none()),
+                }
+                model.insert(0, (k, v));
+            }
+            1 => {
+                let res = c.remove(&k);
+                match model.iter().position(|(mk, _)| *mk == k) {
+                    Some(p) => assert_eq!(res, Some(model.remove(p).1)),
+                    None => assert!(res.is_none()),
+                }
+            }
+            _ => {
+                let res = c.get(&k);
+                match model.iter().position(|(mk, _)| *mk == k) {
+                    Some(p) => {
+                        let e = model.remove(p);
+                        assert_eq!(res, Some(e.1));
+                        model.insert(0, e);
+                    }
+                    None => assert!(res.is_none()),
+                }
+            }
+        }
+        check_against_model(c, &model);
+        check(c, &model, rng);
+    }
+}
+
+thread_local! {
+    static CMPS_LEFT: Cell<usize> = const { Cell::new(usize::MAX) };
+}
+
+/// Orders keys by their `Ord` impl, but panics on the `n`th comparison after [`PanicsAfter::arm`]`(n)`
+/// on the same thread
+pub struct PanicsAfter;
+
+impl PanicsAfter {
+    pub fn arm(n: usize) {
+        CMPS_LEFT.set(n);
+    }
+
+    pub fn disarm() {
+        CMPS_LEFT.set(usize::MAX);
+    }
+}
+
+impl<T: Ord + ?Sized> Comparator<T> for PanicsAfter {
+    fn cmp(a: &T, b: &T) -> Ordering {
+        let left = CMPS_LEFT.get();
+        if left != usize::MAX {
+            if left <= 1 {
+                Self::disarm();
+                panic!("comparator panicked");
+            }
+            CMPS_LEFT.set(left - 1);
+        }
+        a.cmp(b)
+    }
+}
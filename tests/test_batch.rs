@@ -0,0 +1,275 @@
+mod common;
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use common::{Lcg, PanicsAfter};
+use const_lru::{ConstLru, InsertReplaced};
+
+fn keys<const CAP: usize>(c: &ConstLru<u8, u16, CAP, u8>) -> Vec<u8> {
+    c.keys().copied().collect()
+}
+
+#[test]
+fn insert_batch() {
+    let mut c: ConstLru<u8, u16, 5, u8> = [(5, 5), (3, 3), (1, 1)].into();
+    let mut replaced = Vec::new();
+    c.insert_batch([(4, 4), (1, 10), (2, 2)], |r| replaced.push(r));
+    assert_eq!(replaced, [InsertReplaced::OldValue(1)]);
+    assert_eq!(keys(&c), [2, 1, 4, 5, 3]);
+    assert!(c
+        .iter_key_order()
+        .eq([(&1, &10), (&2, &2), (&3, &3), (&4, &4), (&5, &5)]));
+}
+
+#[test]
+fn insert_batch_duplicates() {
+    let mut c: ConstLru<u8, u16, 5, u8> = [(1, 1)].into();
+    let mut replaced = Vec::new();
+    c.insert_batch([(2, 2), (1, 10), (2, 20), (2, 21), (3, 3)], |r| {
+        replaced.push(r)
+    });
+    assert_eq!(
+        replaced,
+        [
+            InsertReplaced::OldValue(1),
+            InsertReplaced::OldValue(2),
+            InsertReplaced::OldValue(20)
+        ]
+    );
+    assert_eq!(keys(&c), [3, 2, 1]);
+    assert!(c.iter().eq([(&3, &3), (&2, &21), (&1, &10)]));
+}
+
+#[test]
+fn insert_batch_evicts() {
+    let mut c: ConstLru<u8, u16, 4, u8> = [(4, 4), (3, 3), (2, 2), (1, 1)].into();
+    let mut replaced = Vec::new();
+    c.insert_batch([(5, 5), (1, 10), (6, 6)], |r| replaced.push(r));
+    assert_eq!(
+        replaced,
+        [
+            InsertReplaced::LruEvicted(1, 1),
+            InsertReplaced::LruEvicted(2, 2),
+            InsertReplaced::LruEvicted(3, 3)
+        ]
+    );
+    assert!(c.iter().eq([(&6, &6), (&1, &10), (&5, &5), (&4, &4)]));
+}
+
+#[test]
+fn insert_batch_larger_than_cap() {
+    let mut c: ConstLru<u8, u16, 3, u8> = [(2, 2), (1, 1)].into();
+    let mut replaced = Vec::new();
+    c.insert_batch([(1, 10), (3, 3), (4, 4), (5, 5), (6, 6)], |r| {
+        replaced.push(r)
+    });
+    assert_eq!(
+        replaced,
+        [
+            InsertReplaced::OldValue(1),
+            InsertReplaced::LruEvicted(2, 2),
+            InsertReplaced::LruEvicted(1, 10),
+            InsertReplaced::LruEvicted(3, 3),
+        ]
+    );
+    assert!(c.iter().eq([(&6, &6), (&5, &5), (&4, &4)]));
+    assert!(c.get(&1).is_none());
+}
+
+#[test]
+fn insert_batch_iter_in_chunks() {
+    let mut batched: ConstLru<u8, u16, 4, u8> = [(1, 1), (20, 20)].into();
+    let mut one_by_one = batched.clone();
+    let entries = (0..11).map(|i| (i % 7, u16::from(i)));
+    let mut replaced = Vec::new();
+    batched.insert_batch(entries.clone(), |r| replaced.push(r));
+    let expected: Vec<_> = entries
+        .filter_map(|(k, v)| one_by_one.insert(k, v))
+        .collect();
+    assert!(batched.eq_with_order(&one_by_one));
+    assert_eq!(replaced, expected);
+    assert_eq!(keys(&batched), [3, 2, 1, 0]);
+}
+
+#[test]
+fn insert_batch_full_cache_reports_like_insert() {
+    // MRU -> LRU: 0, 1, ..., 5
+    let mut batched: ConstLru<u8, u16, 6, u8> = (0..6).rev().map(|k| (k, u16::from(k))).collect();
+    let mut one_by_one = batched.clone();
+    // 5 and 4 are evicted by 10 and 11 before they are inserted again,
+    // then the re-inserted 5 is updated by the last entry
+    let entries = [
+        (10, 10),
+        (11, 11),
+        (5, 50),
+        (0, 60),
+        (4, 40),
+        (12, 12),
+        (5, 51),
+    ];
+    let mut replaced = Vec::new();
+    batched.insert_batch(entries, |r| replaced.push(r));
+    let expected: Vec<_> = entries
+        .into_iter()
+        .filter_map(|(k, v)| one_by_one.insert(k, v))
+        .collect();
+    assert_eq!(
+        expected,
+        [
+            InsertReplaced::LruEvicted(5, 5),
+            InsertReplaced::LruEvicted(4, 4),
+            InsertReplaced::LruEvicted(3, 3),
+            InsertReplaced::OldValue(0),
+            InsertReplaced::LruEvicted(2, 2),
+            InsertReplaced::LruEvicted(1, 1),
+            InsertReplaced::OldValue(50),
+        ]
+    );
+    assert_eq!(replaced, expected);
+    assert!(batched.eq_with_order(&one_by_one));
+    assert_eq!(keys(&batched), [5, 12, 4, 0, 11, 10]);
+}
+
+#[test]
+fn insert_batch_zero_cap() {
+    let mut c: ConstLru<u8, u16, 0, u8> = ConstLru::new();
+    c.insert_batch([(1, 1)], |_| panic!("nothing is replaced"));
+    assert!(c.is_empty());
+}
+
+fn check_insert_batch_matches_insert<const CAP: usize, const N: usize>() {
+    let mut rng = Lcg::new(12345);
+    let n_keys = 3 * u32::try_from(CAP).unwrap();
+    let mut batched: ConstLru<u8, u16, CAP, u8> = ConstLru::new();
+    let mut one_by_one: ConstLru<u8, u16, CAP, u8> = ConstLru::new();
+    for round in 0..1_000u16 {
+        // remove some entries so the index's gap moves around
+        for _ in 0..rng.below(3) {
+            let k = u8::try_from(rng.below(n_keys)).unwrap();
+            assert_eq!(batched.remove(&k), one_by_one.remove(&k));
+        }
+        let batch: [(u8, u16); N] = core::array::from_fn(|i| {
+            let v = round * 16 + u16::try_from(i).unwrap();
+            (u8::try_from(rng.below(n_keys)).unwrap(), v)
+        });
+        let mut replaced = Vec::new();
+        batched.insert_batch(batch, |r| replaced.push(r));
+        let expected: Vec<_> = batch
+            .into_iter()
+            .filter_map(|(k, v)| one_by_one.insert(k, v))
+            .collect();
+        assert!(batched.eq_with_order(&one_by_one), "round {round}");
+        assert_eq!(replaced, expected, "round {round}");
+        assert!(batched.keys_key_order().is_sorted());
+    }
+}
+
+#[test]
+fn insert_batch_matches_insert() {
+    check_insert_batch_matches_insert::<8, 6>();
+    check_insert_batch_matches_insert::<8, 12>();
+    check_insert_batch_matches_insert::<1, 3>();
+    check_insert_batch_matches_insert::<33, 4>();
+    check_insert_batch_matches_insert::<40, 70>();
+}
+
+#[test]
+fn insert_batch_panicking_callback() {
+    let mut batched: ConstLru<u8, u16, 4, u8> = (0..4).map(|k| (k * 2, u16::from(k))).collect();
+    let mut one_by_one = batched.clone();
+    let entries = [(2, 10), (1, 11), (9, 12), (2, 13), (7, 14)];
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        batched.insert_batch(entries, |_| panic!("callback panicked"));
+    }));
+    assert!(res.is_err());
+    for (k, v) in entries {
+        one_by_one.insert(k, v);
+    }
+    assert!(batched.eq_with_order(&one_by_one));
+
+    batched.insert_batch([(3, 20), (2, 21)], |_| {});
+    assert_eq!(batched.get(&3), Some(&20));
+    assert_eq!(batched.remove(&2), Some(21));
+    assert!(batched.keys_key_order().is_sorted());
+}
+
+#[test]
+fn insert_batch_panicking_comparator() {
+    for n in 1.. {
+        let mut c: ConstLru<u16, u16, 8, u16, PanicsAfter> = (0..8).map(|k| (k * 2, k)).collect();
+        PanicsAfter::arm(n);
+        let res = catch_unwind(AssertUnwindSafe(|| {
+            c.insert_batch([(5, 5), (2, 2), (17, 17), (5, 6), (3, 3)], |_| {});
+        }));
+        PanicsAfter::disarm();
+        assert_eq!(c.keys_key_order().count(), usize::from(c.len()));
+        assert!(c.keys_key_order().is_sorted());
+        let lru_keys: Vec<u16> = c.keys().copied().collect();
+        for k in &lru_keys {
+            assert!(c.get_untouched(k).is_some());
+        }
+        c.insert(100, 100);
+        for k in lru_keys {
+            c.remove(&k);
+        }
+        assert!(c.keys().eq([&100]));
+        if res.is_ok() {
+            break;
+        }
+    }
+}
+
+#[test]
+fn get_batch() {
+    let mut c: ConstLru<u8, u16, 8, u8> = (0..8).map(|k| (k * 2, u16::from(k))).collect();
+    let mut found = Vec::new();
+    c.get_batch(&[2, 3, 8, 8, 14, 16], |k, v| {
+        found.push((
+            *k,
+            v.map(|v| {
+                *v += 100;
+                *v
+            }),
+        ))
+    });
+    assert_eq!(
+        found,
+        [
+            (2, Some(101)),
+            (3, None),
+            (8, Some(104)),
+            (8, Some(204)),
+            (14, Some(107)),
+            (16, None)
+        ]
+    );
+    assert_eq!(keys(&c)[..3], [14, 8, 2]);
+
+    // unsorted keys are still found
+    let mut found = Vec::new();
+    c.get_batch_untouched(&[12, 0, 6, 4, 5], |k, v| found.push((*k, v.copied())));
+    assert_eq!(
+        found,
+        [
+            (12, Some(6)),
+            (0, Some(0)),
+            (6, Some(3)),
+            (4, Some(2)),
+            (5, None)
+        ]
+    );
+    assert_eq!(keys(&c)[..3], [14, 8, 2]);
+}
+
+#[test]
+fn get_batch_string_keys() {
+    let mut c: ConstLru<String, u16, 4, u8> = ConstLru::new();
+    c.insert("a".to_owned(), 1);
+    c.insert("b".to_owned(), 2);
+    let mut sum = 0;
+    c.get_batch::<String, _>(&[], |_, _| unreachable!());
+    c.get_batch_untouched(&["a".to_owned(), "c".to_owned()], |_, v| {
+        sum += v.copied().unwrap_or(10)
+    });
+    assert_eq!(sum, 11);
+}
@@ -0,0 +1,78 @@
+use std::mem::MaybeUninit;
+
+mod common;
+
+use common::Lcg;
+use const_lru::{ConstLru, InsertReplaced};
+
+// moves the gap around the ring in both directions, past both ends
+fn randomized_ops<const CAP: usize>(seed: u32, key_range: u16) {
+    let mut c: ConstLru<u16, u16, CAP, u16> = ConstLru::new();
+    let mut rng = Lcg::new(seed);
+    common::randomized_ops(&mut c, CAP, &mut rng, key_range, 5_000, |c, model, rng| {
+        if rng.below(64) == 0 {
+            common::check_against_model(&c.clone(), model);
+        }
+    });
+}
+
+#[test]
+fn randomized_ops_small() {
+    randomized_ops::<1>(1, 4);
+    randomized_ops::<2>(2, 5);
+    randomized_ops::<3>(3, 6);
+    randomized_ops::<7>(4, 12);
+}
+
+#[test]
+fn randomized_ops_large() {
+    randomized_ops::<32>(5, 48);
+    randomized_ops::<33>(6, 100);
+}
+
+#[test]
+fn monotonic_keys_evict_then_insert() {
+    let mut slot: Box<MaybeUninit<ConstLru<u32, u32, 1_000, u16>>> = Box::new_uninit();
+    let c = ConstLru::init_in(&mut slot).unwrap();
+    for k in 0..1_000 {
+        assert!(c.insert(k, k).is_none());
+    }
+    // smallest key is always the LRU, new key is always the largest
+    for k in 1_000..5_000 {
+        assert_eq!(
+            c.insert(k, k),
+            Some(InsertReplaced::LruEvicted(k - 1_000, k - 1_000))
+        );
+    }
+    assert!(c.iter_key_order().map(|(k, _)| *k).eq(4_000..5_000));
+    // largest key is now the LRU, new key is always the smallest
+    for k in (4_000..5_000).rev() {
+        c.get(&k);
+    }
+    for k in (0..1_000).rev() {
+        assert_eq!(
+            c.insert(k, k),
+            Some(InsertReplaced::LruEvicted(k + 4_000, k + 4_000))
+        );
+    }
+    assert!(c.iter_key_order().map(|(k, _)| *k).eq(0..1_000));
+    for k in 0..1_000 {
+        assert_eq!(c.get_untouched(&k), Some(&k));
+    }
+}
+
+#[test]
+fn alternating_ends() {
+    let mut c: ConstLru<u16, u16, 100, u8> = ConstLru::new();
+    for i in 0..50 {
+        assert!(c.insert(500 + i, i).is_none());
+        assert!(c.insert(499 - i, i).is_none());
+    }
+    assert!(c.iter_key_order().map(|(k, _)| *k).eq(450..550));
+    for i in 0..50 {
+        assert_eq!(c.remove(&(450 + i)), Some(49 - i));
+        assert_eq!(c.remove(&(549 - i)), Some(49 - i));
+    }
+    assert!(c.is_empty());
+    assert!(c.iter_key_order().next().is_none());
+}
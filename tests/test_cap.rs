@@ -1,4 +1,6 @@
-use const_lru::ConstLru;
+use core::mem::MaybeUninit;
+
+use const_lru::{CapacityError, ConstLru};
 
 #[test]
 #[should_panic]
@@ -11,3 +13,49 @@ fn u128_i_panic() {
 fn cap_oob_panic() {
     let _c: ConstLru<u8, u8, 256, u8> = ConstLru::new();
 }
+
+#[test]
+fn u128_i_init_in_err() {
+    let mut slot: MaybeUninit<ConstLru<u8, u8, 1, u128>> = MaybeUninit::uninit();
+    assert_eq!(
+        ConstLru::init_in(&mut slot).unwrap_err(),
+        CapacityError::IndexExceedsUsizeMax
+    );
+}
+
+#[test]
+fn cap_oob_init_in_err() {
+    let mut slot: MaybeUninit<ConstLru<u8, u8, 256, u8>> = MaybeUninit::uninit();
+    assert_eq!(
+        ConstLru::init_in(&mut slot).unwrap_err(),
+        CapacityError::CapExceedsIndexMax
+    );
+}
+
+#[test]
+fn max_cap_init_in() {
+    let mut slot: MaybeUninit<ConstLru<u8, u8, 255, u8>> = MaybeUninit::uninit();
+    let c = ConstLru::init_in(&mut slot).unwrap();
+    assert!(c.is_empty());
+    assert!(c.insert(1, 2).is_none());
+    assert_eq!(*c.get(&1).unwrap(), 2);
+    unsafe { slot.assume_init_drop() };
+}
+
+#[test]
+fn max_cap_new_checked() {
+    let mut c: ConstLru<u8, u8, 255, u8> = ConstLru::new_checked();
+    assert!(c.insert(1, 2).is_none());
+    assert_eq!(*c.get(&1).unwrap(), 2);
+}
+
+#[test]
+fn clone_into_uninit() {
+    let mut c: ConstLru<u8, u16, 3, u8> = ConstLru::new();
+    c.insert(1, 2);
+    c.insert(3, 4);
+    let mut slot = MaybeUninit::uninit();
+    let cloned = c.clone_into_uninit(&mut slot);
+    assert!(cloned.iter().eq(c.iter()));
+    unsafe { slot.assume_init_drop() };
+}
@@ -0,0 +1,173 @@
+use core::cmp::Ordering;
+
+use const_lru::{Comparator, ConstLru, Entry, InsertReplaced};
+
+struct CaseInsensitive;
+
+impl Comparator<str> for CaseInsensitive {
+    fn cmp(a: &str, b: &str) -> Ordering {
+        let a = a.bytes().map(|c| c.to_ascii_lowercase());
+        let b = b.bytes().map(|c| c.to_ascii_lowercase());
+        a.cmp(b)
+    }
+}
+
+impl Comparator<String> for CaseInsensitive {
+    fn cmp(a: &String, b: &String) -> Ordering {
+        <Self as Comparator<str>>::cmp(a, b)
+    }
+}
+
+struct Reverse;
+
+impl Comparator<u8> for Reverse {
+    fn cmp(a: &u8, b: &u8) -> Ordering {
+        b.cmp(a)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct User {
+    id: u32,
+    name: &'static str,
+}
+
+struct ById;
+
+impl Comparator<User> for ById {
+    fn cmp(a: &User, b: &User) -> Ordering {
+        a.id.cmp(&b.id)
+    }
+}
+
+#[test]
+fn case_insensitive_borrowed_lookup() {
+    let mut c: ConstLru<String, u8, 3, u8, CaseInsensitive> = ConstLru::new();
+    assert!(c.insert("Hello".to_owned(), 1).is_none());
+    assert!(c.insert("world".to_owned(), 2).is_none());
+
+    assert_eq!(*c.get("HELLO").unwrap(), 1);
+    assert_eq!(*c.get_untouched("World").unwrap(), 2);
+    *c.get_mut("hello").unwrap() = 3;
+    assert_eq!(*c.get_mut_untouched("hElLo").unwrap(), 3);
+    assert!(c.get("help").is_none());
+
+    assert_eq!(c.remove("WORLD").unwrap(), 2);
+    assert_eq!(c.len(), 1);
+}
+
+#[test]
+fn case_insensitive_insert_replaces() {
+    let mut c: ConstLru<String, u8, 3, u8, CaseInsensitive> = ConstLru::new();
+    c.insert("Hello".to_owned(), 1);
+    assert_eq!(
+        c.insert("HELLO".to_owned(), 2).unwrap(),
+        InsertReplaced::OldValue(1)
+    );
+    assert_eq!(c.len(), 1);
+    // key is not updated
+    assert_eq!(c.iter().next().unwrap(), (&"Hello".to_owned(), &2));
+}
+
+#[test]
+fn case_insensitive_entry() {
+    let mut c: ConstLru<String, u8, 3, u8, CaseInsensitive> = ConstLru::new();
+    c.insert("Hello".to_owned(), 1);
+    let Entry::Occupied(mut entry) = c.entry("hello".to_owned()) else {
+        panic!("not occupied")
+    };
+    assert_eq!(*entry.get(), 1);
+}
+
+#[test]
+fn reverse_key_order() {
+    let mut c: ConstLru<u8, u16, 3, u8, Reverse> = ConstLru::new();
+    for k in [2, 3, 1] {
+        c.insert(k, k.into());
+    }
+    let keys: Vec<u8> = c.iter_key_order().map(|(k, _)| *k).collect();
+    assert_eq!(keys, [3, 2, 1]);
+    let keys: Vec<u8> = c.iter_key_order_mut().rev().map(|(k, _)| *k).collect();
+    assert_eq!(keys, [1, 2, 3]);
+}
+
+#[test]
+fn reverse_evicts_lru() {
+    let mut c: ConstLru<u8, u16, 2, u8, Reverse> = ConstLru::new();
+    c.insert(1, 1);
+    c.insert(2, 2);
+    assert_eq!(c.insert(3, 3).unwrap(), InsertReplaced::LruEvicted(1, 1));
+    let keys: Vec<u8> = c.iter_key_order().map(|(k, _)| *k).collect();
+    assert_eq!(keys, [3, 2]);
+}
+
+#[test]
+fn reverse_from() {
+    const ENTRIES: [(u8, u16); 3] = [(1, 2), (3, 4), (2, 5)];
+    let c: ConstLru<u8, u16, 3, u8, Reverse> = ENTRIES.into();
+    let keys: Vec<u8> = c.iter_key_order().map(|(k, _)| *k).collect();
+    assert_eq!(keys, [3, 2, 1]);
+    for (i, tup) in c.iter().enumerate() {
+        assert_eq!(tup, (&ENTRIES[i].0, &ENTRIES[i].1));
+    }
+}
+
+#[test]
+fn projection_try_from_duplicates() {
+    let entries = [
+        (User { id: 1, name: "a" }, 1),
+        (User { id: 1, name: "b" }, 2),
+    ];
+    let err = ConstLru::<User, u8, 2, u8, ById>::try_from_array_strict(entries).unwrap_err();
+    assert_eq!(err.0.id, 1);
+}
+
+#[test]
+fn projection_lookup() {
+    let mut c: ConstLru<User, u8, 2, u8, ById> = ConstLru::new();
+    c.insert(User { id: 2, name: "a" }, 1);
+    assert_eq!(*c.get(&User { id: 2, name: "b" }).unwrap(), 1);
+    assert!(c.get(&User { id: 1, name: "a" }).is_none());
+}
+
+#[test]
+fn projection_replace_key() {
+    let mut c: ConstLru<User, u8, 2, u8, ById> = ConstLru::new();
+    c.insert(User { id: 1, name: "a" }, 1);
+    c.insert(User { id: 2, name: "a" }, 2);
+
+    let Entry::Occupied(e) = c.entry(User { id: 1, name: "b" }) else {
+        panic!("not occupied")
+    };
+    assert_eq!(e.key().name, "b");
+    assert_eq!(e.replace_key().unwrap().name, "a");
+    assert_eq!(
+        c.get_key_value_untouched(&User { id: 1, name: "c" })
+            .unwrap()
+            .0
+            .name,
+        "b"
+    );
+    // replace_key does not change LRU order
+    assert!(c.keys().map(|u| u.id).eq([2, 1]));
+
+    let Entry::Occupied(e) = c.entry(User { id: 1, name: "c" }) else {
+        panic!("not occupied")
+    };
+    let (old_k, old_v) = e.replace_entry_untouched(3);
+    assert_eq!((old_k.unwrap().name, old_v), ("b", 1));
+    assert!(c.keys().map(|u| u.id).eq([2, 1]));
+
+    let Entry::Occupied(e) = c.entry(User { id: 1, name: "d" }) else {
+        panic!("not occupied")
+    };
+    let (old_k, old_v) = e.replace_entry(4);
+    assert_eq!((old_k.unwrap().name, old_v), ("c", 3));
+    assert!(c.iter().map(|(u, v)| (u.name, *v)).eq([("d", 4), ("a", 2)]));
+
+    // entries returned by insert_entry were inserted with their key, so there's nothing to replace
+    let (e, _) = c.entry(User { id: 3, name: "e" }).insert_entry(5);
+    assert_eq!(e.key().name, "e");
+    assert_eq!(e.replace_entry(6), (None, 5));
+    assert_eq!(c.get_untouched(&User { id: 3, name: "f" }), Some(&6));
+}
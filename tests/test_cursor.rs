@@ -0,0 +1,151 @@
+use const_lru::{ConstLru, InsertReplaced};
+
+/// LRU order: 4, 3, 2, 1, 0
+fn lru() -> ConstLru<u8, u16, 5, u8> {
+    (0..5).map(|k| (k, u16::from(k) * 10)).collect()
+}
+
+fn keys<const CAP: usize>(c: &ConstLru<u8, u16, CAP, u8>) -> Vec<u8> {
+    c.keys().copied().collect()
+}
+
+#[test]
+fn walk_front_to_back() {
+    let mut c = lru();
+    let mut cursor = c.cursor_mut_front();
+    let mut walked = Vec::new();
+    while let Some((k, v)) = cursor.current() {
+        *v += 1;
+        walked.push(*k);
+        cursor.move_next();
+    }
+    assert_eq!(walked, [4, 3, 2, 1, 0]);
+    // at ghost, wraps around
+    assert_eq!(cursor.peek_next(), Some((&4, &41)));
+    assert_eq!(cursor.peek_prev(), Some((&0, &1)));
+    cursor.move_next();
+    assert_eq!(cursor.current().unwrap().0, &4);
+    assert!(cursor.peek_prev().is_none());
+    // walking doesn't change LRU order
+    assert_eq!(keys(&c), [4, 3, 2, 1, 0]);
+}
+
+#[test]
+fn walk_back_to_front() {
+    let mut c = lru();
+    let mut cursor = c.cursor_mut_back();
+    assert!(cursor.peek_next().is_none());
+    let mut walked = Vec::new();
+    while let Some((k, _)) = cursor.current() {
+        walked.push(*k);
+        cursor.move_prev();
+    }
+    assert_eq!(walked, [0, 1, 2, 3, 4]);
+    cursor.move_prev();
+    assert_eq!(cursor.current().unwrap().0, &0);
+}
+
+#[test]
+fn move_current_to_back() {
+    let mut c = lru();
+    let mut cursor = c.cursor_mut_front();
+    // demote odd keys towards the LRU end
+    for _ in 0..5 {
+        let (k, _) = cursor.current().unwrap();
+        if k % 2 == 1 {
+            cursor.move_current_to_back();
+        } else {
+            cursor.move_next();
+        }
+    }
+    assert_eq!(keys(&c), [4, 2, 0, 3, 1]);
+    // evicts demoted entries first
+    assert_eq!(c.insert(5, 50), Some(InsertReplaced::LruEvicted(1, 10)));
+    assert_eq!(c.insert(6, 60), Some(InsertReplaced::LruEvicted(3, 30)));
+    assert_eq!(keys(&c), [6, 5, 4, 2, 0]);
+    assert!(c.iter_key_order().map(|(k, _)| *k).eq([0, 2, 4, 5, 6]));
+}
+
+#[test]
+fn move_current_to_front() {
+    let mut c = lru();
+    let mut cursor = c.cursor_mut_back();
+    cursor.move_prev();
+    // at 1
+    cursor.move_current_to_front();
+    // moved to 0, which was next to 1
+    assert_eq!(cursor.current().unwrap().0, &0);
+    cursor.move_current_to_front();
+    // 0 was LRU, so at the ghost
+    assert!(cursor.current().is_none());
+    cursor.move_current_to_front();
+    assert_eq!(keys(&c), [0, 1, 4, 3, 2]);
+}
+
+#[test]
+fn move_tail_to_back_and_head_to_front() {
+    let mut c = lru();
+    let mut cursor = c.cursor_mut_back();
+    cursor.move_current_to_back();
+    assert!(cursor.current().is_none());
+    let mut cursor = c.cursor_mut_front();
+    cursor.move_current_to_front();
+    assert_eq!(cursor.current().unwrap().0, &3);
+    assert_eq!(keys(&c), [4, 3, 2, 1, 0]);
+}
+
+#[test]
+fn remove_current() {
+    let mut c = lru();
+    let mut cursor = c.cursor_mut_front();
+    cursor.move_next();
+    assert_eq!(cursor.remove_current(), Some((3, 30)));
+    assert_eq!(cursor.current().unwrap().0, &2);
+    cursor.move_prev();
+    assert_eq!(cursor.remove_current(), Some((4, 40)));
+    assert_eq!(cursor.current().unwrap().0, &2);
+    assert!(cursor.peek_prev().is_none());
+    assert_eq!(keys(&c), [2, 1, 0]);
+    assert!(c.get(&3).is_none());
+    assert!(c.insert(3, 3).is_none());
+    assert!(c.insert(4, 4).is_none());
+    assert!(c.is_full());
+}
+
+#[test]
+fn remove_all() {
+    let mut c = lru();
+    let mut cursor = c.cursor_mut_back();
+    let mut removed = Vec::new();
+    while let Some((k, _)) = cursor.remove_current() {
+        removed.push(k);
+    }
+    assert_eq!(removed, [0]);
+    let mut cursor = c.cursor_mut_front();
+    while let Some((k, _)) = cursor.remove_current() {
+        removed.push(k);
+    }
+    assert_eq!(removed, [0, 4, 3, 2, 1]);
+    assert!(c.is_empty());
+    assert!(c.cursor_mut_front().current().is_none());
+    c.extend((0..5).map(|k| (k, 0)));
+    assert_eq!(keys(&c), [4, 3, 2, 1, 0]);
+}
+
+#[test]
+fn empty() {
+    let mut c: ConstLru<u8, u16, 2, u8> = ConstLru::new();
+    let mut cursor = c.cursor_mut_front();
+    assert!(cursor.current().is_none());
+    assert!(cursor.peek_next().is_none());
+    assert!(cursor.peek_prev().is_none());
+    cursor.move_next();
+    cursor.move_prev();
+    cursor.move_current_to_back();
+    assert!(cursor.remove_current().is_none());
+
+    let mut c: ConstLru<u8, u16, 0, u8> = ConstLru::new();
+    let mut cursor = c.cursor_mut_back();
+    cursor.move_next();
+    assert!(cursor.current().is_none());
+}
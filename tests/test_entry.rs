@@ -1,4 +1,4 @@
-use const_lru::{ConstLru, Entry};
+use const_lru::{ConstLru, Entry, MaybeStored, TryEntry, TryInsertError};
 
 #[test]
 #[should_panic]
@@ -137,3 +137,167 @@ fn occupied_remove() {
     assert!(c.get(&k).is_none());
     assert_eq!(c.len(), 1);
 }
+
+#[test]
+fn try_insert() {
+    let mut c: ConstLru<u8, u8, 2, u8> = ConstLru::new();
+    *c.try_insert(1, 1).unwrap() += 10;
+    assert_eq!(*c.try_insert(2, 2).unwrap(), 2);
+    assert!(c.iter().eq([(&2, &2), (&1, &11)]));
+
+    let Err(TryInsertError::Occupied(e, v)) = c.try_insert(1, 3) else {
+        panic!("not occupied")
+    };
+    assert_eq!(v, 3);
+    assert_eq!(*e.get_untouched(), 11);
+    assert_eq!(
+        format!("{}", TryInsertError::Occupied(e, v)),
+        "key already present: 1"
+    );
+    // not touched
+    assert!(c.iter().eq([(&2, &2), (&1, &11)]));
+
+    let err = c.try_insert(3, 3).unwrap_err();
+    assert_eq!(format!("{err}"), "full, cannot insert key: 3");
+    let TryInsertError::Full(k, v) = err else {
+        panic!("not full")
+    };
+    assert_eq!((k, v), (3, 3));
+    assert!(c.iter().eq([(&2, &2), (&1, &11)]));
+}
+
+#[test]
+fn try_insert_zero_cap() {
+    let mut c: ConstLru<u8, u8, 0, u8> = ConstLru::new();
+    assert!(matches!(
+        c.try_insert(1, 1),
+        Err(TryInsertError::Full(1, 1))
+    ));
+}
+
+#[test]
+fn vacant_try_insert() {
+    let mut c: ConstLru<u8, u8, 2, u8> = ConstLru::new();
+    let Entry::Vacant(e) = c.entry(1) else {
+        panic!("not vacant")
+    };
+    assert_eq!(*e.try_insert(1).unwrap(), 1);
+    c.insert(2, 2);
+    let Entry::Vacant(e) = c.entry(3) else {
+        panic!("not vacant")
+    };
+    assert!(matches!(e.try_insert(3), Err(TryInsertError::Full(3, 3))));
+    assert_eq!(c.len(), 2);
+}
+
+#[test]
+fn zero_cap_try_entry() {
+    let mut c: ConstLru<u8, u8, 0, u8> = ConstLru::new();
+    let TryEntry::ZeroCap(k) = c.try_entry(1) else {
+        panic!("should not store")
+    };
+    assert_eq!(k, 1);
+    assert_eq!(*c.try_entry(1).key(), 1);
+    assert_eq!(c.try_entry(1).into_entry().unwrap_err(), 1);
+
+    let mut v = c.try_entry(1).or_insert(2);
+    *v += 1;
+    assert!(matches!(v, MaybeStored::Unstored(3)));
+    assert_eq!(*c.try_entry(1).or_insert_with(|| 4), 4);
+    assert_eq!(*c.try_entry(1).or_insert_with_key(|k| k + 4), 5);
+    assert_eq!(c.try_entry(1).or_default().into_owned(), 0);
+    assert!(c.is_empty());
+}
+
+#[test]
+fn try_entry() {
+    let mut c: ConstLru<u8, u8, 2, u8> = ConstLru::new();
+    let mut v = c.try_entry(1).or_insert(1);
+    assert!(v.is_stored());
+    *v += 1;
+    assert_eq!(c.get_untouched(&1), Some(&2));
+    assert!(matches!(c.try_entry(1), TryEntry::Occupied(_)));
+    assert!(matches!(c.try_entry(2), TryEntry::Vacant(_)));
+    assert!(matches!(c.try_entry(2).into_entry(), Ok(Entry::Vacant(_))));
+    assert_eq!(*c.try_entry(2).or_default(), 0);
+    assert_eq!(c.try_entry(1).or_insert_with(|| 5).into_owned(), 2);
+    // existing entry is moved to the most-recently-used slot
+    assert!(c.keys().eq(&[1, 2]));
+}
+
+#[test]
+fn and_modify() {
+    let mut c: ConstLru<u8, u8, 3, u8> = ConstLru::new();
+    c.insert(1, 1);
+    c.insert(2, 2);
+
+    assert_eq!(*c.entry(1).and_modify(|v| *v += 10).or_insert(0), 11);
+    assert!(c.keys().eq(&[1, 2]));
+    assert_eq!(*c.entry(3).and_modify(|v| *v += 10).or_insert(0), 0);
+    assert!(c.keys().eq(&[3, 1, 2]));
+
+    let Entry::Occupied(e) = c.entry(2).and_modify_untouched(|v| *v += 10) else {
+        panic!("not occupied")
+    };
+    assert_eq!(*e.into_mut_untouched(), 12);
+    assert!(c.keys().eq(&[3, 1, 2]));
+}
+
+#[test]
+fn or_try_insert_with() {
+    let mut c: ConstLru<u8, u8, 2, u8> = ConstLru::new();
+    assert_eq!(c.entry(1).or_try_insert_with(|| Err("fail")), Err("fail"));
+    assert!(c.is_empty());
+
+    let (v, evicted) = c.entry(1).or_try_insert_with(|| Ok::<_, ()>(1)).unwrap();
+    assert_eq!((*v, evicted), (1, None));
+    c.insert(2, 2);
+
+    // existing entries don't call the default function
+    let (v, evicted) = c
+        .entry(1)
+        .or_try_insert_with_untouched(|| Err("unreachable"))
+        .unwrap();
+    assert_eq!((*v, evicted), (1, None));
+    assert!(c.keys().eq(&[2, 1]));
+    c.entry(1)
+        .or_try_insert_with(|| Err("unreachable"))
+        .unwrap();
+    assert!(c.keys().eq(&[1, 2]));
+
+    let (v, evicted) = c.entry(3).or_try_insert_with(|| Ok::<_, ()>(3)).unwrap();
+    assert_eq!((*v, evicted), (3, Some((2, 2))));
+    assert!(c.keys().eq(&[3, 1]));
+}
+
+#[test]
+fn insert_entry() {
+    let mut c: ConstLru<u8, u8, 2, u8> = ConstLru::new();
+    let (mut e, evicted) = c.entry(1).insert_entry(1);
+    assert!(evicted.is_none());
+    assert_eq!(*e.key(), 1);
+    assert_eq!(e.insert(2), 1);
+    c.insert(2, 2);
+
+    let (e, evicted) = c.entry(1).insert_entry_untouched(3);
+    assert!(evicted.is_none());
+    assert_eq!(*e.get_untouched(), 3);
+    assert!(c.keys().eq(&[2, 1]));
+
+    let (e, evicted) = c.entry(1).insert_entry(4);
+    assert!(evicted.is_none());
+    assert_eq!(e.remove_entry(), (1, 4));
+    assert!(c.keys().eq(&[2]));
+
+    c.insert(1, 1);
+    let Entry::Vacant(e) = c.entry(3) else {
+        panic!("not vacant")
+    };
+    let (mut e, evicted) = e.insert_entry(3);
+    assert_eq!(evicted, Some((2, 2)));
+    assert_eq!(e.insert_untouched(5), 3);
+    // bs index of the new entry accounts for the evicted entry
+    assert_eq!(e.remove(), 5);
+    assert!(c.keys().eq(&[1]));
+    assert!(c.get(&3).is_none());
+}
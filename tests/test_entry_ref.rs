@@ -0,0 +1,102 @@
+use std::{borrow::Borrow, cell::Cell};
+
+use const_lru::{ConstLru, Entry, EntryRef};
+
+thread_local! {
+    static CONVERSIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// String key that counts how many times it's created from a `&str`
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct CountedKey(String);
+
+impl From<&str> for CountedKey {
+    fn from(s: &str) -> Self {
+        CONVERSIONS.with(|c| c.set(c.get() + 1));
+        Self(s.to_owned())
+    }
+}
+
+impl Borrow<str> for CountedKey {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+fn conversions() -> usize {
+    CONVERSIONS.with(Cell::get)
+}
+
+#[test]
+#[should_panic]
+fn zero_cap_entry_ref_panic() {
+    let mut c: ConstLru<String, u8, 0, u8> = ConstLru::new();
+    c.entry_ref("a");
+}
+
+#[test]
+fn only_converts_key_on_insert() {
+    let mut c: ConstLru<CountedKey, u8, 2, u8> = ConstLru::new();
+    *c.entry_ref("a").or_insert(0) += 1;
+    assert_eq!(conversions(), 1);
+    *c.entry_ref("a").or_insert(0) += 1;
+    *c.entry_ref("a").or_insert_with(|| unreachable!()) += 1;
+    c.entry_ref("a").and_modify(|v| *v += 1).or_default();
+    assert_eq!(conversions(), 1);
+    assert_eq!(c.get_untouched("a"), Some(&4));
+
+    assert_eq!(*c.entry_ref("bc").or_insert_with_key(|k| k.len() as u8), 2);
+    assert_eq!(conversions(), 2);
+}
+
+#[test]
+fn key_and_lru_order() {
+    let mut c: ConstLru<String, u8, 2, u8> = ConstLru::new();
+    c.insert("a".to_owned(), 1);
+    c.insert("b".to_owned(), 2);
+
+    let e = c.entry_ref("a");
+    assert_eq!(e.key(), "a");
+    let EntryRef::Occupied(e) = e.and_modify_untouched(|v| *v += 10) else {
+        panic!("not occupied")
+    };
+    // no owned lookup key, so the stored key is returned and there's nothing to replace
+    assert_eq!(e.key(), "a");
+    assert!(e.replace_key().is_none());
+    assert!(c.keys().eq(["b", "a"]));
+
+    assert_eq!(*c.entry_ref("a").or_insert(0), 11);
+    assert!(c.keys().eq(["a", "b"]));
+}
+
+#[test]
+fn vacant_insert_evicts() {
+    let mut c: ConstLru<String, u8, 2, u8> = ConstLru::new();
+    c.insert("b".to_owned(), 2);
+    c.insert("c".to_owned(), 3);
+
+    let EntryRef::Vacant(e) = c.entry_ref("a") else {
+        panic!("not vacant")
+    };
+    assert_eq!(e.key(), "a");
+    let (v, evicted) = e.insert(1);
+    assert_eq!(*v, 1);
+    assert_eq!(evicted, Some(("b".to_owned(), 2)));
+    assert!(c.keys().eq(["a", "c"]));
+
+    let (e, evicted) = c.entry_ref("d").insert_entry(4);
+    assert_eq!(evicted, Some(("c".to_owned(), 3)));
+    assert_eq!(e.remove_entry(), ("d".to_owned(), 4));
+    assert!(c.keys().eq(["a"]));
+}
+
+#[test]
+fn into_entry() {
+    let mut c: ConstLru<String, u8, 2, u8> = ConstLru::new();
+    let Entry::Vacant(e) = c.entry_ref("a").into_entry() else {
+        panic!("not vacant")
+    };
+    assert_eq!(e.into_key(), "a");
+    c.insert("a".to_owned(), 1);
+    assert!(matches!(c.entry_ref("a").into_entry(), Entry::Occupied(_)));
+}
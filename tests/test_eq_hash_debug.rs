@@ -0,0 +1,126 @@
+use std::{
+    collections::{
+        hash_map::{DefaultHasher, RandomState},
+        HashSet,
+    },
+    hash::{Hash, Hasher},
+};
+
+use const_lru::{ConstHashLru, ConstLru, ConstLruTree, EytzingerLru};
+
+fn hash_of<T: Hash>(t: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    t.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn eq_ignores_lru_order() {
+    let a: ConstLru<u8, u16, 4, u8> = [(3, 3), (2, 2), (1, 1)].into();
+    let b: ConstLru<u8, u16, 4, u8> = [(2, 2), (1, 1), (3, 3)].into();
+    assert_eq!(a, b);
+    assert!(a.eq_as_map(&b));
+    assert!(!a.eq_with_order(&b));
+    assert_eq!(hash_of(&a), hash_of(&b));
+
+    let c: ConstLru<u8, u16, 4, u8> = [(2, 2), (1, 1), (3, 3)].into();
+    assert!(b.eq_with_order(&c));
+}
+
+#[test]
+fn ne() {
+    let a: ConstLru<u8, u16, 4, u8> = [(2, 2), (1, 1)].into();
+    let b: ConstLru<u8, u16, 4, u8> = [(2, 3), (1, 1)].into();
+    let c: ConstLru<u8, u16, 4, u8> = [(1, 1)].into();
+    assert_ne!(a, b);
+    assert_ne!(a, c);
+    assert_ne!(c, a);
+    assert!(!a.eq_with_order(&c));
+    assert_ne!(hash_of(&a), hash_of(&b));
+    assert_eq!(ConstLru::<u8, u16, 4, u8>::new(), ConstLru::new());
+}
+
+#[test]
+fn eq_different_cap_and_index() {
+    let a: ConstLru<u8, u16, 4, u8> = [(2, 2), (1, 1)].into();
+    let b: ConstLru<u8, u16, 300, u16> = [(1, 1), (2, 2)].into();
+    assert!(a.eq_as_map(&b));
+    assert!(!a.eq_with_order(&b));
+}
+
+#[test]
+fn hash_set_of_caches() {
+    let mut set = HashSet::new();
+    set.insert(ConstLru::<u8, u16, 4, u8>::from([(1, 1), (2, 2)]));
+    assert!(!set.insert(ConstLru::from([(1, 1), (2, 2)])));
+    assert!(set.insert(ConstLru::from([(2, 2)])));
+}
+
+#[test]
+fn debug_prints_entries_in_lru_order() {
+    let mut c: ConstLru<u8, u16, 4, u8> = [(2, 20), (1, 10)].into();
+    assert_eq!(format!("{c:?}"), "{2: 20, 1: 10}");
+    c.get(&1);
+    assert_eq!(format!("{c:?}"), "{1: 10, 2: 20}");
+    assert_eq!(format!("{:?}", ConstLru::<u8, u16, 4, u8>::new()), "{}");
+}
+
+#[test]
+fn tree() {
+    let mut a: ConstLruTree<u8, u16, 4, u8> = ConstLruTree::new();
+    let mut b: ConstLruTree<u8, u16, 4, u8> = ConstLruTree::new();
+    for k in [1, 2, 3] {
+        a.insert(k, u16::from(k));
+    }
+    for k in [3, 1, 2] {
+        b.insert(k, u16::from(k));
+    }
+    assert_eq!(a, b);
+    assert!(!a.eq_with_order(&b));
+    assert_eq!(hash_of(&a), hash_of(&b));
+    assert_eq!(format!("{a:?}"), "{3: 3, 2: 2, 1: 1}");
+    b.insert(3, 4);
+    assert_ne!(a, b);
+}
+
+#[test]
+fn hash_lru() {
+    let mut a: ConstHashLru<u8, u16, 4, u8> = ConstHashLru::new();
+    let mut b: ConstHashLru<u8, u16, 4, u8> = ConstHashLru::new();
+    for k in [1, 2, 3] {
+        a.insert(k, u16::from(k));
+    }
+    for k in [3, 1, 2] {
+        b.insert(k, u16::from(k));
+    }
+    assert_eq!(a, b);
+    assert_eq!(hash_of(&a), hash_of(&b));
+    assert!(!a.eq_with_order(&b));
+    assert_eq!(format!("{b:?}"), "{2: 2, 1: 1, 3: 3}");
+    b.remove(&3);
+    assert_ne!(a, b);
+    assert_ne!(hash_of(&a), hash_of(&b));
+    b.insert(4, 4);
+    assert_ne!(a, b);
+}
+
+#[test]
+fn hash_lru_independent_of_hasher_seed() {
+    let mut a: ConstHashLru<u8, u16, 16, u8, RandomState> = ConstHashLru::new();
+    let mut b: ConstHashLru<u8, u16, 16, u8, RandomState> = ConstHashLru::new();
+    for k in 0..16 {
+        a.insert(k, u16::from(k) * 3);
+        b.insert(15 - k, u16::from(15 - k) * 3);
+    }
+    assert_eq!(a, b);
+    assert_eq!(hash_of(&a), hash_of(&b));
+}
+
+#[test]
+fn eytzinger() {
+    let a: EytzingerLru<u8, u16, 4, u8> = ConstLru::from([(2, 2), (1, 1)]).into();
+    let b: EytzingerLru<u8, u16, 4, u8> = ConstLru::from([(1, 1), (2, 2)]).into();
+    assert_eq!(a, b);
+    assert_eq!(hash_of(&a), hash_of(&b));
+    assert_eq!(format!("{a:?}"), "{2: 2, 1: 1}");
+}
@@ -0,0 +1,178 @@
+mod common;
+
+use std::borrow::Borrow;
+use std::cell::Cell;
+use std::rc::Rc;
+
+use common::Lcg;
+use const_lru::{ConstLru, Entry, EytzingerLru, InsertReplaced};
+
+#[test]
+fn matches_const_lru() {
+    const CAP: usize = 50;
+    let mut e: EytzingerLru<u16, u16, CAP, u8> = EytzingerLru::new();
+    let mut c: ConstLru<u16, u16, CAP, u8> = ConstLru::new();
+    let mut rng = Lcg::new(42);
+
+    for _ in 0..5_000 {
+        let k = rng.next_u16() % 120;
+        let v = rng.next_u16();
+        match rng.next_u16() % 6 {
+            0 => assert_eq!(e.insert(k, v), c.insert(k, v)),
+            1 => assert_eq!(e.remove(&k), c.remove(&k)),
+            2 => assert_eq!(e.get_untouched(&k), c.get_untouched(&k)),
+            3 => e.rebuild_index(),
+            4 => assert_eq!(e.get_mut(&k), c.get_mut(&k)),
+            _ => assert_eq!(e.get(&k), c.get(&k)),
+        }
+        assert!(e.iter().eq(c.iter()));
+    }
+}
+
+#[test]
+fn entry_matches_const_lru() {
+    const CAP: usize = 20;
+    let mut e: EytzingerLru<u16, u16, CAP, u8> = EytzingerLru::new();
+    let mut c: ConstLru<u16, u16, CAP, u8> = ConstLru::new();
+    let mut rng = Lcg::new(7);
+
+    for _ in 0..5_000 {
+        let k = rng.next_u16() % 40;
+        let v = rng.next_u16();
+        match rng.next_u16() % 4 {
+            0 => match (e.entry(k), c.entry(k)) {
+                (Entry::Occupied(eo), Entry::Occupied(co)) => {
+                    assert_eq!(eo.remove(), co.remove());
+                }
+                (Entry::Vacant(ev), Entry::Vacant(cv)) => {
+                    assert_eq!(ev.insert(v), cv.insert(v));
+                }
+                _ => panic!("entries of {k} differ"),
+            },
+            // leaves the keys unchanged
+            1 => assert_eq!(*e.entry(k).or_insert(v), *c.entry(k).or_insert(v)),
+            2 => assert_eq!(e.get_untouched(&k), c.get_untouched(&k)),
+            _ => assert_eq!(e.get(&k), c.get(&k)),
+        }
+        assert!(e.iter().eq(c.iter()));
+    }
+}
+
+/// Key that counts how many times it's cloned, to tell when the Eytzinger copy is rebuilt
+#[derive(Debug)]
+struct CountedKey(u8, Rc<Cell<usize>>);
+
+impl Clone for CountedKey {
+    fn clone(&self) -> Self {
+        self.1.set(self.1.get() + 1);
+        Self(self.0, self.1.clone())
+    }
+}
+
+impl PartialEq for CountedKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for CountedKey {}
+
+impl PartialOrd for CountedKey {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CountedKey {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl Borrow<u8> for CountedKey {
+    fn borrow(&self) -> &u8 {
+        &self.0
+    }
+}
+
+#[test]
+fn entry_only_rebuilds_after_insert_or_remove() {
+    let clones = Rc::new(Cell::new(0));
+    let key = |k| CountedKey(k, clones.clone());
+    let mut e: EytzingerLru<CountedKey, u8, 3, u8> = EytzingerLru::new();
+    for k in 0..3 {
+        e.insert(key(k), k);
+    }
+    e.rebuild_index();
+    assert_eq!(clones.get(), 3);
+
+    *e.entry(key(1)).or_insert(0) += 10;
+    let _unused = e.entry(key(5));
+    assert_eq!(e.get(&1), Some(&11));
+    assert_eq!(clones.get(), 3);
+
+    // evicts 0 and reuses its slot, so the length stays the same
+    e.entry(key(5)).or_insert(5);
+    assert_eq!(e.get(&5), Some(&5));
+    assert!(e.get(&0).is_none());
+    assert_eq!(clones.get(), 6);
+
+    if let Entry::Occupied(o) = e.entry(key(2)) {
+        o.remove();
+    }
+    assert!(e.get(&2).is_none());
+    assert_eq!(clones.get(), 8);
+    assert!(e.keys().map(|k| k.0).eq([5, 1]));
+}
+
+#[test]
+fn every_len() {
+    let mut e: EytzingerLru<u8, u8, 20, u8> = EytzingerLru::new();
+    for len in 0..20 {
+        for k in 0..len {
+            assert_eq!(*e.get(&k).unwrap(), k);
+            assert_eq!(*e.get_untouched(&k).unwrap(), k);
+        }
+        assert!(e.get(&len).is_none());
+        assert!(e.insert(len, len).is_none());
+    }
+    assert!(e.is_full());
+    assert_eq!(e.insert(20, 20).unwrap(), InsertReplaced::LruEvicted(0, 0));
+    assert!(e.get(&0).is_none());
+    assert_eq!(*e.get_mut_untouched(&20).unwrap(), 20);
+}
+
+#[test]
+fn entry_and_clear() {
+    let mut e: EytzingerLru<u8, u8, 3, u8> = EytzingerLru::from(ConstLru::new());
+    *e.entry(1).or_insert(1) += 1;
+    assert_eq!(*e.get(&1).unwrap(), 2);
+    let cloned = e.clone();
+    e.clear();
+    assert!(e.get(&1).is_none());
+    assert_eq!(*cloned.get_untouched(&1).unwrap(), 2);
+    assert_eq!(cloned.into_inner().len(), 1);
+}
+
+#[test]
+fn key_clones_dropped() {
+    use std::rc::Rc;
+
+    let k = Rc::new(1);
+    let mut e: EytzingerLru<Rc<u8>, u8, 3, u8> = EytzingerLru::new();
+    e.insert(k.clone(), 1);
+    assert_eq!(*e.get(&1).unwrap(), 1);
+    assert_eq!(Rc::strong_count(&k), 3);
+    let cloned = e.clone();
+    assert_eq!(Rc::strong_count(&k), 5);
+    drop(cloned);
+    e.remove(&1);
+    assert!(e.get(&1).is_none());
+    assert_eq!(Rc::strong_count(&k), 1);
+    e.insert(k.clone(), 2);
+    assert_eq!(*e.get_mut(&1).unwrap(), 2);
+    let c = e.into_inner();
+    assert_eq!(Rc::strong_count(&k), 2);
+    drop(c);
+    assert_eq!(Rc::strong_count(&k), 1);
+}
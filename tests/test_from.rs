@@ -0,0 +1,97 @@
+use const_lru::{ConstLru, DuplicateKeysError};
+
+#[test]
+fn from_zero_cap() {
+    const ENTRIES: [(u8, u16); 0] = [];
+    let c: ConstLru<u8, u16, 0, u8> = ENTRIES.into();
+    assert!(c.is_empty());
+    assert!(c.is_full());
+}
+
+#[test]
+fn from_one_cap() {
+    const ENTRIES: [(u8, u16); 1] = [(1, 2)];
+    let mut c: ConstLru<u8, u16, 1, u8> = ENTRIES.into();
+    assert!(c.is_full());
+
+    assert_eq!(*c.get(&ENTRIES[0].0).unwrap(), ENTRIES[0].1);
+
+    let mut iter = c.iter();
+    assert_eq!(iter.next().unwrap(), (&ENTRIES[0].0, &ENTRIES[0].1));
+    assert!(iter.next().is_none());
+
+    assert_eq!(c.remove(&ENTRIES[0].0).unwrap(), ENTRIES[0].1);
+
+    assert!(c.is_empty());
+}
+
+#[test]
+fn from_three_cap() {
+    const ENTRIES: [(u8, u16); 3] = [(1, 2), (3, 4), (5, 6)];
+    let mut c: ConstLru<u8, u16, 3, u8> = ENTRIES.into();
+    assert!(c.is_full());
+
+    // check lru order, entries are MRU -> LRU
+    for (i, tup) in c.iter().enumerate() {
+        assert_eq!(tup, (&ENTRIES[i].0, &ENTRIES[i].1));
+    }
+
+    // check get works
+    for (k, v) in ENTRIES {
+        assert_eq!(*c.get(&k).unwrap(), v);
+    }
+
+    // check remove works
+    for (new_len, (k, v)) in ENTRIES.iter().enumerate().rev() {
+        assert_eq!(c.remove(k).unwrap(), *v);
+        assert_eq!(c.len(), u8::try_from(new_len).unwrap());
+    }
+    assert!(c.is_empty());
+}
+
+#[test]
+fn from_partial_fill() {
+    let mut c: ConstLru<u8, u16, 4, u8> = [(2, 1), (1, 2)].into();
+    assert_eq!(c.len(), 2);
+    assert!(!c.is_full());
+    assert!(c.iter().eq([(&2, &1), (&1, &2)]));
+    assert!(c.insert(3, 3).is_none());
+}
+
+#[test]
+fn from_duplicates_keep_mru() {
+    let c: ConstLru<u8, u16, 3, u8> = [(1, 1), (2, 2), (1, 3)].into();
+    assert!(c.iter().eq([(&1, &1), (&2, &2)]));
+}
+
+#[test]
+fn from_iter_overflow_evicts_lru() {
+    let c: ConstLru<u8, u16, 3, u8> = (0..10).map(|k| (k, u16::from(k) * 2)).collect();
+    assert!(c.is_full());
+    assert!(c.iter().eq([(&9, &18), (&8, &16), (&7, &14)]));
+}
+
+#[test]
+fn extend() {
+    let mut c: ConstLru<u8, u16, 3, u8> = ConstLru::new();
+    c.insert(1, 1);
+    c.extend([(2, 2), (1, 10), (3, 3), (4, 4)]);
+    assert!(c.iter().eq([(&4, &4), (&3, &3), (&1, &10)]));
+}
+
+#[test]
+fn try_from_iter_strict() {
+    let c = ConstLru::<u8, u16, 3, u8>::try_from_iter_strict([(1, 1), (2, 2)]).unwrap();
+    assert!(c.iter().eq([(&2, &2), (&1, &1)]));
+
+    let err = ConstLru::<u8, u16, 3, u8>::try_from_iter_strict([(1, 1), (2, 2), (1, 3)]);
+    assert_eq!(err.unwrap_err(), DuplicateKeysError(1));
+
+    // 0 is evicted before its duplicate is inserted
+    let c =
+        ConstLru::<u8, u16, 2, u8>::try_from_iter_strict([(0, 0), (1, 1), (2, 2), (0, 3)]).unwrap();
+    assert!(c.iter().eq([(&0, &3), (&2, &2)]));
+
+    let c = ConstLru::<u8, u16, 0, u8>::try_from_iter_strict([(1, 1), (1, 1)]).unwrap();
+    assert!(c.is_empty());
+}
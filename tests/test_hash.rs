@@ -0,0 +1,149 @@
+use core::hash::{BuildHasherDefault, Hasher};
+use std::mem::MaybeUninit;
+use std::rc::Rc;
+
+mod common;
+
+use common::Lcg;
+use const_lru::hash::Entry;
+use const_lru::{ConstHashLru, InsertReplaced};
+
+/// Hashes everything to the same value to force every key into the same probe sequence
+#[derive(Default)]
+struct CollidingHasher;
+
+impl Hasher for CollidingHasher {
+    fn write(&mut self, _bytes: &[u8]) {}
+
+    fn finish(&self) -> u64 {
+        0xdead_beef
+    }
+}
+
+type Colliding = BuildHasherDefault<CollidingHasher>;
+
+fn randomized_ops<S: core::hash::BuildHasher + Default, const CAP: usize>(key_range: u16) {
+    let mut c: ConstHashLru<u16, u16, CAP, u16, S> = ConstHashLru::new();
+    let mut rng = Lcg::new(12345);
+    common::randomized_ops(&mut c, CAP, &mut rng, key_range, 5_000, |_, _, _| ());
+}
+
+#[test]
+fn randomized_default_hasher() {
+    randomized_ops::<const_lru::hash::DefaultHashBuilder, 16>(40);
+}
+
+#[test]
+fn randomized_colliding_hasher() {
+    randomized_ops::<Colliding, 16>(40);
+}
+
+#[test]
+fn randomized_std_hasher() {
+    randomized_ops::<std::collections::hash_map::RandomState, 100>(300);
+}
+
+#[test]
+fn borrowed_lookup() {
+    let mut c: ConstHashLru<String, u8, 3, u8> = ConstHashLru::new();
+    assert!(c.insert("hello".to_owned(), 1).is_none());
+    assert!(c.insert("world".to_owned(), 2).is_none());
+
+    assert_eq!(*c.get("hello").unwrap(), 1);
+    assert_eq!(*c.get_untouched("world").unwrap(), 2);
+    *c.get_mut("hello").unwrap() = 3;
+    assert_eq!(*c.get_mut_untouched("hello").unwrap(), 3);
+    assert!(c.get("help").is_none());
+
+    assert_eq!(c.remove("world").unwrap(), 2);
+    assert_eq!(c.len(), 1);
+}
+
+#[test]
+fn evicts_lru() {
+    let mut c: ConstHashLru<u8, u8, 3, u8> = ConstHashLru::new();
+    c.insert(1, 1);
+    c.insert(2, 2);
+    c.insert(3, 3);
+    c.get(&1);
+    assert_eq!(c.insert(4, 4).unwrap(), InsertReplaced::LruEvicted(2, 2));
+    assert!(c.is_full());
+    let keys: Vec<u8> = c.iter().map(|(k, _)| *k).collect();
+    assert_eq!(keys, [4, 1, 3]);
+    let keys: Vec<u8> = c.into_iter().rev().map(|(k, _)| k).collect();
+    assert_eq!(keys, [3, 1, 4]);
+}
+
+#[test]
+fn zero_cap() {
+    let mut c: ConstHashLru<u8, u8, 0, u8> = ConstHashLru::new();
+    assert!(c.insert(1, 1).is_none());
+    assert!(c.get(&1).is_none());
+    assert!(c.remove(&1).is_none());
+    assert!(c.is_empty());
+    assert!(c.is_full());
+}
+
+#[test]
+#[should_panic]
+fn zero_cap_entry_panic() {
+    let mut c: ConstHashLru<u8, u8, 0, u8> = ConstHashLru::new();
+    c.entry(1);
+}
+
+#[test]
+fn entry() {
+    let mut c: ConstHashLru<u8, u8, 2, u8, Colliding> = ConstHashLru::new();
+    *c.entry(1).or_insert(1) += 10;
+    assert_eq!(*c.entry(2).or_default(), 0);
+
+    let Entry::Occupied(mut entry) = c.entry(1) else {
+        panic!("not occupied")
+    };
+    assert_eq!(*entry.get(), 11);
+    assert_eq!(entry.insert(5), 11);
+    assert_eq!(entry.remove_entry(), (1, 5));
+    assert_eq!(c.len(), 1);
+
+    c.insert(3, 3);
+    let Entry::Vacant(entry) = c.entry(4) else {
+        panic!("not vacant")
+    };
+    let (m, evicted) = entry.insert(4);
+    assert_eq!(*m, 4);
+    assert_eq!(evicted.unwrap(), (2, 0));
+    assert_eq!(*c.get(&3).unwrap(), 3);
+    assert_eq!(*c.get(&4).unwrap(), 4);
+}
+
+#[test]
+fn clone_and_clear() {
+    let mut c: ConstHashLru<Rc<u8>, u8, 3, u8> = ConstHashLru::new();
+    let k = Rc::new(1);
+    c.insert(k.clone(), 1);
+    c.insert(Rc::new(2), 2);
+
+    let mut slot = MaybeUninit::uninit();
+    let cloned = c.clone_into_uninit(&mut slot);
+    assert_eq!(Rc::strong_count(&k), 3);
+    assert_eq!(*cloned.get(&1).unwrap(), 1);
+    assert_eq!(
+        c.iter().collect::<Vec<_>>(),
+        c.clone().iter().collect::<Vec<_>>()
+    );
+    unsafe { slot.assume_init_drop() };
+    assert_eq!(Rc::strong_count(&k), 2);
+
+    c.clear();
+    assert_eq!(Rc::strong_count(&k), 1);
+    assert!(c.is_empty());
+    assert!(c.get(&1).is_none());
+    c.insert(k.clone(), 3);
+    assert_eq!(*c.get(&1).unwrap(), 3);
+}
+
+#[test]
+fn init_in_errors() {
+    let mut slot: MaybeUninit<ConstHashLru<u8, u8, 256, u8>> = MaybeUninit::uninit();
+    assert!(ConstHashLru::init_in(&mut slot).is_err());
+}
@@ -0,0 +1,107 @@
+use const_lru::ConstLru;
+
+fn lru() -> ConstLru<u8, u16, 8, u8> {
+    // LRU order differs from key order
+    [(40, 4), (20, 2), (50, 5), (10, 1), (30, 3)].into()
+}
+
+#[test]
+fn first_last() {
+    let c = lru();
+    assert_eq!(c.first_key_value(), Some((&10, &1)));
+    assert_eq!(c.last_key_value(), Some((&50, &5)));
+    // LRU order is unchanged
+    assert!(c.keys().eq(&[40, 20, 50, 10, 30]));
+
+    let empty: ConstLru<u8, u16, 8, u8> = ConstLru::new();
+    assert!(empty.first_key_value().is_none());
+    assert!(empty.last_key_value().is_none());
+}
+
+#[test]
+fn pop_first_last() {
+    let mut c = lru();
+    assert_eq!(c.pop_first(), Some((10, 1)));
+    assert_eq!(c.pop_last(), Some((50, 5)));
+    assert_eq!(c.pop_first(), Some((20, 2)));
+    assert!(c.keys().eq(&[40, 30]));
+    assert_eq!(c.pop_last(), Some((40, 4)));
+    assert_eq!(c.pop_last(), Some((30, 3)));
+    assert!(c.pop_first().is_none());
+    assert!(c.pop_last().is_none());
+
+    let mut zero: ConstLru<u8, u16, 0, u8> = ConstLru::new();
+    assert!(zero.pop_first().is_none());
+}
+
+#[test]
+fn lower_bound() {
+    let c = lru();
+    let cursor = c.lower_bound(&20);
+    assert_eq!(cursor.current(), Some((&20, &2)));
+    assert_eq!(cursor.index(), Some(1));
+    assert_eq!(cursor.peek_prev(), Some((&10, &1)));
+    assert_eq!(cursor.peek_next(), Some((&30, &3)));
+
+    assert_eq!(c.lower_bound(&21).current(), Some((&30, &3)));
+    assert_eq!(c.lower_bound(&0).current(), Some((&10, &1)));
+
+    let ghost = c.lower_bound(&51);
+    assert!(ghost.current().is_none());
+    assert!(ghost.index().is_none());
+    assert_eq!(ghost.peek_prev(), Some((&50, &5)));
+    assert_eq!(ghost.peek_next(), Some((&10, &1)));
+}
+
+#[test]
+fn upper_bound() {
+    let c = lru();
+    let cursor = c.upper_bound(&20);
+    assert_eq!(cursor.current(), Some((&30, &3)));
+    // largest key <= 20
+    assert_eq!(cursor.peek_prev(), Some((&20, &2)));
+    assert_eq!(c.upper_bound(&19).peek_prev(), Some((&10, &1)));
+
+    let cursor = c.upper_bound(&5);
+    assert_eq!(cursor.current(), Some((&10, &1)));
+    assert!(cursor.peek_prev().is_none());
+
+    assert!(c.upper_bound(&50).current().is_none());
+    assert_eq!(c.upper_bound(&50).peek_prev(), Some((&50, &5)));
+}
+
+#[test]
+fn cursor_steps_both_ways() {
+    let c = lru();
+    let mut cursor = c.lower_bound(&40);
+    cursor.move_next();
+    assert_eq!(cursor.current(), Some((&50, &5)));
+    cursor.move_next();
+    assert!(cursor.current().is_none());
+    cursor.move_next();
+    assert_eq!(cursor.current(), Some((&10, &1)));
+    cursor.move_prev();
+    assert!(cursor.current().is_none());
+    cursor.move_prev();
+    assert_eq!(cursor.current(), Some((&50, &5)));
+
+    let mut keys = Vec::new();
+    let mut cursor = c.lower_bound(&30);
+    while let Some((k, _)) = cursor.current() {
+        keys.push(*k);
+        cursor.move_prev();
+    }
+    assert_eq!(keys, [30, 20, 10]);
+}
+
+#[test]
+fn cursor_empty() {
+    let c: ConstLru<u8, u16, 8, u8> = ConstLru::new();
+    let mut cursor = c.lower_bound(&1);
+    assert!(cursor.current().is_none());
+    assert!(cursor.peek_next().is_none());
+    assert!(cursor.peek_prev().is_none());
+    cursor.move_next();
+    cursor.move_prev();
+    assert!(cursor.current().is_none());
+}
@@ -0,0 +1,196 @@
+#![cfg(feature = "std")]
+
+use std::{
+    cell::Cell,
+    future::Future,
+    pin::{pin, Pin},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+    time::Duration,
+};
+
+use const_lru::LoadingCache;
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+fn block_on<F: Future>(f: F) -> F::Output {
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    let mut f = pin!(f);
+    loop {
+        if let Poll::Ready(res) = f.as_mut().poll(&mut cx) {
+            return res;
+        }
+        thread::park();
+    }
+}
+
+type Task<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+/// Polls all `tasks` on the current thread until they're all done
+fn join_all<'a, T>(tasks: Vec<Task<'a, T>>) -> Vec<T> {
+    let mut tasks: Vec<_> = tasks.into_iter().map(|t| (t, None)).collect();
+    block_on(core::future::poll_fn(|cx| {
+        let mut all_done = true;
+        for (task, res) in tasks.iter_mut() {
+            if res.is_none() {
+                match task.as_mut().poll(cx) {
+                    Poll::Ready(r) => *res = Some(r),
+                    Poll::Pending => all_done = false,
+                }
+            }
+        }
+        if all_done {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }));
+    tasks.into_iter().map(|(_, res)| res.unwrap()).collect()
+}
+
+/// Returns `Pending` once, so that other tasks get polled
+async fn yield_now() {
+    let mut yielded = false;
+    core::future::poll_fn(|cx| {
+        if yielded {
+            return Poll::Ready(());
+        }
+        yielded = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    })
+    .await
+}
+
+#[test]
+fn coalesces_concurrent_misses() {
+    let cache: LoadingCache<u8, u32, 4, u8> = LoadingCache::new();
+    let calls = Cell::new(0);
+    let load = |k: &u8| {
+        let k = *k;
+        calls.set(calls.get() + 1);
+        async move {
+            yield_now().await;
+            Ok::<_, ()>(u32::from(k) * 10)
+        }
+    };
+    let tasks: Vec<Task<_>> = (0..8)
+        .map(|i| Box::pin(cache.get_or_load(i % 2, load)) as Task<_>)
+        .collect();
+    let res = join_all(tasks);
+    assert_eq!(calls.get(), 2);
+    for (i, r) in res.into_iter().enumerate() {
+        assert_eq!(r, Ok(u32::try_from(i % 2).unwrap() * 10));
+    }
+    assert_eq!(cache.len(), 2);
+    assert_eq!(
+        block_on(cache.get_or_load(1, |_| async { Err(()) })),
+        Ok(10)
+    );
+}
+
+#[test]
+fn failed_loads_not_cached() {
+    let cache: LoadingCache<u8, u32, 4, u8> = LoadingCache::new();
+    assert_eq!(
+        block_on(cache.get_or_load(1, |_| async { Err("backend down") })),
+        Err("backend down")
+    );
+    assert!(cache.is_empty());
+    assert_eq!(
+        block_on(cache.get_or_load(1, |_| async { Ok::<_, &str>(1) })),
+        Ok(1)
+    );
+    assert_eq!(cache.get_cloned(&1), Some(1));
+}
+
+#[test]
+fn waiters_retry_after_failed_load() {
+    let cache: LoadingCache<u8, u32, 4, u8> = LoadingCache::new();
+    let calls = Cell::new(0);
+    let load = |_: &u8| {
+        calls.set(calls.get() + 1);
+        let fail = calls.get() == 1;
+        async move {
+            yield_now().await;
+            if fail {
+                Err(())
+            } else {
+                Ok(5)
+            }
+        }
+    };
+    let tasks: Vec<Task<_>> = (0..4)
+        .map(|_| Box::pin(cache.get_or_load(0, load)) as Task<_>)
+        .collect();
+    let res = join_all(tasks);
+    // only the first caller sees its load fail, the rest share the 2nd load
+    assert_eq!(res, [Err(()), Ok(5u32), Ok(5), Ok(5)]);
+    assert_eq!(calls.get(), 2);
+}
+
+#[test]
+fn dropped_load_wakes_waiters() {
+    let cache: LoadingCache<u8, u32, 4, u8> = LoadingCache::new();
+    let never = |_: &u8| core::future::pending::<Result<u32, ()>>();
+    let mut abandoned = Box::pin(cache.get_or_load(0, never));
+    let mut waiting = Box::pin(cache.get_or_load(0, |_| async { Ok::<_, ()>(7) }));
+    let waker = Waker::noop();
+    let mut cx = Context::from_waker(waker);
+    assert!(abandoned.as_mut().poll(&mut cx).is_pending());
+    assert!(waiting.as_mut().poll(&mut cx).is_pending());
+    drop(abandoned);
+    assert_eq!(waiting.as_mut().poll(&mut cx), Poll::Ready(Ok(7)));
+    assert_eq!(cache.get_cloned(&0), Some(7));
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn coalesces_across_threads() {
+    let cache: LoadingCache<u32, u32, 16, u8> = LoadingCache::new();
+    let calls = AtomicUsize::new(0);
+    thread::scope(|s| {
+        for _ in 0..8 {
+            s.spawn(|| {
+                let v = block_on(cache.get_or_load(3, |k| {
+                    calls.fetch_add(1, Ordering::Relaxed);
+                    let k = *k;
+                    async move {
+                        thread::sleep(Duration::from_millis(50));
+                        Ok::<_, ()>(k + 1)
+                    }
+                }));
+                assert_eq!(v, Ok(4));
+            });
+        }
+    });
+    assert_eq!(calls.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn evicts_lru() {
+    let cache: LoadingCache<u8, u8, 2, u8> = LoadingCache::new();
+    for k in 0..3 {
+        assert_eq!(
+            block_on(cache.get_or_load(k, |k| {
+                let k = *k;
+                async move { Ok::<_, ()>(k) }
+            })),
+            Ok(k)
+        );
+    }
+    assert!(cache.get_cloned(&0).is_none());
+    assert!(cache.remove(&1).is_some());
+    assert_eq!(cache.len(), 1);
+}
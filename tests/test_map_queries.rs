@@ -0,0 +1,120 @@
+use const_lru::ConstLru;
+
+fn lru() -> ConstLru<String, u16, 4, u8> {
+    // LRU order: c, a, b. Key order: a, b, c
+    [
+        ("c".to_owned(), 3),
+        ("a".to_owned(), 1),
+        ("b".to_owned(), 2),
+    ]
+    .into()
+}
+
+#[test]
+fn contains_key() {
+    let c = lru();
+    assert!(c.contains_key("a"));
+    assert!(!c.contains_key("d"));
+    // does not touch
+    assert!(c.keys().eq(["c", "a", "b"]));
+}
+
+#[test]
+fn get_key_value() {
+    let mut c = lru();
+    assert_eq!(c.get_key_value_untouched("b"), Some((&"b".to_owned(), &2)));
+    assert!(c.keys().eq(["c", "a", "b"]));
+    assert_eq!(c.get_key_value("b"), Some((&"b".to_owned(), &2)));
+    assert!(c.keys().eq(["b", "c", "a"]));
+    assert!(c.get_key_value("d").is_none());
+    assert!(c.get_key_value_untouched("d").is_none());
+}
+
+#[test]
+fn index() {
+    let mut c = lru();
+    assert_eq!(c["a"], 1);
+    c["a"] += 10;
+    assert_eq!(c["a"], 11);
+    // does not touch
+    assert!(c.keys().eq(["c", "a", "b"]));
+}
+
+#[test]
+#[should_panic]
+fn index_missing_panic() {
+    let c = lru();
+    let _ = c["d"];
+}
+
+#[test]
+fn keys_and_values() {
+    let mut c = lru();
+    assert!(c.keys().eq(["c", "a", "b"]));
+    assert!(c.keys().rev().eq(["b", "a", "c"]));
+    assert!(c.keys_key_order().eq(["a", "b", "c"]));
+    assert!(c.values().eq(&[3, 1, 2]));
+    assert!(c.values_key_order().eq(&[1, 2, 3]));
+    assert_eq!(c.values_key_order().len(), 3);
+
+    for v in c.values_mut() {
+        *v *= 10;
+    }
+    assert!(c.values().eq(&[30, 10, 20]));
+    for (i, v) in c.values_key_order_mut().enumerate() {
+        *v += u16::try_from(i).unwrap();
+    }
+    assert!(c.values_key_order().eq(&[10, 21, 32]));
+    // does not touch
+    assert!(c.keys().eq(["c", "a", "b"]));
+}
+
+#[test]
+fn into_keys_and_values() {
+    assert!(lru().into_keys().eq(["c", "a", "b"]));
+    assert!(lru().into_keys_key_order().eq(["a", "b", "c"]));
+    assert!(lru().into_keys_key_order().rev().eq(["c", "b", "a"]));
+    assert!(lru().into_values().eq([3, 1, 2]));
+    assert!(lru().into_values_key_order().eq([1, 2, 3]));
+
+    let mut iter = lru().into_iter_key_order();
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.next_back(), Some(("c".to_owned(), 3)));
+    assert_eq!(iter.next(), Some(("a".to_owned(), 1)));
+    assert_eq!(iter.len(), 1);
+    assert_eq!(iter.next(), Some(("b".to_owned(), 2)));
+    assert!(iter.next().is_none());
+    assert!(iter.next_back().is_none());
+}
+
+#[test]
+fn get_many_mut() {
+    let mut c: ConstLru<u8, u16, 4, u8> = (1..=4).map(|k| (k, u16::from(k))).collect();
+
+    let [a, b] = c.get_many_mut([&1, &3]).unwrap();
+    core::mem::swap(a, b);
+    assert!(c.iter().eq([(&3, &1), (&1, &3), (&4, &4), (&2, &2)]));
+
+    let [a, b, d] = c.get_many_mut_untouched([&2, &4, &1]).unwrap();
+    *a += 10;
+    *b += 10;
+    *d += 10;
+    assert!(c.iter().eq([(&3, &1), (&1, &13), (&4, &14), (&2, &12)]));
+
+    assert!(c.get_many_mut([&1, &5]).is_none());
+    assert!(c.get_many_mut([&1, &1]).is_none());
+    assert!(c.get_many_mut_untouched([&2, &2]).is_none());
+    // LRU order is unchanged on failure
+    assert!(c.keys().eq(&[3, 1, 4, 2]));
+
+    let [] = c.get_many_mut::<u8, 0>([]).unwrap();
+}
+
+#[test]
+fn get_many() {
+    let mut c: ConstLru<u8, u16, 4, u8> = (1..=4).map(|k| (k, u16::from(k))).collect();
+    assert_eq!(c.get_many([&2, &1, &2]), Some([&2, &1, &2]));
+    assert!(c.keys().eq(&[2, 1, 4, 3]));
+    assert!(c.get_many([&3, &5]).is_none());
+    assert!(c.keys().eq(&[2, 1, 4, 3]));
+}
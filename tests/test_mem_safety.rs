@@ -182,7 +182,39 @@ fn into_iter_partially_consumed_no_double_free() {
 }
 
 #[test]
-fn try_from_takes_ownership_of_entries() {
+fn into_iter_key_order_partially_consumed_no_double_free() {
+    let entries: [(Rc<u8>, Rc<u16>); 3] = [
+        (Rc::new(0), Rc::new(1)),
+        (Rc::new(2), Rc::new(3)),
+        (Rc::new(4), Rc::new(5)),
+    ];
+
+    {
+        let mut c: ConstLru<Rc<u8>, Rc<u16>, 3, u8> = ConstLru::new();
+        // LRU order differs from key order
+        for i in [1, 0, 2] {
+            c.insert(entries[i].0.clone(), entries[i].1.clone());
+        }
+
+        let mut iter = c.into_iter_key_order();
+        assert_eq!(*iter.next_back().unwrap().0, 4); // drop [2] immediately
+        assert_eq!(*iter.next().unwrap().0, 0); // drop [0] immediately
+
+        assert_eq!(Rc::strong_count(&entries[0].0), 1);
+        assert_eq!(Rc::strong_count(&entries[0].1), 1);
+        assert_eq!(Rc::strong_count(&entries[1].0), 2);
+        assert_eq!(Rc::strong_count(&entries[1].1), 2);
+        assert_eq!(Rc::strong_count(&entries[2].0), 1);
+        assert_eq!(Rc::strong_count(&entries[2].1), 1);
+    }
+    for (k, v) in entries.iter() {
+        assert_eq!(Rc::strong_count(k), 1);
+        assert_eq!(Rc::strong_count(v), 1);
+    }
+}
+
+#[test]
+fn from_takes_ownership_of_entries() {
     let entries: [(Rc<u8>, Rc<u16>); 2] = [(Rc::new(0), Rc::new(1)), (Rc::new(2), Rc::new(3))];
     let cloned = entries.clone();
     assert_eq!(Rc::strong_count(&entries[0].0), 2);
@@ -190,7 +222,7 @@ fn try_from_takes_ownership_of_entries() {
     assert_eq!(Rc::strong_count(&entries[1].0), 2);
     assert_eq!(Rc::strong_count(&entries[1].1), 2);
     {
-        let _c: ConstLru<Rc<u8>, Rc<u16>, 2, u8> = ConstLru::try_from(cloned).unwrap();
+        let _c: ConstLru<Rc<u8>, Rc<u16>, 2, u8> = ConstLru::from(cloned);
         assert_eq!(Rc::strong_count(&entries[0].0), 2);
         assert_eq!(Rc::strong_count(&entries[0].1), 2);
         assert_eq!(Rc::strong_count(&entries[1].0), 2);
@@ -203,7 +235,7 @@ fn try_from_takes_ownership_of_entries() {
 }
 
 #[test]
-fn try_from_no_double_free_on_failure() {
+fn try_from_iter_strict_no_double_free_on_failure() {
     let entries: [(Rc<u8>, Rc<u16>); 2] = [(Rc::new(0), Rc::new(1)), (Rc::new(0), Rc::new(2))];
     let cloned = entries.clone();
     assert_eq!(Rc::strong_count(&entries[0].0), 2);
@@ -211,7 +243,29 @@ fn try_from_no_double_free_on_failure() {
     assert_eq!(Rc::strong_count(&entries[1].0), 2);
     assert_eq!(Rc::strong_count(&entries[1].1), 2);
     {
-        let err = ConstLru::<Rc<u8>, Rc<u16>, 2, u8>::try_from(cloned).unwrap_err();
+        let err = ConstLru::<Rc<u8>, Rc<u16>, 2, u8>::try_from_iter_strict(cloned).unwrap_err();
+        assert_eq!(err.0, Rc::new(0));
+        assert_eq!(Rc::strong_count(&entries[0].0), 1);
+        assert_eq!(Rc::strong_count(&entries[0].1), 1);
+        assert_eq!(Rc::strong_count(&entries[1].0), 2);
+        assert_eq!(Rc::strong_count(&entries[1].1), 1);
+    }
+    assert_eq!(Rc::strong_count(&entries[0].0), 1);
+    assert_eq!(Rc::strong_count(&entries[0].1), 1);
+    assert_eq!(Rc::strong_count(&entries[1].0), 1);
+    assert_eq!(Rc::strong_count(&entries[1].1), 1);
+}
+
+#[test]
+fn try_from_array_strict_no_double_free_on_failure() {
+    let entries: [(Rc<u8>, Rc<u16>); 2] = [(Rc::new(0), Rc::new(1)), (Rc::new(0), Rc::new(2))];
+    let cloned = entries.clone();
+    assert_eq!(Rc::strong_count(&entries[0].0), 2);
+    assert_eq!(Rc::strong_count(&entries[0].1), 2);
+    assert_eq!(Rc::strong_count(&entries[1].0), 2);
+    assert_eq!(Rc::strong_count(&entries[1].1), 2);
+    {
+        let err = ConstLru::<Rc<u8>, Rc<u16>, 2, u8>::try_from_array_strict(cloned).unwrap_err();
         assert_eq!(err.0, Rc::new(0));
         assert_eq!(Rc::strong_count(&entries[0].0), 2);
         assert_eq!(Rc::strong_count(&entries[0].1), 1);
@@ -223,3 +277,78 @@ fn try_from_no_double_free_on_failure() {
     assert_eq!(Rc::strong_count(&entries[1].0), 1);
     assert_eq!(Rc::strong_count(&entries[1].1), 1);
 }
+
+#[test]
+fn insert_batch_no_double_free() {
+    let k: [Rc<u8>; 4] = core::array::from_fn(|i| Rc::new(u8::try_from(i).unwrap()));
+    let v = Rc::new(0u16);
+    {
+        let mut c: ConstLru<Rc<u8>, Rc<u16>, 2, u8> = ConstLru::new();
+        c.insert(k[0].clone(), v.clone());
+        c.insert(k[1].clone(), v.clone());
+        let mut replaced = Vec::new();
+        // duplicate of 2, existing 1, and 0 and 2 evicted
+        c.insert_batch(
+            [
+                (k[2].clone(), v.clone()),
+                (k[2].clone(), v.clone()),
+                (k[1].clone(), v.clone()),
+                (k[3].clone(), v.clone()),
+            ],
+            |r| replaced.push(r),
+        );
+        assert_eq!(replaced.len(), 4);
+        assert_eq!(Rc::strong_count(&k[0]), 2);
+        assert_eq!(Rc::strong_count(&k[1]), 2);
+        assert_eq!(Rc::strong_count(&k[2]), 2);
+        assert_eq!(Rc::strong_count(&k[3]), 2);
+        assert_eq!(Rc::strong_count(&v), 1 + 2 + 4);
+        drop(replaced);
+        assert_eq!(Rc::strong_count(&k[0]), 1);
+        assert_eq!(Rc::strong_count(&k[2]), 1);
+        assert_eq!(Rc::strong_count(&v), 1 + 2);
+    }
+    for k in k.iter() {
+        assert_eq!(Rc::strong_count(k), 1);
+    }
+    assert_eq!(Rc::strong_count(&v), 1);
+}
+
+#[test]
+fn split_merge_no_double_free() {
+    let k: [Rc<u8>; 4] = core::array::from_fn(|i| Rc::new(u8::try_from(i).unwrap()));
+    let v = Rc::new(0u16);
+    {
+        let mut a: ConstLru<Rc<u8>, Rc<u16>, 3, u8> = ConstLru::new();
+        for k in &k[..3] {
+            a.insert(k.clone(), v.clone());
+        }
+        let mut b = a.split_off(&k[1]);
+        assert_eq!(Rc::strong_count(&k[1]), 2);
+        assert_eq!(Rc::strong_count(&v), 1 + 3);
+
+        // duplicate of 1, 3 evicts 0
+        b.insert(k[3].clone(), v.clone());
+        a.insert(k[1].clone(), v.clone());
+        let mut evicted = Vec::new();
+        a.merge_from(&mut b, |_, _, _| (), |k, v| evicted.push((k, v)));
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(Rc::strong_count(&k[1]), 2);
+        assert_eq!(Rc::strong_count(&v), 1 + 4);
+        drop(evicted);
+        assert_eq!(Rc::strong_count(&v), 1 + 3);
+
+        let mut c: ConstLru<Rc<u8>, Rc<u16>, 3, u8> = ConstLru::new();
+        c.insert(k[0].clone(), v.clone());
+        c.insert(k[1].clone(), v.clone());
+        // duplicate of 1, 0 evicts the LRU of a
+        a.append(&mut c);
+        assert!(c.is_empty());
+        assert_eq!(a.len(), 3);
+        assert_eq!(Rc::strong_count(&v), 1 + 3);
+    }
+    for k in k.iter() {
+        assert_eq!(Rc::strong_count(k), 1);
+    }
+    assert_eq!(Rc::strong_count(&v), 1);
+}
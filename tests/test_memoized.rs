@@ -0,0 +1,82 @@
+use std::mem::MaybeUninit;
+
+use const_lru::{ConstLru, Memoized};
+
+#[test]
+fn get_or_insert_with_evicts_lru() {
+    let mut c: ConstLru<u8, u8, 2, u8> = ConstLru::new();
+    assert_eq!(*c.get_or_insert_with(1, |k| k + 10), 11);
+    assert_eq!(*c.get_or_insert_with(2, |k| k + 10), 12);
+    // hit moves 1 to MRU
+    assert_eq!(*c.get_or_insert_with(1, |_| panic!("should be cached")), 11);
+    assert_eq!(*c.get_or_insert_with(3, |k| k + 10), 13);
+    assert!(c.get_untouched(&2).is_none());
+    assert!(c.iter().map(|(k, _)| *k).eq([3, 1]));
+}
+
+#[test]
+fn get_or_try_insert_with() {
+    let mut c: ConstLru<u8, u8, 2, u8> = ConstLru::new();
+    assert_eq!(c.get_or_try_insert_with(1, |_| Err("nope")), Err("nope"));
+    assert!(c.is_empty());
+    assert_eq!(c.get_or_try_insert_with(1, |k| Ok::<_, ()>(*k)), Ok(&1));
+    assert_eq!(c.get_or_try_insert_with(1, |_| Err("cached")), Ok(&1));
+    assert_eq!(c.len(), 1);
+}
+
+#[test]
+#[should_panic]
+fn get_or_insert_with_zero_cap_panic() {
+    let mut c: ConstLru<u8, u8, 0, u8> = ConstLru::new();
+    c.get_or_insert_with(1, |k| *k);
+}
+
+#[test]
+fn memoized() {
+    let mut calls = Vec::new();
+    let mut m: Memoized<_, u32, String, 2, u8> = Memoized::new(|k: &u32| {
+        calls.push(*k);
+        k.to_string()
+    });
+    assert_eq!(*m.call(1), "1");
+    assert_eq!(*m.call(2), "2");
+    assert_eq!(*m.call(1), "1");
+    assert_eq!(*m.call(3), "3");
+    assert_eq!(*m.call(2), "2");
+    assert_eq!(m.cache().len(), 2);
+    m.cache_mut().clear();
+    assert_eq!(*m.call(2), "2");
+    drop(m);
+    assert_eq!(calls, [1, 2, 3, 2, 2]);
+}
+
+#[test]
+fn memoized_init_in() {
+    type Double = Memoized<fn(&u32) -> u64, u32, u64, 1_000, u16>;
+    let mut slot: Box<MaybeUninit<Double>> = Box::new_uninit();
+    let m = Memoized::init_in(&mut slot, |k: &u32| u64::from(*k) * 2).unwrap();
+    for k in 0..2_000 {
+        assert_eq!(*m.call(k % 1_500), u64::from(k % 1_500) * 2);
+    }
+    assert!(m.cache().is_full());
+
+    type CapTooBig = Memoized<fn(&u32) -> u32, u32, u32, 256, u8>;
+    let mut slot: MaybeUninit<CapTooBig> = MaybeUninit::uninit();
+    assert!(Memoized::init_in(&mut slot, |k: &u32| *k).is_err());
+}
+
+#[test]
+fn memoized_zero_cap_calls_every_time() {
+    let mut calls = 0;
+    let mut m: Memoized<_, u32, String, 0, u8> = Memoized::new(|k: &u32| {
+        calls += 1;
+        k.to_string()
+    });
+    let res = m.call(1);
+    assert!(!res.is_stored());
+    assert_eq!(res.into_owned(), "1");
+    assert_eq!(*m.call(1), "1");
+    assert!(m.cache().is_empty());
+    drop(m);
+    assert_eq!(calls, 2);
+}
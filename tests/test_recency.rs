@@ -0,0 +1,209 @@
+mod common;
+
+use std::mem::MaybeUninit;
+
+use common::Lcg;
+use const_lru::{ConstLru, InsertReplaced};
+
+/// LRU order: 4, 3, 2, 1, 0
+fn lru() -> ConstLru<u8, u16, 5, u8> {
+    (0..5).map(|k| (k, u16::from(k))).collect()
+}
+
+fn keys<const CAP: usize>(c: &ConstLru<u8, u16, CAP, u8>) -> Vec<u8> {
+    c.keys().copied().collect()
+}
+
+#[test]
+fn promote() {
+    let mut c = lru();
+    assert!(c.promote(&0));
+    assert_eq!(keys(&c), [0, 4, 3, 2, 1]);
+    assert!(c.promote(&0));
+    assert_eq!(keys(&c), [0, 4, 3, 2, 1]);
+    assert!(!c.promote(&5));
+    assert_eq!(c.insert(5, 5), Some(InsertReplaced::LruEvicted(1, 1)));
+}
+
+#[test]
+fn demote() {
+    let mut c = lru();
+    assert!(c.demote(&4));
+    assert_eq!(keys(&c), [3, 2, 1, 0, 4]);
+    assert!(c.demote(&4));
+    assert!(c.demote(&2));
+    assert_eq!(keys(&c), [3, 1, 0, 4, 2]);
+    assert!(!c.demote(&5));
+    assert_eq!(c.insert(5, 5), Some(InsertReplaced::LruEvicted(2, 2)));
+    assert_eq!(keys(&c), [5, 3, 1, 0, 4]);
+}
+
+#[test]
+fn demote_not_full() {
+    let mut c: ConstLru<u8, u16, 5, u8> = [(2, 2), (1, 1), (0, 0)].into();
+    assert!(c.demote(&2));
+    assert_eq!(keys(&c), [1, 0, 2]);
+    // free slots after the tail are still usable
+    assert!(c.insert(3, 3).is_none());
+    assert!(c.insert(4, 4).is_none());
+    assert_eq!(keys(&c), [4, 3, 1, 0, 2]);
+    assert_eq!(c.insert(5, 5), Some(InsertReplaced::LruEvicted(2, 2)));
+}
+
+#[test]
+fn touch_many() {
+    let mut c = lru();
+    assert_eq!(c.touch_many([&1, &9, &0]), 2);
+    assert_eq!(keys(&c), [0, 1, 4, 3, 2]);
+    assert_eq!(c.touch_many(&[2, 3]), 2);
+    assert_eq!(keys(&c), [3, 2, 0, 1, 4]);
+    assert_eq!(c.touch_many(core::iter::empty::<&u8>()), 0);
+}
+
+#[test]
+fn set_position() {
+    let mut c = lru();
+    assert!(c.set_position(&4, 2));
+    assert_eq!(keys(&c), [3, 2, 4, 1, 0]);
+    assert!(c.set_position(&0, 1));
+    assert_eq!(keys(&c), [3, 0, 2, 4, 1]);
+    assert!(c.set_position(&3, 3));
+    assert_eq!(keys(&c), [0, 2, 4, 3, 1]);
+    assert!(c.set_position(&2, 0));
+    assert_eq!(keys(&c), [2, 0, 4, 3, 1]);
+    assert!(c.set_position(&2, 200));
+    assert_eq!(keys(&c), [0, 4, 3, 1, 2]);
+    assert!(!c.set_position(&5, 0));
+    assert!(c.iter().rev().map(|(k, _)| *k).eq([2, 1, 3, 4, 0]));
+}
+
+#[test]
+fn set_position_matches_vec() {
+    const CAP: usize = 13;
+    let mut c: ConstLru<u8, u16, CAP, u8> = ConstLru::new();
+    let mut model: Vec<u8> = Vec::new();
+    let mut rng = Lcg::new(12345);
+    for _ in 0..2_000 {
+        let k = u8::try_from(rng.below(20)).unwrap();
+        match rng.below(3) {
+            0 => {
+                if let Some(InsertReplaced::LruEvicted(evicted, _)) = c.insert(k, 0) {
+                    assert_eq!(model.pop(), Some(evicted));
+                }
+                model.retain(|m| *m != k);
+                model.insert(0, k);
+            }
+            1 => {
+                let n = u8::try_from(rng.below(15)).unwrap();
+                let present = model.iter().position(|m| *m == k);
+                assert_eq!(c.set_position(&k, n), present.is_some());
+                if let Some(i) = present {
+                    model.remove(i);
+                    let n = usize::from(n).min(model.len());
+                    model.insert(n, k);
+                }
+            }
+            _ => {
+                assert_eq!(c.remove(&k).is_some(), model.contains(&k));
+                model.retain(|m| *m != k);
+            }
+        }
+        assert_eq!(keys(&c), model);
+        assert!(c
+            .iter()
+            .rev()
+            .map(|(k, _)| *k)
+            .eq(model.iter().rev().copied()));
+    }
+}
+
+#[test]
+fn insert_cold() {
+    let mut c: ConstLru<u8, u16, 3, u8> = ConstLru::new();
+    assert!(c.insert(0, 0).is_none());
+    assert!(c.insert_cold(1, 1).is_none());
+    assert!(c.insert(2, 2).is_none());
+    assert_eq!(keys(&c), [2, 0, 1]);
+    // evicts the existing tail, then is evicted next
+    assert_eq!(c.insert_cold(3, 3), Some(InsertReplaced::LruEvicted(1, 1)));
+    assert_eq!(keys(&c), [2, 0, 3]);
+    assert_eq!(c.insert(4, 4), Some(InsertReplaced::LruEvicted(3, 3)));
+    // existing entries keep their position
+    assert_eq!(c.insert_cold(4, 40), Some(InsertReplaced::OldValue(4)));
+    assert_eq!(keys(&c), [4, 2, 0]);
+    assert_eq!(c.get_untouched(&4), Some(&40));
+    assert!(c.iter_key_order().map(|(k, _)| *k).eq([0, 2, 4]));
+
+    let mut c: ConstLru<u8, u16, 0, u8> = ConstLru::new();
+    assert!(c.insert_cold(0, 0).is_none());
+}
+
+#[test]
+fn insert_at_fraction() {
+    let mut c = lru();
+    assert!(c.remove(&2).is_some());
+    // 5 entries after inserting, so position floor(4 * 0.5) = 2
+    assert!(c.insert_at_fraction(5, 5, 0.5).is_none());
+    assert_eq!(keys(&c), [4, 3, 5, 1, 0]);
+    assert_eq!(
+        c.insert_at_fraction(6, 6, 0.375),
+        Some(InsertReplaced::LruEvicted(0, 0))
+    );
+    assert_eq!(keys(&c), [4, 6, 3, 5, 1]);
+    assert_eq!(
+        c.insert_at_fraction(7, 7, 0.0),
+        Some(InsertReplaced::LruEvicted(1, 1))
+    );
+    assert_eq!(keys(&c), [7, 4, 6, 3, 5]);
+    assert_eq!(
+        c.insert_at_fraction(8, 8, 2.0),
+        Some(InsertReplaced::LruEvicted(5, 5))
+    );
+    assert_eq!(
+        c.insert_at_fraction(9, 9, f32::NAN),
+        Some(InsertReplaced::LruEvicted(8, 8))
+    );
+    assert_eq!(keys(&c), [9, 7, 4, 6, 3]);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn insert_at_fraction_large_cap() {
+    const CAP: usize = 1 << 20;
+    type Lru = ConstLru<u32, (), CAP, u32>;
+    let mut slot: Box<MaybeUninit<Lru>> = Box::new_uninit();
+    let c = ConstLru::init_in(&mut slot).unwrap();
+    for k in 0..u32::try_from(CAP).unwrap() - 1 {
+        c.insert(k, ());
+    }
+    // (CAP - 1) * 0.753 = 789576.99..., which rounds up to the next position in f32
+    assert!(c.insert_at_fraction(u32::MAX, (), 0.753).is_none());
+    assert_eq!(c.iter().position(|(k, _)| *k == u32::MAX), Some(789_576));
+    assert_eq!(
+        c.insert_at_fraction(u32::MAX - 1, (), 1.0),
+        Some(InsertReplaced::LruEvicted(0, ()))
+    );
+    assert_eq!(c.iter().next_back(), Some((&(u32::MAX - 1), &())));
+    let _c = unsafe { slot.assume_init() };
+}
+
+#[test]
+fn vacant_entry_insert_cold() {
+    use const_lru::Entry;
+
+    let mut c = lru();
+    let Entry::Vacant(e) = c.entry(5) else {
+        panic!("5 should be vacant")
+    };
+    let (v, evicted) = e.insert_cold(5);
+    *v += 1;
+    assert_eq!(evicted, Some((0, 0)));
+    assert_eq!(keys(&c), [4, 3, 2, 1, 5]);
+    assert_eq!(c.get_untouched(&5), Some(&6));
+
+    let Entry::Vacant(e) = c.entry(6) else {
+        panic!("6 should be vacant")
+    };
+    assert_eq!(e.insert_at_fraction(6, 0.5), (&mut 6, Some((5, 6))));
+    assert_eq!(keys(&c), [4, 3, 6, 2, 1]);
+}
@@ -0,0 +1,185 @@
+use std::mem::MaybeUninit;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+mod common;
+
+use common::Lcg;
+use const_lru::{ConstLru, InsertReplaced, NaturalOrder, SeqLockConstLru};
+
+#[test]
+fn matches_const_lru() {
+    const CAP: usize = 20;
+    // big enough to never drop a recorded read in a single thread
+    let s: SeqLockConstLru<u16, u16, CAP, u8, NaturalOrder, 5_000> = SeqLockConstLru::new();
+    let mut c: ConstLru<u16, u16, CAP, u8> = ConstLru::new();
+    let mut rng = Lcg::new(7);
+
+    for _ in 0..5_000 {
+        let k = rng.next_u16() % 50;
+        let v = rng.next_u16();
+        match rng.next_u16() % 6 {
+            0 | 1 => assert_eq!(s.insert(k, v), c.insert(k, v)),
+            2 => assert_eq!(s.remove(&k), c.remove(&k)),
+            3 => s.flush_reads(),
+            4 => assert_eq!(s.get_untouched(&k).as_ref(), c.get_untouched(&k)),
+            _ => assert_eq!(s.get(&k).as_ref(), c.get(&k)),
+        }
+        // only checks what's published, since `with` would republish everything
+        assert_eq!(s.len(), c.len());
+        for k in 0..50 {
+            assert_eq!(s.get_untouched(&k).as_ref(), c.get_untouched(&k));
+        }
+    }
+    s.with(|inner| assert!(inner.iter().eq(c.iter())));
+}
+
+/// Entries that readers see, in key order
+fn published<const CAP: usize>(s: &SeqLockConstLru<u8, u8, CAP, u8>) -> Vec<(u8, u8)> {
+    (0..=u8::MAX)
+        .filter_map(|k| s.get_untouched(&k).map(|v| (k, v)))
+        .collect()
+}
+
+#[test]
+fn with_publishes_every_change() {
+    let s: SeqLockConstLru<u8, u8, 4, u8> = SeqLockConstLru::new();
+    for k in 0..4 {
+        s.insert(k, k);
+    }
+    s.with(|c| {
+        *c.get_mut_untouched(&1).unwrap() = 10;
+        c.remove(&2);
+        c.insert(5, 5);
+        c.insert(6, 6);
+    });
+    assert_eq!(published(&s), [(1, 10), (3, 3), (5, 5), (6, 6)]);
+    s.clear();
+    assert!(s.is_empty());
+    assert!(s.insert(2, 20).is_none());
+    assert_eq!(published(&s), [(2, 20)]);
+}
+
+#[test]
+fn reads_applied_on_next_write() {
+    let s: SeqLockConstLru<u8, u8, 3, u8> = SeqLockConstLru::new();
+    for k in 0..3 {
+        s.insert(k, k);
+    }
+    assert_eq!(s.get(&0), Some(0));
+    // 0 would be the LRU if the read wasn't applied
+    assert_eq!(s.insert(3, 3), Some(InsertReplaced::LruEvicted(1, 1)));
+    s.with(|c| assert!(c.iter().map(|(k, _)| *k).eq([3, 0, 2])));
+}
+
+#[test]
+fn full_buffer_drops_reads() {
+    let s: SeqLockConstLru<u8, u8, 3, u8, NaturalOrder, 1> = SeqLockConstLru::new();
+    for k in 0..3 {
+        s.insert(k, k);
+    }
+    assert_eq!(s.get(&1), Some(1));
+    assert_eq!(s.get(&0), Some(0));
+    s.flush_reads();
+    s.with(|c| assert!(c.iter().map(|(k, _)| *k).eq([1, 2, 0])));
+    assert_eq!(s.get(&0), Some(0));
+    s.flush_reads();
+    s.with(|c| assert!(c.iter().map(|(k, _)| *k).eq([0, 1, 2])));
+}
+
+#[test]
+fn empty_and_zero_cap() {
+    let s: SeqLockConstLru<u8, u8, 0, u8> = SeqLockConstLru::default();
+    assert!(s.insert(1, 1).is_none());
+    assert!(s.get(&1).is_none());
+    assert!(s.is_empty());
+
+    let s: SeqLockConstLru<u8, u8, 5, u8> = SeqLockConstLru::new();
+    assert!(s.get(&1).is_none());
+    s.insert(1, 1);
+    s.clear();
+    assert!(s.get_untouched(&1).is_none());
+    assert_eq!(s.cap(), 5);
+}
+
+#[test]
+fn reads_dont_wait_for_writes() {
+    let s: SeqLockConstLru<u8, u8, 3, u8> = SeqLockConstLru::new();
+    s.insert(1, 1);
+    s.with(|c| {
+        c.insert(2, 2);
+        // would spin forever if readers waited for the write to finish
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                assert_eq!(s.get_untouched(&1), Some(1));
+                assert_eq!(s.get_untouched(&2), None);
+            });
+        });
+    });
+    assert_eq!(s.get_untouched(&2), Some(2));
+}
+
+#[test]
+fn panicking_write_republishes() {
+    let s: SeqLockConstLru<u8, u8, 3, u8> = SeqLockConstLru::new();
+    s.insert(1, 1);
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        s.with(|c| {
+            c.insert(2, 2);
+            c.remove(&1);
+            panic!("write panicked");
+        })
+    }));
+    assert!(res.is_err());
+    assert_eq!(s.get_untouched(&1), None);
+    assert_eq!(s.get_untouched(&2), Some(2));
+    assert_eq!(s.len(), 1);
+    s.insert(3, 3);
+    assert_eq!(s.get_untouched(&3), Some(3));
+}
+
+/// Values are `[x, !x]`, so a torn read would break the invariant
+#[test]
+fn concurrent_reads_not_torn() {
+    // miri is too slow for many writes of a big cache
+    const CAP: usize = if cfg!(miri) { 16 } else { 256 };
+    type Cache = SeqLockConstLru<u32, [u64; 2], CAP, u16>;
+    let mut slot: Box<MaybeUninit<Cache>> = Box::new_uninit();
+    let s = &*SeqLockConstLru::init_in(&mut slot).unwrap();
+    for k in 0..CAP as u32 {
+        s.insert(k, [k.into(), !u64::from(k)]);
+    }
+    let is_done = AtomicBool::new(false);
+    thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                let mut k = 0;
+                while !is_done.load(Ordering::Relaxed) {
+                    if let Some([a, b]) = s.get(&(k % (2 * CAP as u32))) {
+                        assert_eq!(a, !b);
+                    }
+                    k = k.wrapping_add(7);
+                }
+            });
+        }
+        scope.spawn(|| {
+            let writes = if cfg!(miri) { 100 } else { 20_000u64 };
+            for i in 0..writes {
+                let k = (i % (2 * CAP as u64)) as u32;
+                if i % 3 == 0 {
+                    s.remove(&k);
+                } else {
+                    s.insert(k, [i, !i]);
+                }
+            }
+            is_done.store(true, Ordering::Relaxed);
+        });
+    });
+    s.with(|c| {
+        for (_, [a, b]) in c.iter() {
+            assert_eq!(*a, !*b);
+        }
+        assert!(c.iter_key_order().map(|(k, _)| k).is_sorted());
+    });
+}
@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+use std::mem::MaybeUninit;
+use std::thread;
+
+use const_lru::sharded::{RangeRouter, ShardRouter};
+use const_lru::{InsertReplaced, NaturalOrder, ShardedConstLru};
+
+#[test]
+fn routes_to_same_shard() {
+    let c: ShardedConstLru<u16, u16, 8, 10, u8> = ShardedConstLru::new();
+    for k in 0..80 {
+        let shard = c.shard_index(&k);
+        assert!(shard < 8);
+        assert_eq!(shard, c.shard_index(&k));
+        c.insert(k, k);
+        assert_eq!(
+            c.with_shard_at(shard, |s| s.get_untouched(&k).copied()),
+            Some(k)
+        );
+    }
+    let counts: Vec<usize> = (0..8)
+        .map(|i| c.with_shard_at(i, |s| s.len().into()))
+        .collect();
+    assert_eq!(counts.iter().sum::<usize>(), c.len());
+    // hashing spreads sequential keys over all shards
+    assert!(counts.iter().all(|n| *n > 0));
+}
+
+#[test]
+fn evicts_per_shard() {
+    let c: ShardedConstLru<u16, u16, 4, 3, u8> = ShardedConstLru::new();
+    let mut shard_contents: [Vec<u16>; 4] = Default::default();
+    for k in 0..100 {
+        let shard = c.shard_index(&k);
+        let res = c.insert(k, k);
+        let contents = &mut shard_contents[shard];
+        if contents.len() == 3 {
+            let lru = contents.remove(0);
+            assert_eq!(res, Some(InsertReplaced::LruEvicted(lru, lru)));
+        } else {
+            assert!(res.is_none());
+        }
+        contents.push(k);
+    }
+    assert_eq!(c.len(), 12);
+    assert!(c.len() <= c.cap());
+    for contents in shard_contents.iter() {
+        for k in contents {
+            assert_eq!(c.get_untouched_cloned(k), Some(*k));
+        }
+    }
+}
+
+#[test]
+fn range_router() {
+    let router: RangeRouter<u16, 3> = RangeRouter::new([10, 20, 30]);
+    assert_eq!(router.route(&0, 4), 0);
+    assert_eq!(router.route(&9, 4), 0);
+    assert_eq!(router.route(&10, 4), 1);
+    assert_eq!(router.route(&29, 4), 2);
+    assert_eq!(router.route(&30, 4), 3);
+    assert_eq!(router.route(&u16::MAX, 4), 3);
+    // extra bounds go to the last shard
+    assert_eq!(router.route(&30, 2), 1);
+
+    let c: ShardedConstLru<u16, u16, 4, 10, u8, NaturalOrder, _> =
+        ShardedConstLru::with_router(router);
+    for k in 0..40 {
+        c.insert(k, k);
+    }
+    for i in 0..4 {
+        let keys: Vec<u16> = c.with_shard_at(i, |s| s.iter_key_order().map(|(k, _)| *k).collect());
+        assert!(keys.into_iter().eq((i as u16 * 10)..(i as u16 * 10 + 10)));
+    }
+}
+
+#[test]
+#[should_panic]
+fn range_router_unsorted() {
+    let _router: RangeRouter<u16, 3> = RangeRouter::new([10, 30, 20]);
+}
+
+#[test]
+fn borrowed_keys() {
+    let c: ShardedConstLru<String, u8, 4, 4, u8> = ShardedConstLru::new();
+    c.insert("hello".to_owned(), 1);
+    assert_eq!(c.shard_index("hello"), c.shard_index(&"hello".to_owned()));
+    assert_eq!(c.get_cloned("hello"), Some(1));
+    assert_eq!(c.remove("hello"), Some(1));
+    assert!(c.is_empty());
+}
+
+#[test]
+fn concurrent_inserts_and_gets() {
+    const THREADS: u32 = 8;
+    const KEYS_PER_THREAD: u32 = 1_000;
+
+    let mut slot: Box<MaybeUninit<ShardedConstLru<u32, u32, 16, 1_000, u16>>> = Box::new_uninit();
+    let c = &*ShardedConstLru::init_in(&mut slot).unwrap();
+    thread::scope(|s| {
+        for t in 0..THREADS {
+            s.spawn(move || {
+                for k in (t * KEYS_PER_THREAD)..((t + 1) * KEYS_PER_THREAD) {
+                    assert!(c.insert(k, k + 1).is_none());
+                    assert_eq!(c.get_cloned(&k), Some(k + 1));
+                    c.with_shard(&k, |shard| *shard.get_mut(&k).unwrap() += 1);
+                }
+            });
+        }
+    });
+    assert_eq!(c.len(), (THREADS * KEYS_PER_THREAD) as usize);
+    for k in 0..(THREADS * KEYS_PER_THREAD) {
+        assert_eq!(c.get_untouched_cloned(&k), Some(k + 2));
+    }
+
+    // contended counters
+    c.clear();
+    thread::scope(|s| {
+        for _ in 0..THREADS {
+            s.spawn(move || {
+                for i in 0..KEYS_PER_THREAD {
+                    let k = i % 10;
+                    c.with_shard(&k, |shard| *shard.entry(k).or_insert(0) += 1);
+                }
+            });
+        }
+    });
+    let counts: HashMap<u32, u32> = (0..10).map(|k| (k, c.get_cloned(&k).unwrap())).collect();
+    assert!(counts
+        .values()
+        .all(|n| *n == THREADS * KEYS_PER_THREAD / 10));
+}
+
+#[test]
+fn shard_mut() {
+    let mut c: ShardedConstLru<u8, u8, 2, 2, u8> = ShardedConstLru::default();
+    c.insert(1, 1);
+    let shard = c.shard_index(&1);
+    assert_eq!(c.shard_mut(shard).remove(&1), Some(1));
+    assert!(c.is_empty());
+}
@@ -0,0 +1,66 @@
+#![cfg(feature = "critical-section")]
+
+use std::thread;
+
+use const_lru::{InsertReplaced, SharedConstLru};
+
+static CACHE: SharedConstLru<u32, u32, 100, u8> = SharedConstLru::new();
+
+#[test]
+fn static_from_threads() {
+    thread::scope(|s| {
+        for t in 0..4 {
+            s.spawn(move || {
+                for k in (t * 25)..((t + 1) * 25) {
+                    assert!(CACHE.insert(k, k).is_none());
+                    CACHE.with(|c| *c.get_mut(&k).unwrap() += 1);
+                }
+            });
+        }
+    });
+    assert_eq!(CACHE.len(), 100);
+    for k in 0..100 {
+        assert_eq!(CACHE.get_untouched_cloned(&k), Some(k + 1));
+    }
+    assert_eq!(
+        CACHE.insert(100, 100),
+        Some(InsertReplaced::LruEvicted(0, 1))
+    );
+    CACHE.clear();
+    assert!(CACHE.is_empty());
+}
+
+#[test]
+fn lazy_init() {
+    let c: SharedConstLru<String, String, 2, u8> = SharedConstLru::default();
+    // dropping without ever accessing must not drop uninitialized memory
+    drop(c);
+
+    let c: SharedConstLru<String, String, 2, u8> = SharedConstLru::new();
+    assert!(c.is_empty());
+    assert!(c.insert("a".to_owned(), "1".to_owned()).is_none());
+    assert!(c.insert("b".to_owned(), "2".to_owned()).is_none());
+    assert_eq!(c.get_cloned("a").as_deref(), Some("1"));
+    assert_eq!(
+        c.insert("c".to_owned(), "3".to_owned()),
+        Some(InsertReplaced::LruEvicted("b".to_owned(), "2".to_owned()))
+    );
+    assert_eq!(c.remove("a").as_deref(), Some("1"));
+    assert_eq!(c.len(), 1);
+}
+
+#[test]
+fn with_cs() {
+    let c: SharedConstLru<u8, u8, 4, u8> = SharedConstLru::new();
+    critical_section::with(|cs| {
+        c.with_cs(cs, |c| c.insert(1, 2));
+        assert_eq!(c.with_cs(cs, |c| c.get(&1).copied()), Some(2));
+    });
+}
+
+#[test]
+#[should_panic]
+fn reentrant_access() {
+    let c: SharedConstLru<u8, u8, 4, u8> = SharedConstLru::new();
+    c.with(|_| c.insert(1, 2));
+}
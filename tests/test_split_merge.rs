@@ -0,0 +1,212 @@
+mod common;
+
+use common::Lcg;
+use const_lru::ConstLru;
+
+type Lru = ConstLru<u8, u16, 8, u8>;
+
+/// MRU-first entries
+type Entries = Vec<(u8, u16)>;
+
+fn entries(c: &Lru) -> Entries {
+    c.iter().map(|(k, v)| (*k, *v)).collect()
+}
+
+/// Checks that every entry can be looked up and the key order is sorted
+fn check_index(c: &Lru) {
+    assert!(c.keys_key_order().is_sorted());
+    for (k, v) in c.iter() {
+        assert_eq!(c.get_untouched(k), Some(v));
+    }
+}
+
+#[test]
+fn split_off() {
+    let mut c: Lru = [(6, 6), (2, 2), (5, 5), (4, 4), (1, 1), (3, 3)].into();
+    let upper = c.split_off(&4);
+    assert_eq!(entries(&c), [(2, 2), (1, 1), (3, 3)]);
+    assert_eq!(entries(&upper), [(6, 6), (5, 5), (4, 4)]);
+    check_index(&c);
+    check_index(&upper);
+
+    // both can still insert and evict independently
+    let mut upper = upper;
+    for k in 10..16 {
+        upper.insert(k, k.into());
+    }
+    assert!(upper.is_full());
+    assert_eq!(upper.keys().next_back(), Some(&5));
+    check_index(&upper);
+    c.insert(0, 0);
+    assert_eq!(c.keys().next(), Some(&0));
+    check_index(&c);
+}
+
+#[test]
+fn split_off_ends() {
+    let mut c: Lru = [(2, 2), (1, 1)].into();
+    assert!(c.split_off(&3).is_empty());
+    assert_eq!(c.len(), 2);
+
+    let all = c.split_off(&0);
+    assert!(c.is_empty());
+    assert_eq!(entries(&all), [(2, 2), (1, 1)]);
+
+    let mut zero: ConstLru<u8, u16, 0, u8> = ConstLru::new();
+    assert!(zero.split_off(&0).is_empty());
+}
+
+#[test]
+fn split_off_full() {
+    let mut c: Lru = (0..8).map(|k| (k, u16::from(k))).collect();
+    let mut upper = c.split_off(&5);
+    assert_eq!(c.len() + upper.len(), 8);
+    upper.insert(20, 20);
+    c.insert(20, 20);
+    check_index(&c);
+    check_index(&upper);
+}
+
+#[test]
+fn append() {
+    let mut a: Lru = [(4, 4), (3, 3), (2, 2), (1, 1)].into();
+    let mut b: Lru = [(6, 6), (2, 20), (5, 5)].into();
+    a.append(&mut b);
+    assert!(b.is_empty());
+    assert_eq!(
+        entries(&a),
+        [(6, 6), (2, 20), (5, 5), (4, 4), (3, 3), (1, 1)]
+    );
+    check_index(&a);
+
+    // b is still usable
+    b.insert(1, 1);
+    assert_eq!(entries(&b), [(1, 1)]);
+}
+
+#[test]
+fn append_evicts() {
+    let mut a: Lru = (0..8).map(|k| (k, u16::from(k))).collect();
+    let mut b: Lru = (10..16).map(|k| (k, u16::from(k))).collect();
+    a.append(&mut b);
+    assert!(a.keys().eq(&[15, 14, 13, 12, 11, 10, 7, 6]));
+    check_index(&a);
+}
+
+#[test]
+fn merge_from() {
+    let mut a: Lru = [(5, 5), (4, 4), (3, 3), (2, 2), (1, 1)].into();
+    let mut b: Lru = [(12, 12), (11, 11), (4, 40), (10, 10)].into();
+    let mut evicted = Vec::new();
+    a.merge_from(&mut b, |_, a, b| *a += b, |k, v| evicted.push((k, v)));
+    assert!(b.is_empty());
+    // 4 keeps a's position
+    assert_eq!(
+        entries(&a),
+        [
+            (5, 5),
+            (12, 12),
+            (4, 44),
+            (11, 11),
+            (3, 3),
+            (10, 10),
+            (2, 2),
+            (1, 1)
+        ]
+    );
+    assert!(evicted.is_empty());
+    check_index(&a);
+}
+
+#[test]
+fn merge_from_evicts_interleaved_tail() {
+    let mut a: Lru = (0..8).map(|k| (k, u16::from(k))).collect();
+    let mut b: Lru = (10..14).map(|k| (k, u16::from(k))).collect();
+    let mut evicted = Vec::new();
+    a.merge_from(&mut b, |_, _, _| unreachable!(), |k, _| evicted.push(k));
+    assert!(a.keys().eq(&[7, 13, 6, 12, 5, 11, 4, 10]));
+    evicted.sort_unstable();
+    assert_eq!(evicted, [0, 1, 2, 3]);
+    check_index(&a);
+
+    let mut zero: ConstLru<u8, u16, 0, u8> = ConstLru::new();
+    zero.merge_from(&mut ConstLru::new(), |_, _, _| (), |_, _| unreachable!());
+}
+
+/// Expected (entries, evicted) of merging `b` into `a` given MRU-first entries,
+/// interleaving them if `interleave` or putting all of `b` first otherwise
+fn merge_model(a: &[(u8, u16)], b: &[(u8, u16)], interleave: bool) -> (Entries, Entries) {
+    let mut a = a.to_vec();
+    let mut b = b.to_vec();
+    if interleave {
+        for (k, v) in a.iter_mut() {
+            if let Some(p) = b.iter().position(|(bk, _)| bk == k) {
+                *v = v.wrapping_add(b.remove(p).1);
+            }
+        }
+    } else {
+        a.retain(|(k, _)| b.iter().all(|(bk, _)| bk != k));
+    }
+    let mut combined = Vec::new();
+    if interleave {
+        for n in 0..a.len().max(b.len()) {
+            combined.extend(a.get(n));
+            combined.extend(b.get(n));
+        }
+    } else {
+        combined.extend(b);
+        combined.extend(a);
+    }
+    let evicted = combined.split_off(combined.len().min(8));
+    (combined, evicted)
+}
+
+#[test]
+fn split_merge_match_model() {
+    let mut rng = Lcg::new(12345);
+    for round in 0..1_000u16 {
+        let mut a = Lru::new();
+        let mut b = Lru::new();
+        for c in [&mut a, &mut b] {
+            for _ in 0..rng.below(12) {
+                let k = u8::try_from(rng.below(16)).unwrap();
+                c.insert(k, round.wrapping_mul(16).wrapping_add(k.into()));
+            }
+            // move the gap of the index around
+            c.remove(&u8::try_from(rng.below(16)).unwrap());
+        }
+        let (ea, eb) = (entries(&a), entries(&b));
+
+        let split_at = u8::try_from(rng.below(17)).unwrap();
+        let mut lower = a.clone();
+        let upper = lower.split_off(&split_at);
+        let expected: Vec<_> = ea.iter().copied().filter(|&(k, _)| k < split_at).collect();
+        assert_eq!(entries(&lower), expected, "round {round}");
+        let expected: Vec<_> = ea.iter().copied().filter(|&(k, _)| k >= split_at).collect();
+        assert_eq!(entries(&upper), expected, "round {round}");
+        check_index(&lower);
+        check_index(&upper);
+
+        let interleave = rng.below(2) == 0;
+        let mut evicted = Vec::new();
+        if interleave {
+            a.merge_from(
+                &mut b,
+                |_, a, b| *a = a.wrapping_add(b),
+                |k, v| evicted.push((k, v)),
+            );
+        } else {
+            a.append(&mut b);
+        }
+        let (expected, expected_evicted) = merge_model(&ea, &eb, interleave);
+        assert_eq!(entries(&a), expected, "round {round}");
+        if interleave {
+            evicted.sort_unstable();
+            let mut expected_evicted = expected_evicted;
+            expected_evicted.sort_unstable();
+            assert_eq!(evicted, expected_evicted, "round {round}");
+        }
+        assert!(b.is_empty());
+        check_index(&a);
+    }
+}
@@ -3,8 +3,9 @@
 //! Figure out how to re-enable miri for this and remove all the `#[cfg_attr(miri, ignore)]`s
 
 use std::alloc::{alloc, Layout};
+use std::mem::MaybeUninit;
 
-use const_lru::ConstLru;
+use const_lru::{ConstHashLru, ConstLru};
 
 // ~400 MB
 type BigConstLru = ConstLru<usize, usize, 10_000_000>;
@@ -54,3 +55,34 @@ fn clone_to_alloc_doesnt_stack_overflow() {
     };
     assert!(cloned.insert(1, 2).is_none());
 }
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn init_in_doesnt_stack_overflow() {
+    let mut slot: Box<MaybeUninit<BigConstLru>> = Box::new_uninit();
+    let c = ConstLru::init_in(&mut slot).unwrap();
+    assert!(c.insert(1, 2).is_none());
+    let c = unsafe { slot.assume_init() };
+    let mut cloned_slot: Box<MaybeUninit<BigConstLru>> = Box::new_uninit();
+    let cloned = c.clone_into_uninit(&mut cloned_slot);
+    assert_eq!(*cloned.get(&1).unwrap(), 2);
+    let _cloned = unsafe { cloned_slot.assume_init() };
+}
+
+// ~32 MB
+type BigConstHashLru = ConstHashLru<u32, u32, 1_000_000, u32>;
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn hash_init_in_clear_clone_dont_stack_overflow() {
+    let mut slot: Box<MaybeUninit<BigConstHashLru>> = Box::new_uninit();
+    let c = ConstHashLru::init_in(&mut slot).unwrap();
+    assert!(c.insert(1, 2).is_none());
+    c.clear();
+    assert!(c.insert(1, 2).is_none());
+    let c = unsafe { slot.assume_init() };
+    let mut cloned_slot: Box<MaybeUninit<BigConstHashLru>> = Box::new_uninit();
+    let cloned = c.clone_into_uninit(&mut cloned_slot);
+    assert_eq!(*cloned.get(&1).unwrap(), 2);
+    let _cloned = unsafe { cloned_slot.assume_init() };
+}
@@ -0,0 +1,163 @@
+use core::cmp::Ordering;
+use std::mem::MaybeUninit;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::rc::Rc;
+
+mod common;
+
+use common::{Lcg, PanicsAfter};
+use const_lru::tree::Entry;
+use const_lru::{Comparator, ConstLruTree, InsertReplaced};
+
+#[test]
+fn randomized_ops() {
+    const CAP: usize = 32;
+    let mut c: ConstLruTree<u16, u16, CAP, u16> = ConstLruTree::new();
+    let mut rng = Lcg::new(6789);
+    common::randomized_ops(&mut c, CAP, &mut rng, 80, 5_000, |_, _, _| ());
+}
+
+#[test]
+fn sorted_inserts_and_removes() {
+    let mut slot: Box<MaybeUninit<ConstLruTree<u16, u16, 10_000, u16>>> = Box::new_uninit();
+    let c = ConstLruTree::init_in(&mut slot).unwrap();
+    for k in 0..10_000 {
+        assert!(c.insert(k, k).is_none());
+    }
+    assert!(c.is_full());
+    assert!(c.iter_key_order().map(|(k, _)| *k).eq(0..10_000));
+    for k in (0..10_000).step_by(2) {
+        assert_eq!(c.remove(&k).unwrap(), k);
+    }
+    assert!(c
+        .iter_key_order()
+        .map(|(k, _)| *k)
+        .eq((1..10_000).step_by(2)));
+    for k in (1..10_000).rev().step_by(2) {
+        assert_eq!(*c.get_untouched(&k).unwrap(), k);
+        assert_eq!(c.remove(&k).unwrap(), k);
+    }
+    assert!(c.is_empty());
+    assert!(c.iter_key_order().next().is_none());
+}
+
+struct Reverse;
+
+impl Comparator<u8> for Reverse {
+    fn cmp(a: &u8, b: &u8) -> Ordering {
+        b.cmp(a)
+    }
+}
+
+#[test]
+fn comparator() {
+    let mut c: ConstLruTree<u8, u8, 3, u8, Reverse> = ConstLruTree::new();
+    c.insert(1, 1);
+    c.insert(3, 3);
+    c.insert(2, 2);
+    let keys: Vec<u8> = c.iter_key_order().map(|(k, _)| *k).collect();
+    assert_eq!(keys, [3, 2, 1]);
+    assert_eq!(c.insert(4, 4).unwrap(), InsertReplaced::LruEvicted(1, 1));
+    let keys: Vec<u8> = c.iter_key_order().map(|(k, _)| *k).collect();
+    assert_eq!(keys, [4, 3, 2]);
+}
+
+#[test]
+fn panicking_comparator_keeps_tree_consistent() {
+    // panic at every comparison an evicting insert makes in turn
+    for n in 1.. {
+        let mut c: ConstLruTree<u16, u16, 8, u16, PanicsAfter> = ConstLruTree::new();
+        for k in 0..8 {
+            c.insert(k * 2, k);
+        }
+        PanicsAfter::arm(n);
+        let res = catch_unwind(AssertUnwindSafe(|| c.insert(7, 7)));
+        PanicsAfter::disarm();
+
+        let mut keys: Vec<u16> = c.iter().map(|(k, _)| *k).collect();
+        keys.sort_unstable();
+        assert!(c.iter_key_order().map(|(k, _)| *k).eq(keys.iter().copied()));
+        c.insert(100, 100);
+        for k in keys.iter().skip(1) {
+            assert!(c.remove(k).is_some(), "{k} after panic at comparison {n}");
+        }
+        assert!(c.iter().map(|(k, _)| *k).eq([100]));
+        if res.is_ok() {
+            break;
+        }
+    }
+}
+
+#[test]
+fn borrowed_lookup() {
+    let mut c: ConstLruTree<String, u8, 3, u8> = ConstLruTree::new();
+    c.insert("hello".to_owned(), 1);
+    assert_eq!(*c.get("hello").unwrap(), 1);
+    *c.get_mut_untouched("hello").unwrap() = 2;
+    assert_eq!(c.remove("hello").unwrap(), 2);
+    assert!(c.get("hello").is_none());
+}
+
+#[test]
+fn entry() {
+    let mut c: ConstLruTree<u8, u8, 2, u8> = ConstLruTree::new();
+    *c.entry(1).or_insert(1) += 10;
+    assert_eq!(*c.entry(2).or_default(), 0);
+
+    let Entry::Occupied(mut entry) = c.entry(1) else {
+        panic!("not occupied")
+    };
+    assert_eq!(*entry.get(), 11);
+    assert_eq!(entry.insert(5), 11);
+    assert_eq!(entry.remove_entry(), (1, 5));
+    assert_eq!(c.len(), 1);
+
+    c.insert(3, 3);
+    let Entry::Vacant(entry) = c.entry(0) else {
+        panic!("not vacant")
+    };
+    let (m, evicted) = entry.insert(4);
+    assert_eq!(*m, 4);
+    assert_eq!(evicted.unwrap(), (2, 0));
+    let keys: Vec<u8> = c.iter_key_order().map(|(k, _)| *k).collect();
+    assert_eq!(keys, [0, 3]);
+}
+
+#[test]
+#[should_panic]
+fn zero_cap_entry_panic() {
+    let mut c: ConstLruTree<u8, u8, 0, u8> = ConstLruTree::new();
+    c.entry(1);
+}
+
+#[test]
+fn zero_cap() {
+    let mut c: ConstLruTree<u8, u8, 0, u8> = ConstLruTree::new();
+    assert!(c.insert(1, 1).is_none());
+    assert!(c.get(&1).is_none());
+    assert!(c.iter_key_order().next().is_none());
+}
+
+#[test]
+fn clone_and_clear() {
+    let mut c: ConstLruTree<Rc<u8>, u8, 3, u8> = ConstLruTree::new();
+    let k = Rc::new(1);
+    c.insert(k.clone(), 1);
+    c.insert(Rc::new(2), 2);
+
+    let mut cloned = c.clone();
+    assert_eq!(Rc::strong_count(&k), 3);
+    for (_, v) in cloned.iter_key_order_mut() {
+        *v += 10;
+    }
+    assert_eq!(*cloned.get(&1).unwrap(), 11);
+    assert_eq!(*c.get(&1).unwrap(), 1);
+    drop(cloned);
+    assert_eq!(Rc::strong_count(&k), 2);
+
+    c.clear();
+    assert_eq!(Rc::strong_count(&k), 1);
+    assert!(c.is_empty());
+    c.insert(k.clone(), 3);
+    assert_eq!(*c.get(&1).unwrap(), 3);
+}
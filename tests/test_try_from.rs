@@ -1,17 +1,17 @@
-use const_lru::ConstLru;
+use const_lru::{ConstLru, DuplicateKeysError};
 
 #[test]
-fn try_from_zero_cap() {
+fn try_from_array_strict_zero_cap() {
     const ENTRIES: [(u8, u16); 0] = [];
-    let c: ConstLru<u8, u16, 0, u8> = ENTRIES.try_into().unwrap();
+    let c = ConstLru::<u8, u16, 0, u8>::try_from_array_strict(ENTRIES).unwrap();
     assert!(c.is_empty());
     assert!(c.is_full());
 }
 
 #[test]
-fn try_from_one_cap() {
+fn try_from_array_strict_one_cap() {
     const ENTRIES: [(u8, u16); 1] = [(1, 2)];
-    let mut c: ConstLru<u8, u16, 1, u8> = ENTRIES.try_into().unwrap();
+    let mut c = ConstLru::<u8, u16, 1, u8>::try_from_array_strict(ENTRIES).unwrap();
     assert!(c.is_full());
 
     assert_eq!(*c.get(&ENTRIES[0].0).unwrap(), ENTRIES[0].1);
@@ -26,12 +26,15 @@ fn try_from_one_cap() {
 }
 
 #[test]
-fn try_from_three_cap() {
+fn try_from_array_strict_three_cap() {
     const ENTRIES: [(u8, u16); 3] = [(1, 2), (3, 4), (5, 6)];
-    let mut c: ConstLru<u8, u16, 3, u8> = ENTRIES.try_into().unwrap();
+    let mut c = ConstLru::<u8, u16, 3, u8>::try_from_array_strict(ENTRIES).unwrap();
     assert!(c.is_full());
 
-    // check lru order
+    // check lru order, entries are MRU -> LRU like From
+    assert!(c
+        .iter()
+        .eq(ConstLru::<u8, u16, 3, u8>::from(ENTRIES).iter()));
     for (i, tup) in c.iter().enumerate() {
         assert_eq!(tup, (&ENTRIES[i].0, &ENTRIES[i].1));
     }
@@ -48,3 +51,19 @@ fn try_from_three_cap() {
     }
     assert!(c.is_empty());
 }
+
+#[test]
+fn try_from_array_strict_partial_fill() {
+    const ENTRIES: [(u8, u16); 2] = [(1, 2), (3, 4)];
+    let mut c = ConstLru::<u8, u16, 3, u8>::try_from_array_strict(ENTRIES).unwrap();
+    assert_eq!(c.len(), 2);
+    assert!(!c.is_full());
+    assert!(c.iter().eq([(&1, &2), (&3, &4)]));
+    assert!(c.insert(5, 6).is_none());
+}
+
+#[test]
+fn try_from_array_strict_duplicates() {
+    let err = ConstLru::<u8, u16, 3, u8>::try_from_array_strict([(1, 1), (2, 2), (1, 3)]);
+    assert_eq!(err.unwrap_err(), DuplicateKeysError(1));
+}